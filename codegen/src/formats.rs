@@ -0,0 +1,317 @@
+//! Parses the `VkFormat` enumerants in `vk.xml` and outputs a table of
+//! per-format metadata derived from their names.
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write, BufReader, BufWriter};
+use xml::reader::{EventReader, XmlEvent};
+use xml::attribute::OwnedAttribute;
+use INDENT;
+
+
+/// Extensions whose formats are included in `Format`.
+const FORMAT_EXTENSIONS: &[&str] = &["VK_IMG_format_pvrtc"];
+
+/// Variant names which do not follow the usual conversion.
+const VARIANT_OVERRIDES: &[(&str, &str)] = &[
+    ("R4G4B4A4_UNORM_PACK16", "R4G4B4B4UnormPack16"),
+];
+
+const NUMERIC_TYPES: &[(&str, &str)] = &[
+    ("UNORM", "Unorm"), ("SNORM", "Snorm"), ("USCALED", "Uscaled"), ("SSCALED", "Sscaled"),
+    ("UINT", "Uint"), ("SINT", "Sint"), ("UFLOAT", "Ufloat"), ("SFLOAT", "Sfloat"),
+    ("SRGB", "Srgb"),
+];
+
+
+struct Info {
+    block_size: u32,
+    block_extent: [u32; 3],
+    components: Vec<(char, u32, &'static str)>,
+    numeric_type: Option<&'static str>,
+    color: bool,
+    depth: bool,
+    stencil: bool,
+    compressed: bool,
+    packed: bool,
+}
+
+impl Info {
+    fn new() -> Info {
+        Info {
+            block_size: 0,
+            block_extent: [1, 1, 1],
+            components: Vec::new(),
+            numeric_type: None,
+            color: false,
+            depth: false,
+            stencil: false,
+            compressed: false,
+            packed: false,
+        }
+    }
+}
+
+
+fn attrib<'a>(attributes: &'a [OwnedAttribute], name: &str) -> Option<&'a str> {
+    attributes.iter().find(|a| a.name.local_name == name).map(|a| a.value.as_str())
+}
+
+/// Returns the names of all formats, without their `VK_FORMAT_` prefix, in
+/// registry order.
+fn parse_format_names() -> Vec<String> {
+    let file = File::open(concat!(env!("CARGO_MANIFEST_DIR"), "/gen_src/vk.xml")).unwrap();
+    let parser = EventReader::new(BufReader::new(file));
+
+    let mut names = Vec::with_capacity(256);
+    let mut in_format_enums = false;
+    let mut in_format_extension = false;
+
+    for e in parser {
+        match e {
+            Ok(XmlEvent::StartElement { name, attributes, .. }) => {
+                match name.local_name.as_str() {
+                    "enums" => in_format_enums = attrib(&attributes, "name") == Some("VkFormat"),
+                    "extension" => in_format_extension = attrib(&attributes, "name")
+                        .map(|n| FORMAT_EXTENSIONS.contains(&n)).unwrap_or(false),
+                    "enum" => {
+                        let extends_format = attrib(&attributes, "extends") == Some("VkFormat");
+                        if in_format_enums || (in_format_extension && extends_format) {
+                            let enum_name = attrib(&attributes, "name").unwrap();
+                            names.push(enum_name.trim_left_matches("VK_FORMAT_").to_owned());
+                        }
+                    },
+                    _ => (),
+                }
+            },
+            Ok(XmlEvent::EndElement { name }) => {
+                match name.local_name.as_str() {
+                    "enums" => in_format_enums = false,
+                    "extension" => in_format_extension = false,
+                    _ => (),
+                }
+            },
+            Err(e) => panic!("Error: {}", e),
+            _ => (),
+        }
+    }
+    names
+}
+
+/// Returns true if `token` is a sequence of components such as `R8G8B8A8`.
+fn is_component_word(token: &str) -> bool {
+    !token.is_empty() && token.starts_with(|c| "RGBADSXE".contains(c)) &&
+        token.chars().any(|c| c.is_digit(10)) &&
+        token.split(|c: char| "RGBADSXE".contains(c)).skip(1)
+            .all(|bits| !bits.is_empty() && bits.chars().all(|c| c.is_digit(10)))
+}
+
+/// Splits a component word into its components and their bit widths.
+fn components(token: &str) -> Vec<(char, u32)> {
+    let mut components = Vec::new();
+    let mut chars = token.chars().peekable();
+    while let Some(component) = chars.next() {
+        let mut bits = String::new();
+        while chars.peek().map(|c| c.is_digit(10)).unwrap_or(false) {
+            bits.push(chars.next().unwrap());
+        }
+        components.push((component, bits.parse().unwrap()));
+    }
+    components
+}
+
+fn numeric_type(token: &str) -> Option<&'static str> {
+    NUMERIC_TYPES.iter().find(|&&(t, _)| t == token).map(|&(_, v)| v)
+}
+
+/// Converts a format name to its `Format` variant name.
+fn variant_name(name: &str) -> String {
+    if let Some(&(_, variant)) = VARIANT_OVERRIDES.iter().find(|&&(n, _)| n == name) {
+        return variant.to_owned();
+    }
+    let is_astc_extent = |token: &str| token.split('x').count() == 2 &&
+        token.split('x').all(|n| !n.is_empty() && n.chars().all(|c| c.is_digit(10)));
+    name.split('_').map(|token| {
+        if is_component_word(token) || is_astc_extent(token) {
+            token.to_owned()
+        } else {
+            token[..1].to_owned() + &token[1..].to_lowercase()
+        }
+    }).collect()
+}
+
+fn compressed_info(name: &str, tokens: &[&str]) -> Info {
+    let mut info = Info::new();
+    let block_idx = tokens.iter().position(|&t| t == "BLOCK").unwrap();
+    let numeric = numeric_type(tokens[block_idx - 1]).unwrap();
+    let letters = |s: &str| s.chars().map(|c| (c, 0, numeric)).collect::<Vec<_>>();
+
+    info.compressed = true;
+    info.color = true;
+    info.numeric_type = Some(numeric);
+    info.block_extent = [4, 4, 1];
+
+    match tokens[0] {
+        "BC1" => { info.block_size = 8; info.components = letters(tokens[1]); },
+        "BC2" | "BC3" | "BC7" => { info.block_size = 16; info.components = letters("RGBA"); },
+        "BC4" => { info.block_size = 8; info.components = letters("R"); },
+        "BC5" => { info.block_size = 16; info.components = letters("RG"); },
+        "BC6H" => { info.block_size = 16; info.components = letters("RGB"); },
+        "ETC2" | "EAC" => {
+            info.block_size = if tokens[1] == "R8G8B8A8" || tokens[1] == "R11G11" { 16 } else { 8 };
+            info.components = components(tokens[1]).into_iter()
+                .map(|(c, bits)| (c, bits, numeric)).collect();
+        },
+        "ASTC" => {
+            let extent: Vec<u32> = tokens[1].split('x').map(|n| n.parse().unwrap()).collect();
+            info.block_size = 16;
+            info.block_extent = [extent[0], extent[1], 1];
+            info.components = letters("RGBA");
+        },
+        "PVRTC1" | "PVRTC2" => {
+            info.block_size = 8;
+            info.block_extent = if tokens[1] == "2BPP" { [8, 4, 1] } else { [4, 4, 1] };
+            info.components = letters("RGBA");
+        },
+        _ => panic!("unknown compressed format: {}", name),
+    }
+    info
+}
+
+fn format_info(name: &str) -> Info {
+    let tokens: Vec<&str> = name.split('_').collect();
+    if name == "UNDEFINED" {
+        return Info::new();
+    }
+    if tokens.contains(&"BLOCK") {
+        return compressed_info(name, &tokens);
+    }
+
+    let mut info = Info::new();
+    let mut pending = Vec::new();
+    let mut bits = 0;
+
+    for token in &tokens {
+        if is_component_word(token) {
+            pending.extend(components(token));
+        } else if let Some(numeric) = numeric_type(token) {
+            for (component, component_bits) in pending.drain(..) {
+                bits += component_bits;
+                // Padding and shared exponents are not components:
+                if component != 'X' && component != 'E' {
+                    info.components.push((component, component_bits, numeric));
+                }
+            }
+        } else if token.starts_with("PACK") {
+            info.packed = true;
+            info.block_size = token["PACK".len()..].parse::<u32>().unwrap() / 8;
+        } else {
+            panic!("unknown token \"{}\" in format: {}", token, name);
+        }
+    }
+    assert!(pending.is_empty(), "format has components without a numeric type: {}", name);
+
+    if !info.packed {
+        info.block_size = bits / 8;
+    }
+    info.numeric_type = Some(info.components[0].2);
+    info.depth = info.components.iter().any(|c| c.0 == 'D');
+    info.stencil = info.components.iter().any(|c| c.0 == 'S');
+    info.color = !(info.depth || info.stencil);
+    info
+}
+
+/// Returns the name of the format which is `name` with `from` replaced by
+/// `to`, if it exists.
+fn counterpart(names: &[String], name: &str, from: &str, to: &str) -> Option<String> {
+    let tokens: Vec<&str> = name.split('_').collect();
+    if !tokens.contains(&from) {
+        return None;
+    }
+    let other = tokens.iter().map(|&t| if t == from { to } else { t }).collect::<Vec<_>>()
+        .join("_");
+    if names.contains(&other) { Some(other) } else { None }
+}
+
+/// Writes the format metadata table to an output file which is overwritten
+/// if it exists.
+pub fn write_formats() -> io::Result<()> {
+    let output_file_path = "/src/voodoo/src/format_table.rs";
+    let names = parse_format_names();
+
+    let output_file = OpenOptions::new()
+        .write(true)
+        .truncate(true)
+        .create(true)
+        .open(output_file_path)
+        .unwrap();
+
+    let mut output_write = BufWriter::new(output_file);
+    let o = &mut output_write;
+    let t = INDENT;
+
+    writeln!(o, "//! Format metadata.")?;
+    writeln!(o, "//! ")?;
+    writeln!(o, "// ")?;
+    writeln!(o, "// NOTE: Do not edit this file directly; it is auto-generated.")?;
+    write!(o, "\n")?;
+    writeln!(o, "#![allow(non_upper_case_globals)]")?;
+    write!(o, "\n")?;
+    writeln!(o, "use ::Format;")?;
+    writeln!(o, "use format::{{FormatInfo, FormatComponent, Component, NumericType}};")?;
+    write!(o, "\n\n")?;
+
+    let format_variant = |other: Option<String>| match other {
+        Some(other) => format!("Some(Format::{})", variant_name(&other)),
+        None => "None".to_owned(),
+    };
+
+    for name in &names {
+        let info = format_info(name);
+        writeln!(o, "static {}: FormatInfo = FormatInfo {{", name)?;
+        writeln!(o, "{t}block_size: {},", info.block_size, t=t)?;
+        writeln!(o, "{t}block_extent: [{}, {}, {}],", info.block_extent[0], info.block_extent[1],
+            info.block_extent[2], t=t)?;
+        if info.components.is_empty() {
+            writeln!(o, "{t}components: &[],", t=t)?;
+        } else {
+            writeln!(o, "{t}components: &[", t=t)?;
+            for &(component, bits, numeric) in &info.components {
+                let component = match component {
+                    'D' => "Depth".to_owned(),
+                    'S' => "Stencil".to_owned(),
+                    c => c.to_string(),
+                };
+                writeln!(o, "{t}{t}FormatComponent {{ component: Component::{}, bits: {}, \
+                    numeric_type: NumericType::{} }},", component, bits, numeric, t=t)?;
+            }
+            writeln!(o, "{t}],", t=t)?;
+        }
+        match info.numeric_type {
+            Some(numeric) => writeln!(o, "{t}numeric_type: Some(NumericType::{}),", numeric, t=t)?,
+            None => writeln!(o, "{t}numeric_type: None,", t=t)?,
+        }
+        writeln!(o, "{t}color: {},", info.color, t=t)?;
+        writeln!(o, "{t}depth: {},", info.depth, t=t)?;
+        writeln!(o, "{t}stencil: {},", info.stencil, t=t)?;
+        writeln!(o, "{t}compressed: {},", info.compressed, t=t)?;
+        writeln!(o, "{t}packed: {},", info.packed, t=t)?;
+        writeln!(o, "{t}srgb: {},", format_variant(counterpart(&names, name, "UNORM", "SRGB")),
+            t=t)?;
+        writeln!(o, "{t}linear: {},", format_variant(counterpart(&names, name, "SRGB", "UNORM")),
+            t=t)?;
+        writeln!(o, "}};")?;
+        write!(o, "\n")?;
+    }
+
+    write!(o, "\n")?;
+    writeln!(o, "/// Returns the metadata of `format`.")?;
+    writeln!(o, "pub(crate) fn format_info(format: Format) -> &'static FormatInfo {{")?;
+    writeln!(o, "{t}match format {{", t=t)?;
+    for name in &names {
+        writeln!(o, "{t}{t}Format::{} => &{},", variant_name(name), name, t=t)?;
+    }
+    writeln!(o, "{t}}}", t=t)?;
+    writeln!(o, "}}")?;
+
+    Ok(())
+}
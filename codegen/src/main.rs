@@ -13,6 +13,8 @@ use std::collections::HashMap;
 use xml::reader::{EventReader, XmlEvent};
 use xml::attribute::OwnedAttribute;
 
+mod formats;
+
 
 const INDENT: &'static str = "    ";
 const PRINT: bool = false;
@@ -1692,6 +1694,7 @@ fn write_structs(structs: &HashMap<String,Struct>, struct_order: &[String]) -> i
 fn main() {
     let (structs, struct_order) = parse_structs();
     write_structs(&structs, &struct_order).unwrap();
+    formats::write_formats().unwrap();
 
 }
 
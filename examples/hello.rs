@@ -36,13 +36,13 @@ use vd::{vks, util, Result as VdResult, Instance, Device, SurfaceKhr,
     CullModeFlags, FrontFace, PipelineMultisampleStateCreateInfo, StencilOpState, StencilOp,
     CompareOp, PipelineDepthStencilStateCreateInfo, PipelineColorBlendAttachmentState, BlendFactor,
     BlendOp, ColorComponentFlags, PipelineColorBlendStateCreateInfo, LogicOp, CommandBuffer,
-    CommandBufferLevel, CommandBufferUsageFlags, SubmitInfo, ImageMemoryBarrier, DependencyFlags,
-    ImageSubresourceLayers, BufferImageCopy, Offset3d, Extent3d, DeviceSize, BufferCopy,
-    BufferUsageFlags, MemoryPropertyFlags, MemoryMapFlags, ImageType, Filter, SamplerMipmapMode,
+    CommandBufferLevel, CommandBufferUsageFlags, SubmitInfo,
+    Extent3d, BufferUsageFlags, MemoryPropertyFlags, ImageType, Filter, SamplerMipmapMode,
     SamplerAddressMode, BorderColor, CommandBufferHandle, ClearValue,
-    ClearColorValue, RenderPassBeginInfo, SubpassContents, IndexType, SemaphoreCreateFlags,
+    ClearColorValue, RenderPassBeginInfo, SubpassContents, SemaphoreCreateFlags,
     CallResult, PresentInfoKhr, ErrorKind, VertexInputBindingDescription, VertexInputRate,
-    VertexInputAttributeDescription};
+    VertexInputAttributeDescription, MemoryTypeSelector, Pod, TypedBuffer, IndexElement,
+    ImageTracker};
 use voodoo_winit::winit::{EventsLoop, WindowBuilder, Window, Event, WindowEvent};
 
 #[cfg(debug_assertions)]
@@ -90,6 +90,8 @@ pub struct Vertex {
     pub tex_coord: [f32; 2],
 }
 
+unsafe impl Pod for Vertex {}
+
 impl Vertex {
     pub fn binding_description() -> VertexInputBindingDescription {
         VertexInputBindingDescription::builder()
@@ -153,6 +155,8 @@ pub struct UniformBufferObject {
     pub proj: [[f32; 4]; 4],
 }
 
+unsafe impl Pod for UniformBufferObject {}
+
 
 const VERTICES: [Vertex; 8] =  [
     Vertex { pos: [-0.5, -0.5, 0.25], color: [1.0, 0.0, 0.0], tex_coord: [1.0, 0.0]},
@@ -798,125 +802,34 @@ fn end_single_time_commands(device: &Device, command_buffer: CommandBuffer) -> V
     Ok(())
 }
 
-fn has_stencil_component(format: Format) -> bool {
-    format == Format::D32SfloatS8Uint || format == Format::D24UnormS8Uint
-}
-
 fn transition_image_layout(device: &Device, command_pool: &CommandPool, image: &Image,
-        format: Format, old_layout: ImageLayout, new_layout: ImageLayout)
-         -> VdResult<()> {
+        old_layout: ImageLayout, new_layout: ImageLayout) -> VdResult<()> {
     let command_buffer = begin_single_time_commands(command_pool)?;
 
-    let subresource_range = ImageSubresourceRange::builder()
-        .aspect_mask(ImageAspectFlags::COLOR)
-        .base_mip_level(0)
-        .level_count(1)
-        .base_array_layer(0)
-        .layer_count(1)
-        .build();
-
-    let mut barrier = ImageMemoryBarrier::builder()
-        .src_access_mask(AccessFlags::empty())
-        .dst_access_mask(AccessFlags::empty())
-        .old_layout(old_layout)
-        .new_layout(new_layout)
-        .src_queue_family_index(vd::QUEUE_FAMILY_IGNORED)
-        .dst_queue_family_index(vd::QUEUE_FAMILY_IGNORED)
-        .image(image)
-        .subresource_range(subresource_range)
-        .build();
-
-    if new_layout == ImageLayout::DepthStencilAttachmentOptimal {
-        barrier.subresource_range_mut().set_aspect_mask(ImageAspectFlags::DEPTH);
-        if has_stencil_component(format) {
-            let aspect_mask = barrier.subresource_range().aspect_mask() |
-                ImageAspectFlags::STENCIL;
-            barrier.subresource_range_mut().set_aspect_mask(aspect_mask);
-        }
-    } else {
-        barrier.subresource_range_mut().set_aspect_mask(ImageAspectFlags::COLOR);
-    }
-
-    let source_stage: PipelineStageFlags;
-    let destination_stage: PipelineStageFlags;
+    let (stage_mask, access_mask) = match new_layout {
+        ImageLayout::TransferDstOptimal => {
+            (PipelineStageFlags::TRANSFER, AccessFlags::TRANSFER_WRITE)
+        },
+        ImageLayout::ShaderReadOnlyOptimal => {
+            (PipelineStageFlags::FRAGMENT_SHADER, AccessFlags::SHADER_READ)
+        },
+        ImageLayout::DepthStencilAttachmentOptimal => {
+            (PipelineStageFlags::EARLY_FRAGMENT_TESTS,
+                AccessFlags::DEPTH_STENCIL_ATTACHMENT_READ |
+                AccessFlags::DEPTH_STENCIL_ATTACHMENT_WRITE)
+        },
+        _ => panic!("unsupported layout transition"),
+    };
 
-    if old_layout == ImageLayout::Undefined &&
-            new_layout == ImageLayout::TransferDstOptimal
     {
-        barrier.set_src_access_mask(AccessFlags::empty());
-        barrier.set_dst_access_mask(AccessFlags::TRANSFER_WRITE);
-        source_stage = PipelineStageFlags::TOP_OF_PIPE;
-        destination_stage = PipelineStageFlags::TRANSFER;
-    } else if old_layout == ImageLayout::TransferDstOptimal &&
-            new_layout == ImageLayout::ShaderReadOnlyOptimal
-    {
-        barrier.set_src_access_mask(AccessFlags::TRANSFER_WRITE);
-        barrier.set_dst_access_mask(AccessFlags::SHADER_READ);
-        source_stage = PipelineStageFlags::TRANSFER;
-        destination_stage = PipelineStageFlags::FRAGMENT_SHADER;
-    } else if old_layout == ImageLayout::Undefined &&
-            new_layout == ImageLayout::DepthStencilAttachmentOptimal
-        {
-        barrier.set_src_access_mask(AccessFlags::empty());
-        barrier.set_dst_access_mask(AccessFlags::DEPTH_STENCIL_ATTACHMENT_READ |
-            AccessFlags::DEPTH_STENCIL_ATTACHMENT_WRITE);
-        source_stage = PipelineStageFlags::TOP_OF_PIPE;
-        destination_stage = PipelineStageFlags::EARLY_FRAGMENT_TESTS;
-    } else {
-        panic!("unsupported layout transition");
-    }
-
-    command_buffer.pipeline_barrier(source_stage, destination_stage,
-        DependencyFlags::empty(), &[], &[], &[barrier]);
-
-    end_single_time_commands(device, command_buffer)
-}
-
-fn copy_buffer_to_image(device: &Device, command_pool: &CommandPool, buffer: &Buffer,
-        image: &Image, width: u32, height: u32)  -> VdResult<()> {
-    let command_buffer = begin_single_time_commands(command_pool)?;
-
-    let image_subresource_layers = ImageSubresourceLayers::builder()
-        .aspect_mask(ImageAspectFlags::COLOR)
-        .mip_level(0)
-        .base_array_layer(0)
-        .layer_count(1)
-        .build();
-
-    let region = BufferImageCopy::builder()
-        .buffer_offset(0)
-        .buffer_row_length(0)
-        .buffer_image_height(0)
-        .image_subresource(image_subresource_layers)
-        .image_offset(Offset3d::builder().x(0).y(0).z(0).build())
-        .image_extent(Extent3d::builder().width(width).height(height).depth(1).build())
-        .build();
-
-    unsafe {
-        command_buffer.copy_buffer_to_image(buffer, image, ImageLayout::TransferDstOptimal,
-            &[region]);
+        let mut tracker = ImageTracker::new(&command_buffer);
+        tracker.declare(image, old_layout);
+        tracker.transition(image, new_layout, stage_mask, access_mask)?;
     }
 
     end_single_time_commands(device, command_buffer)
 }
 
-fn copy_buffer(device: &Device, command_pool: &CommandPool, src_buffer: &Buffer,
-        dst_buffer: &Buffer, size: DeviceSize)  -> VdResult<()> {
-    let command_buffer = begin_single_time_commands(command_pool)?;
-
-    let copy_region = BufferCopy::builder()
-        .src_offset(0)
-        .dst_offset(0)
-        .size(size)
-        .build();
-
-    unsafe {
-        command_buffer.copy_buffer(src_buffer, dst_buffer, &[copy_region]);
-    }
-    end_single_time_commands(device, command_buffer)
-}
-
-
 fn load_model(device: &Device, model_path: &Path) -> VdResult<(Vec<Vertex>, Vec<u32>)> {
     let (models, materials) = tobj::load_obj(model_path)
         .expect("Error loading model");
@@ -959,94 +872,20 @@ fn load_model(device: &Device, model_path: &Path) -> VdResult<(Vec<Vertex>, Vec<
     Ok((vertices, indices))
 }
 
-fn create_vertex_buffer(device: &Device, command_pool: &CommandPool, vertices: &[Vertex])
-        -> VdResult<(Buffer, DeviceMemory)> {
-    let buffer_bytes = (mem::size_of::<Vertex>() * vertices.len()) as u64;
-
-    let staging_buffer = Buffer::builder()
-        .size(buffer_bytes)
-        .usage(BufferUsageFlags::TRANSFER_SRC)
-        .sharing_mode(SharingMode::Exclusive)
-        .build(device.clone())?;
-
-    let memory_requirements = staging_buffer.memory_requirements().clone();
-    let memory_type_index = device.memory_type_index(memory_requirements.memory_type_bits(),
-        MemoryPropertyFlags::HOST_VISIBLE | MemoryPropertyFlags::HOST_COHERENT)?;
-    let staging_buffer_memory = DeviceMemory::new(device.clone(), memory_requirements.size(),
-        memory_type_index)?;
-    unsafe {
-        staging_buffer.bind_memory(&staging_buffer_memory, 0)?;
-    }
-
-    let mut data = unsafe {
-        staging_buffer_memory.map(0, buffer_bytes, MemoryMapFlags::empty())?
-    };
-    data.copy_from_slice(vertices);
-    staging_buffer_memory.unmap(data);
-
-    let vertex_buffer = Buffer::builder()
-        .size(buffer_bytes)
-        .usage(BufferUsageFlags::TRANSFER_DST | BufferUsageFlags::VERTEX_BUFFER)
-        .sharing_mode(SharingMode::Exclusive)
-        .build(device.clone())?;
-
-    let memory_requirements = vertex_buffer.memory_requirements().clone();
-    let memory_type_index = device.memory_type_index(memory_requirements.memory_type_bits(),
-        MemoryPropertyFlags::DEVICE_LOCAL)?;
-    let vertex_buffer_memory = DeviceMemory::new(device.clone(), memory_requirements.size(),
-        memory_type_index)?;
-    unsafe {
-        vertex_buffer.bind_memory(&vertex_buffer_memory, 0)?;
-    }
-
-    copy_buffer(device, command_pool, &staging_buffer, &vertex_buffer, buffer_bytes)?;
-
-    Ok((vertex_buffer, vertex_buffer_memory))
+fn create_vertex_buffer(device: &Device, vertices: &[Vertex])
+        -> VdResult<TypedBuffer<Vertex>> {
+    let (buffer, upload) = TypedBuffer::from_slice(device, BufferUsageFlags::VERTEX_BUFFER, vertices,
+        device.queue(0).unwrap())?;
+    upload.wait()?;
+    Ok(buffer)
 }
 
-fn create_index_buffer<T: Copy>(device: &Device, command_pool: &CommandPool, indices: &[T])
-        -> VdResult<(Buffer, DeviceMemory)> {
-    let buffer_bytes = (mem::size_of::<T>() * indices.len()) as u64;
-
-    let staging_buffer = Buffer::builder()
-        .size(buffer_bytes)
-        .usage(BufferUsageFlags::TRANSFER_SRC)
-        .sharing_mode(SharingMode::Exclusive)
-        .build(device.clone())?;
-
-    let memory_requirements = staging_buffer.memory_requirements().clone();
-    let memory_type_index = device.memory_type_index(memory_requirements.memory_type_bits(),
-        MemoryPropertyFlags::HOST_VISIBLE)?;
-    let staging_buffer_memory = DeviceMemory::new(device.clone(), memory_requirements.size(),
-        memory_type_index)?;
-    unsafe {
-        staging_buffer.bind_memory(&staging_buffer_memory, 0)?;
-    }
-
-    let mut data = unsafe {
-        staging_buffer_memory.map(0, buffer_bytes, MemoryMapFlags::empty())?
-    };
-    data.copy_from_slice(indices);
-    staging_buffer_memory.unmap(data);
-
-    let index_buffer = Buffer::builder()
-        .size(buffer_bytes)
-        .usage(BufferUsageFlags::TRANSFER_DST | BufferUsageFlags::INDEX_BUFFER)
-        .sharing_mode(SharingMode::Exclusive)
-        .build(device.clone())?;
-
-    let memory_requirements = index_buffer.memory_requirements().clone();
-    let memory_type_index = device.memory_type_index(memory_requirements.memory_type_bits(),
-        MemoryPropertyFlags::DEVICE_LOCAL)?;
-    let index_buffer_memory = DeviceMemory::new(device.clone(), memory_requirements.size(),
-        memory_type_index)?;
-    unsafe {
-        index_buffer.bind_memory(&index_buffer_memory, 0)?;
-    }
-
-    copy_buffer(device, command_pool, &staging_buffer, &index_buffer, buffer_bytes)?;
-
-    Ok((index_buffer, index_buffer_memory))
+fn create_index_buffer<I: IndexElement>(device: &Device, indices: &[I])
+        -> VdResult<TypedBuffer<I>> {
+    let (buffer, upload) = TypedBuffer::from_slice(device, BufferUsageFlags::INDEX_BUFFER, indices,
+        device.queue(0).unwrap())?;
+    upload.wait()?;
+    Ok(buffer)
 }
 
 fn create_uniform_buffer(device: &Device, _command_pool: &CommandPool, _extent: Extent2d)
@@ -1059,8 +898,10 @@ fn create_uniform_buffer(device: &Device, _command_pool: &CommandPool, _extent:
         .build(device.clone())?;
 
     let memory_requirements = uniform_buffer.memory_requirements().clone();
-    let memory_type_index = device.memory_type_index(memory_requirements.memory_type_bits(),
-        MemoryPropertyFlags::HOST_VISIBLE | MemoryPropertyFlags::HOST_COHERENT)?;
+    let memory_type_index = MemoryTypeSelector::new()
+        .required(MemoryPropertyFlags::HOST_VISIBLE | MemoryPropertyFlags::HOST_COHERENT)
+        .preferred(MemoryPropertyFlags::DEVICE_LOCAL)
+        .select(device, memory_requirements.memory_type_bits())?;
     let uniform_buffer_memory = DeviceMemory::new(device.clone(), memory_requirements.size(),
         memory_type_index)?;
     unsafe {
@@ -1093,8 +934,9 @@ fn create_depth_resources(device: &Device, command_pool: &CommandPool,
         .build(device.clone())?;
 
     let memory_requirements = depth_image.memory_requirements().clone();
-    let memory_type_index = device.memory_type_index(memory_requirements.memory_type_bits(),
-        MemoryPropertyFlags::DEVICE_LOCAL)?;
+    let memory_type_index = MemoryTypeSelector::new()
+        .required(MemoryPropertyFlags::DEVICE_LOCAL)
+        .select(device, memory_requirements.memory_type_bits())?;
     let depth_image_memory = DeviceMemory::new(device.clone(), memory_requirements.size(),
         memory_type_index)?;
     unsafe {
@@ -1115,74 +957,41 @@ fn create_depth_resources(device: &Device, command_pool: &CommandPool,
             .build())
         .build(device.clone(), None)?;
 
-    transition_image_layout(device, command_pool, &depth_image, depth_format,
-        ImageLayout::Undefined, ImageLayout::DepthStencilAttachmentOptimal)?;
+    transition_image_layout(device, command_pool, &depth_image, ImageLayout::Undefined,
+        ImageLayout::DepthStencilAttachmentOptimal)?;
 
     Ok((depth_image, depth_image_memory, depth_image_view))
 }
 
-fn create_texture_image(device: &Device, command_pool: &CommandPool)
-        -> VdResult<(Image, DeviceMemory)> {
+fn create_texture_image(device: &Device, command_pool: &CommandPool) -> VdResult<Image> {
     let pixels = image::open(TEXTURE_PATH).unwrap().to_rgba();
     let (tex_width, tex_height) = pixels.dimensions();
-    let image_bytes = (tex_width * tex_height * 4) as u64;
-
-    let staging_buffer = Buffer::builder()
-        .size(image_bytes)
-        .usage(BufferUsageFlags::TRANSFER_SRC)
-        .sharing_mode(SharingMode::Exclusive)
-        .build(device.clone())?;
-
-    let memory_requirements = staging_buffer.memory_requirements().clone();
-    let memory_type_index = device.memory_type_index(memory_requirements.memory_type_bits(),
-        MemoryPropertyFlags::HOST_VISIBLE | MemoryPropertyFlags::HOST_COHERENT)?;
-    let staging_buffer_memory = DeviceMemory::new(device.clone(), memory_requirements.size(),
-        memory_type_index)?;
-    unsafe {
-        staging_buffer.bind_memory(&staging_buffer_memory, 0)?;
-    }
-
-    let mut data = unsafe {
-        staging_buffer_memory.map(0, image_bytes, MemoryMapFlags::empty())?
-    };
-    data.copy_from_slice(&pixels);
-    staging_buffer_memory.unmap(data);
-
     let extent = Extent3d::builder().width(tex_width).height(tex_height).depth(1).build();
+    let mip_levels = 32 - cmp::max(tex_width, tex_height).leading_zeros();
 
-    let texture_image = Image::builder()
-        .image_type(ImageType::Type2d)
+    let mut builder = Image::builder();
+    builder.image_type(ImageType::Type2d)
         // .format(Format::R8G8B8A8Unorm)
         .format(Format::R8G8B8A8Srgb)
-        .extent(extent.clone())
-        .mip_levels(1)
+        .extent(extent)
+        .mip_levels(mip_levels)
         .array_layers(1)
         .samples(SampleCountFlags::COUNT_1)
         .tiling(ImageTiling::Optimal)
-        .usage(ImageUsageFlags::TRANSFER_DST | ImageUsageFlags::SAMPLED)
+        .usage(ImageUsageFlags::SAMPLED | ImageUsageFlags::TRANSFER_SRC)
         .sharing_mode(SharingMode::Exclusive)
-        .initial_layout(ImageLayout::Undefined)
-        .build(device.clone())?;
-
-    let memory_requirements = texture_image.memory_requirements().clone();
-    let memory_type_index = device.memory_type_index(memory_requirements.memory_type_bits(),
-        MemoryPropertyFlags::DEVICE_LOCAL)?;
-    let texture_image_memory = DeviceMemory::new(device.clone(), memory_requirements.size(),
-        memory_type_index)?;
-    unsafe {
-        texture_image.bind_memory(&texture_image_memory, 0)?;
-    }
-
-    transition_image_layout(device, command_pool, &texture_image, Format::R8G8B8A8Unorm,
-        ImageLayout::Undefined, ImageLayout::TransferDstOptimal)?;
+        .initial_layout(ImageLayout::Undefined);
 
-    copy_buffer_to_image(device, command_pool, &staging_buffer, &texture_image,
-        extent.width(), extent.height())?;
+    let (texture_image, upload) = Image::from_pixels(device, &builder, &*pixels,
+        device.queue(0).unwrap())?;
+    upload.wait()?;
 
-    transition_image_layout(device, command_pool, &texture_image, Format::R8G8B8A8Unorm,
-        ImageLayout::TransferDstOptimal, ImageLayout::ShaderReadOnlyOptimal)?;
+    // With several mip levels the image is left in `TransferDstOptimal`:
+    let command_buffer = begin_single_time_commands(command_pool)?;
+    let _mipmaps = texture_image.generate_mipmaps(&command_buffer, Filter::Linear)?;
+    end_single_time_commands(device, command_buffer)?;
 
-    Ok((texture_image, texture_image_memory))
+    Ok(texture_image)
 }
 
 fn create_texture_image_view(device: Device, image: &Image) -> VdResult<ImageView> {
@@ -1195,14 +1004,14 @@ fn create_texture_image_view(device: Device, image: &Image) -> VdResult<ImageVie
         .subresource_range(ImageSubresourceRange::builder()
             .aspect_mask(ImageAspectFlags::COLOR)
             .base_mip_level(0)
-            .level_count(1)
+            .level_count(image.mip_levels())
             .base_array_layer(0)
             .layer_count(1)
             .build())
         .build(device, None)
 }
 
-fn create_texture_sampler(device: Device) -> VdResult<Sampler> {
+fn create_texture_sampler(device: Device, mip_levels: u32) -> VdResult<Sampler> {
     Sampler::builder()
         .mag_filter(Filter::Linear)
         .min_filter(Filter::Linear)
@@ -1216,7 +1025,7 @@ fn create_texture_sampler(device: Device) -> VdResult<Sampler> {
         .compare_enable(false)
         .compare_op(CompareOp::Always)
         .min_lod(0.)
-        .max_lod(0.)
+        .max_lod(mip_levels as f32)
         .border_color(BorderColor::IntOpaqueBlack)
         .unnormalized_coordinates(false)
         .build(device)
@@ -1226,7 +1035,7 @@ fn create_texture_sampler(device: Device) -> VdResult<Sampler> {
 pub fn create_command_buffers(device: &Device, command_pool: &CommandPool,
         render_pass: &RenderPass, graphics_pipeline: &GraphicsPipeline,
         swapchain_framebuffers: &[Framebuffer], swapchain_extent: &Extent2d,
-        vertex_buffer: &Buffer, index_buffer: &Buffer, vertex_count: u32,
+        vertex_buffer: &TypedBuffer<Vertex>, index_buffer: &TypedBuffer<u32>, vertex_count: u32,
         index_count: u32, pipeline_layout: &PipelineLayout,
         descriptor_set: DescriptorSet)
         -> VdResult<SmallVec<[CommandBuffer; 16]>>
@@ -1234,8 +1043,6 @@ pub fn create_command_buffers(device: &Device, command_pool: &CommandPool,
     let command_buffers = command_pool.allocate_command_buffers(CommandBufferLevel::Primary,
             swapchain_framebuffers.len() as u32)?;
 
-    let vertex_buffers = [vertex_buffer];
-    let offsets = [0];
     let descriptor_sets = [&descriptor_set];
 
     for (cmd_buf, swapchain_framebuffer) in command_buffers.iter()
@@ -1262,8 +1069,8 @@ pub fn create_command_buffers(device: &Device, command_pool: &CommandPool,
 
         cmd_buf.begin_render_pass(&render_pass_info, SubpassContents::Inline);
         cmd_buf.bind_pipeline(PipelineBindPoint::Graphics, &graphics_pipeline);
-        cmd_buf.bind_vertex_buffers(0, &vertex_buffers, &offsets);
-        cmd_buf.bind_index_buffer(index_buffer, 0, IndexType::Uint32);
+        cmd_buf.bind_vertex_buffer_slice(0, vertex_buffer.as_slice());
+        cmd_buf.bind_index_buffer_slice(index_buffer.as_slice());
         cmd_buf.bind_descriptor_sets(PipelineBindPoint::Graphics, pipeline_layout,
             0, &descriptor_sets, &[]);
 
@@ -1299,15 +1106,12 @@ struct App {
     frag_shader_code: Vec<u32>,
     command_pool: CommandPool,
     texture_image: Image,
-    texture_image_memory: DeviceMemory,
     texture_image_view: ImageView,
     texture_sampler: Sampler,
     vertices: Vec<Vertex>,
     indices: Vec<u32>,
-    vertex_buffer: Buffer,
-    vertex_buffer_memory: DeviceMemory,
-    index_buffer: Buffer,
-    index_buffer_memory: DeviceMemory,
+    vertex_buffer: TypedBuffer<Vertex>,
+    index_buffer: TypedBuffer<u32>,
     uniform_buffer: Buffer,
     uniform_buffer_memory: DeviceMemory,
     descriptor_pool: DescriptorPool,
@@ -1343,18 +1147,15 @@ impl App {
             &command_pool, swapchain.extent().clone())?;
         let framebuffers = create_framebuffers(&device, &render_pass,
             &image_views, &depth_image_view, swapchain.extent().clone())?;
-        let (texture_image, texture_image_memory) = create_texture_image(&device,
-            &command_pool)?;
+        let texture_image = create_texture_image(&device, &command_pool)?;
         let texture_image_view = create_texture_image_view(device.clone(),
             &texture_image)?;
-        let texture_sampler = create_texture_sampler(device.clone())?;
+        let texture_sampler = create_texture_sampler(device.clone(), texture_image.mip_levels())?;
         // let (vertices, indices) = load_model(&device, &Path::new(MODEL_PATH))?;
         let vertices = VERTICES[..].to_owned();
         let indices = INDICES[..].to_owned();
-        let (vertex_buffer, vertex_buffer_memory) = create_vertex_buffer(&device, &command_pool,
-            &vertices)?;
-        let (index_buffer, index_buffer_memory) = create_index_buffer(&device, &command_pool,
-            &indices)?;
+        let vertex_buffer = create_vertex_buffer(&device, &vertices)?;
+        let index_buffer = create_index_buffer(&device, &indices)?;
         let (uniform_buffer, uniform_buffer_memory) = create_uniform_buffer(&device,
             &command_pool, swapchain.extent().clone())?;
         let descriptor_pool = create_descriptor_pool(device.clone())?;
@@ -1395,15 +1196,12 @@ impl App {
             frag_shader_code,
             command_pool,
             texture_image,
-            texture_image_memory,
             texture_image_view,
             texture_sampler,
             vertices: vertices,
             indices: indices,
             vertex_buffer,
-            vertex_buffer_memory,
             index_buffer,
-            index_buffer_memory,
             uniform_buffer,
             uniform_buffer_memory,
             descriptor_pool,
@@ -1488,12 +1286,8 @@ impl App {
             proj: proj.into(),
         };
 
-        let mut data = unsafe {
-            self.uniform_buffer_memory.map(0, mem::size_of::<UniformBufferObject>() as u64,
-                MemoryMapFlags::empty())?
-        };
-        data.copy_from_slice(&[ubo]);
-        self.uniform_buffer_memory.unmap(data);
+        let ubo_bytes = mem::size_of::<UniformBufferObject>() as u64;
+        self.uniform_buffer_memory.persistent_mapping()?.write(0..ubo_bytes, &[ubo])?;
 
         Ok(())
     }
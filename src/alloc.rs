@@ -0,0 +1,1044 @@
+//! Device memory sub-allocation.
+//!
+//! An `Allocator` allocates large blocks of `DeviceMemory` per memory type
+//! and hands out sub-ranges of them using a buddy strategy. Resources larger
+//! than the dedicated threshold receive their own `DeviceMemory`.
+//!
+//! Each sub-range is owned by an `Allocation` which returns the range to its
+//! block when dropped. Use `Buffer::new_allocated` and `Image::new_allocated`
+//! to create resources which keep their allocation alive.
+//!
+//! A `Defragmenter` compacts blocks by relocating movable resources out of
+//! sparsely used blocks so that those blocks can be released.
+//!
+//! ### Buffer-image granularity
+//!
+//! When `bufferImageGranularity` is greater than one, linear resources
+//! (buffers and linearly tiled images) and non-linear resources (optimally
+//! tiled images) are allocated from separate blocks so that they can never
+//! share a page.
+//!
+
+use std::cmp;
+use std::mem;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Arc, Mutex};
+use ::{VdResult, Device, DeviceMemory, DeviceSize, Error, ErrorKind, CallResult,
+    MemoryRequirements, MemoryTypeSelector, PhysicalDeviceMemoryProperties, Buffer, Image,
+    CommandBuffer, Fence, FenceStatus, ImageLayout, AccessFlags,
+    PipelineStageFlags, DependencyFlags, MemoryBarrier, ImageMemoryBarrier, ImageSubresourceRange,
+    ImageSubresourceLayers, ImageCopy, BufferCopy, Offset3d, Extent3d, BufferUsageFlags,
+    ImageUsageFlags, QUEUE_FAMILY_IGNORED};
+
+
+/// The default size of each block of device memory.
+pub const DEFAULT_BLOCK_SIZE: DeviceSize = 256 * 1024 * 1024;
+
+/// The smallest range handed out from a block. All ranges are a power of two
+/// multiple of this size.
+pub const MIN_NODE_SIZE: DeviceSize = 256;
+
+
+/// Returns the largest power of two less than or equal to `val`.
+fn prev_power_of_two(val: DeviceSize) -> DeviceSize {
+    if val == 0 { 0 } else { 1 << (63 - val.leading_zeros()) }
+}
+
+/// Returns true if `err` indicates that an allocation failed for lack of
+/// memory (or allocation slots).
+fn is_out_of_memory(err: &Error) -> bool {
+    match *err.kind() {
+        ErrorKind::ApiCall(CallResult::ErrorOutOfDeviceMemory, _) |
+        ErrorKind::ApiCall(CallResult::ErrorOutOfHostMemory, _) |
+        ErrorKind::ApiCall(CallResult::ErrorTooManyObjects, _) => true,
+        _ => false,
+    }
+}
+
+
+/// The free lists of a buddy allocator managing a power of two sized range.
+#[derive(Debug, Clone)]
+struct Buddy {
+    max_order: u32,
+    // Offsets of free nodes, indexed by order (node size is
+    // `MIN_NODE_SIZE << order`).
+    free_lists: Vec<BTreeSet<DeviceSize>>,
+    allocated_bytes: DeviceSize,
+}
+
+impl Buddy {
+    fn new(size: DeviceSize) -> Buddy {
+        debug_assert!(size.is_power_of_two() && size >= MIN_NODE_SIZE);
+        let max_order = (size / MIN_NODE_SIZE).trailing_zeros();
+        let mut free_lists = vec![BTreeSet::new(); max_order as usize + 1];
+        free_lists[max_order as usize].insert(0);
+
+        Buddy { max_order, free_lists, allocated_bytes: 0 }
+    }
+
+    /// Returns the order of the smallest node satisfying both the size and
+    /// alignment of a request.
+    ///
+    /// Nodes of order `k` always begin at a multiple of their own size,
+    /// which satisfies any alignment up to and including that size.
+    fn order_for(size: DeviceSize, alignment: DeviceSize) -> u32 {
+        let node_size = cmp::max(cmp::max(size, alignment), MIN_NODE_SIZE).next_power_of_two();
+        (node_size / MIN_NODE_SIZE).trailing_zeros()
+    }
+
+    fn allocate(&mut self, order: u32) -> Option<DeviceSize> {
+        if order > self.max_order { return None; }
+
+        let mut k = order;
+        while self.free_lists[k as usize].is_empty() {
+            k += 1;
+            if k > self.max_order { return None; }
+        }
+
+        let offset = *self.free_lists[k as usize].iter().next().unwrap();
+        self.free_lists[k as usize].remove(&offset);
+
+        // Split until the node is of the requested order, freeing the upper
+        // halves:
+        while k > order {
+            k -= 1;
+            self.free_lists[k as usize].insert(offset + (MIN_NODE_SIZE << k));
+        }
+
+        self.allocated_bytes += MIN_NODE_SIZE << order;
+        Some(offset)
+    }
+
+    fn free(&mut self, mut offset: DeviceSize, mut order: u32) {
+        self.allocated_bytes -= MIN_NODE_SIZE << order;
+
+        // Merge with free buddies:
+        while order < self.max_order {
+            let buddy = offset ^ (MIN_NODE_SIZE << order);
+            if self.free_lists[order as usize].remove(&buddy) {
+                offset = cmp::min(offset, buddy);
+                order += 1;
+            } else {
+                break;
+            }
+        }
+        self.free_lists[order as usize].insert(offset);
+    }
+
+    fn is_empty(&self) -> bool {
+        self.allocated_bytes == 0
+    }
+}
+
+
+/// A single block of device memory managed with a buddy allocator.
+#[derive(Debug)]
+struct Block {
+    id: u64,
+    memory: DeviceMemory,
+    buddy: Buddy,
+}
+
+impl Block {
+    fn new(id: u64, memory: DeviceMemory, size: DeviceSize) -> Block {
+        Block { id, memory, buddy: Buddy::new(size) }
+    }
+}
+
+
+/// Identifies the set of blocks an allocation may come from.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+struct PoolKey {
+    memory_type_index: u32,
+    linear: bool,
+}
+
+
+#[derive(Debug)]
+struct State {
+    pools: HashMap<PoolKey, Vec<Block>>,
+    next_block_id: u64,
+}
+
+
+#[derive(Debug)]
+struct Inner {
+    device: Device,
+    memory_properties: PhysicalDeviceMemoryProperties,
+    buffer_image_granularity: DeviceSize,
+    block_size: DeviceSize,
+    dedicated_threshold: DeviceSize,
+    state: Mutex<State>,
+}
+
+
+/// A device memory sub-allocator.
+///
+/// `Allocator` is cheaply clonable and may be shared between threads.
+///
+#[derive(Debug, Clone)]
+pub struct Allocator {
+    inner: Arc<Inner>,
+}
+
+impl Allocator {
+    /// Returns a new `AllocatorBuilder`.
+    pub fn builder() -> AllocatorBuilder {
+        AllocatorBuilder::new()
+    }
+
+    /// Returns a new `Allocator` with default settings.
+    pub fn new(device: Device) -> VdResult<Allocator> {
+        AllocatorBuilder::new().build(device)
+    }
+
+    /// Allocates a range of memory satisfying `requirements` from the best
+    /// memory type chosen by `memory_type`.
+    ///
+    /// `linear` must be `true` for buffers and linearly tiled images and
+    /// `false` for optimally tiled images.
+    ///
+    /// If a memory type runs out of memory, the next best one is tried.
+    pub fn allocate(&self, requirements: &MemoryRequirements, memory_type: &MemoryTypeSelector,
+            linear: bool) -> VdResult<Allocation> {
+        let dedicated = requirements.size() >= self.inner.dedicated_threshold;
+        self.allocate_inner(requirements, memory_type, linear, dedicated)
+    }
+
+    /// Allocates a dedicated `DeviceMemory` for a single resource.
+    pub fn allocate_dedicated(&self, requirements: &MemoryRequirements,
+            memory_type: &MemoryTypeSelector) -> VdResult<Allocation> {
+        self.allocate_inner(requirements, memory_type, true, true)
+    }
+
+    fn allocate_inner(&self, requirements: &MemoryRequirements, memory_type: &MemoryTypeSelector,
+            linear: bool, dedicated: bool) -> VdResult<Allocation> {
+        let candidates = memory_type.rank(&self.inner.memory_properties,
+            requirements.memory_type_bits());
+        if candidates.is_empty() {
+            return Err(ErrorKind::NoSuitableMemoryType {
+                type_filter: requirements.memory_type_bits(),
+                required: memory_type.required_flags(),
+                forbidden: memory_type.forbidden_flags(),
+            }.into());
+        }
+
+        let mut last_err = None;
+        for &memory_type_index in candidates.iter() {
+            let res = if dedicated {
+                self.allocate_dedicated_from(requirements, memory_type_index)
+            } else {
+                self.allocate_from_block(requirements, memory_type_index, linear)
+            };
+
+            match res {
+                Ok(allocation) => return Ok(allocation),
+                Err(err) => {
+                    if !is_out_of_memory(&err) { return Err(err); }
+                    last_err = Some(err);
+                },
+            }
+        }
+        Err(last_err.unwrap())
+    }
+
+    fn allocate_dedicated_from(&self, requirements: &MemoryRequirements, memory_type_index: u32)
+            -> VdResult<Allocation> {
+        Allocation::dedicated(self.inner.device.clone(), requirements, memory_type_index)
+    }
+
+    fn allocate_from_block(&self, requirements: &MemoryRequirements, memory_type_index: u32,
+            linear: bool) -> VdResult<Allocation> {
+        let key = PoolKey {
+            memory_type_index,
+            linear: linear || self.inner.buffer_image_granularity <= 1,
+        };
+        let order = Buddy::order_for(requirements.size(), requirements.alignment());
+        let node_size = MIN_NODE_SIZE << order;
+
+        let mut state = self.inner.state.lock().unwrap();
+
+        {
+            let blocks = state.pools.entry(key).or_insert_with(Vec::new);
+            for block in blocks.iter_mut() {
+                if let Some(offset) = block.buddy.allocate(order) {
+                    return Ok(Allocation {
+                        memory: block.memory.clone(),
+                        offset,
+                        size: requirements.size(),
+                        memory_type_index,
+                        kind: AllocationKind::Block {
+                            allocator: self.clone(),
+                            key,
+                            block_id: block.id,
+                            order,
+                        },
+                    });
+                }
+            }
+        }
+
+        // No room in existing blocks. Create a new one no larger than an
+        // eighth of its heap, halving its size on failure:
+        let heap_index = self.inner.memory_properties.memory_types()[memory_type_index as usize]
+            .heap_index();
+        let heap_size = self.inner.memory_properties.memory_heaps()[heap_index as usize].size();
+        let mut block_size = cmp::min(self.inner.block_size, prev_power_of_two(heap_size / 8));
+        block_size = cmp::max(block_size, node_size);
+
+        let memory = loop {
+            match DeviceMemory::new(self.inner.device.clone(), block_size, memory_type_index) {
+                Ok(memory) => break memory,
+                Err(err) => {
+                    if is_out_of_memory(&err) && block_size / 2 >= node_size {
+                        block_size /= 2;
+                    } else {
+                        return Err(err);
+                    }
+                },
+            }
+        };
+
+        let block_id = state.next_block_id;
+        state.next_block_id += 1;
+        let mut block = Block::new(block_id, memory, block_size);
+        let offset = block.buddy.allocate(order).expect("new block too small");
+        let memory = block.memory.clone();
+        state.pools.get_mut(&key).unwrap().push(block);
+
+        Ok(Allocation {
+            memory,
+            offset,
+            size: requirements.size(),
+            memory_type_index,
+            kind: AllocationKind::Block { allocator: self.clone(), key, block_id, order },
+        })
+    }
+
+    /// Allocates a node of `order` from an existing block of the pool `key`,
+    /// skipping the blocks in `excluded`, and returns it along with the id of
+    /// its block. Never creates a new block.
+    fn allocate_in_pool(&self, key: PoolKey, order: u32, size: DeviceSize, excluded: &[u64])
+            -> Option<(u64, Allocation)> {
+        let mut state = self.inner.state.lock().unwrap();
+        let blocks = match state.pools.get_mut(&key) {
+            Some(blocks) => blocks,
+            None => return None,
+        };
+
+        // Prefer the fullest blocks:
+        let mut indices: Vec<usize> = (0..blocks.len())
+            .filter(|&i| !excluded.contains(&blocks[i].id))
+            .collect();
+        indices.sort_by(|&a, &b| {
+            blocks[b].buddy.allocated_bytes.cmp(&blocks[a].buddy.allocated_bytes)
+        });
+
+        for i in indices {
+            let block = &mut blocks[i];
+            if let Some(offset) = block.buddy.allocate(order) {
+                return Some((block.id, Allocation {
+                    memory: block.memory.clone(),
+                    offset,
+                    size,
+                    memory_type_index: key.memory_type_index,
+                    kind: AllocationKind::Block {
+                        allocator: self.clone(),
+                        key,
+                        block_id: block.id,
+                        order,
+                    },
+                }));
+            }
+        }
+        None
+    }
+
+    /// Returns the ids and allocated byte counts of the blocks of each pool.
+    fn block_usage(&self) -> HashMap<PoolKey, Vec<(u64, DeviceSize)>> {
+        let state = self.inner.state.lock().unwrap();
+        state.pools.iter().map(|(&key, blocks)| {
+            (key, blocks.iter().map(|b| (b.id, b.buddy.allocated_bytes)).collect())
+        }).collect()
+    }
+
+    /// Returns a range to its block, releasing the block if it is empty and
+    /// not the only block in its pool.
+    fn free(&self, key: PoolKey, block_id: u64, offset: DeviceSize, order: u32) {
+        let mut state = self.inner.state.lock().unwrap();
+        let blocks = state.pools.get_mut(&key).expect("Allocator::free: unknown pool");
+        let block_idx = blocks.iter().position(|b| b.id == block_id)
+            .expect("Allocator::free: unknown block");
+        blocks[block_idx].buddy.free(offset, order);
+
+        if blocks[block_idx].buddy.is_empty() && blocks.len() > 1 {
+            blocks.swap_remove(block_idx);
+        }
+    }
+
+    /// Returns the size of newly created blocks.
+    pub fn block_size(&self) -> DeviceSize {
+        self.inner.block_size
+    }
+
+    /// Returns the size at or above which resources receive a dedicated
+    /// allocation.
+    pub fn dedicated_threshold(&self) -> DeviceSize {
+        self.inner.dedicated_threshold
+    }
+
+    /// Returns a reference to the associated device.
+    pub fn device(&self) -> &Device {
+        &self.inner.device
+    }
+}
+
+
+/// A builder for `Allocator`.
+#[derive(Debug, Clone)]
+pub struct AllocatorBuilder {
+    block_size: DeviceSize,
+    dedicated_threshold: Option<DeviceSize>,
+}
+
+impl AllocatorBuilder {
+    /// Returns a new allocator builder.
+    pub fn new() -> AllocatorBuilder {
+        AllocatorBuilder {
+            block_size: DEFAULT_BLOCK_SIZE,
+            dedicated_threshold: None,
+        }
+    }
+
+    /// Specifies the size of each block of device memory. Rounded up to the
+    /// next power of two. Defaults to `DEFAULT_BLOCK_SIZE`.
+    pub fn block_size<'s>(&'s mut self, block_size: DeviceSize) -> &'s mut AllocatorBuilder {
+        self.block_size = cmp::max(block_size, MIN_NODE_SIZE).next_power_of_two();
+        self
+    }
+
+    /// Specifies the size at or above which resources receive a dedicated
+    /// allocation. Defaults to half the block size.
+    pub fn dedicated_threshold<'s>(&'s mut self, dedicated_threshold: DeviceSize)
+            -> &'s mut AllocatorBuilder {
+        self.dedicated_threshold = Some(dedicated_threshold);
+        self
+    }
+
+    /// Creates and returns a new `Allocator`.
+    pub fn build(&self, device: Device) -> VdResult<Allocator> {
+        let memory_properties = device.physical_device().memory_properties();
+        let buffer_image_granularity = device.physical_device().properties().limits()
+            .buffer_image_granularity();
+        let dedicated_threshold = self.dedicated_threshold.unwrap_or(self.block_size / 2);
+
+        Ok(Allocator {
+            inner: Arc::new(Inner {
+                device,
+                memory_properties,
+                buffer_image_granularity,
+                block_size: self.block_size,
+                dedicated_threshold,
+                state: Mutex::new(State { pools: HashMap::new(), next_block_id: 0 }),
+            })
+        })
+    }
+}
+
+
+#[derive(Debug)]
+enum AllocationKind {
+    Dedicated,
+    Block { allocator: Allocator, key: PoolKey, block_id: u64, order: u32 },
+}
+
+
+/// A range of device memory owned by a single resource.
+///
+///
+/// ### Destruction
+///
+/// Dropping this `Allocation` returns its range to the `Allocator` it came
+/// from (or frees its memory if it is a dedicated allocation). Any resource
+/// bound to it must be destroyed first.
+///
+#[derive(Debug)]
+pub struct Allocation {
+    memory: DeviceMemory,
+    offset: DeviceSize,
+    size: DeviceSize,
+    memory_type_index: u32,
+    kind: AllocationKind,
+}
+
+impl Allocation {
+    /// Allocates a new `DeviceMemory` of the size given by `requirements`
+    /// which is not part of any `Allocator`.
+    pub(crate) fn dedicated(device: Device, requirements: &MemoryRequirements,
+            memory_type_index: u32) -> VdResult<Allocation> {
+        let memory = DeviceMemory::new(device, requirements.size(), memory_type_index)?;
+
+        Ok(Allocation {
+            memory,
+            offset: 0,
+            size: requirements.size(),
+            memory_type_index,
+            kind: AllocationKind::Dedicated,
+        })
+    }
+
+    /// Returns the memory object this allocation is a part of.
+    pub fn memory(&self) -> &DeviceMemory {
+        &self.memory
+    }
+
+    /// Returns the offset in bytes of this allocation within its memory
+    /// object.
+    pub fn offset(&self) -> DeviceSize {
+        self.offset
+    }
+
+    /// Returns the requested size in bytes of this allocation.
+    pub fn size(&self) -> DeviceSize {
+        self.size
+    }
+
+    /// Returns the index of the memory type this allocation was made from.
+    pub fn memory_type_index(&self) -> u32 {
+        self.memory_type_index
+    }
+
+    /// Returns `true` if this allocation has its own `DeviceMemory`.
+    pub fn is_dedicated(&self) -> bool {
+        match self.kind {
+            AllocationKind::Dedicated => true,
+            AllocationKind::Block { .. } => false,
+        }
+    }
+}
+
+impl Drop for Allocation {
+    fn drop(&mut self) {
+        if let AllocationKind::Block { ref allocator, key, block_id, order } = self.kind {
+            allocator.free(key, block_id, self.offset, order);
+        }
+    }
+}
+
+
+/// A resource which can be relocated by a `Defragmenter`.
+#[derive(Debug, Clone)]
+pub enum Resource {
+    Buffer(Buffer),
+    /// An image and the layout it will be in when the copy commands execute.
+    Image(Image, ImageLayout),
+}
+
+impl Resource {
+    fn allocation(&self) -> Option<&Allocation> {
+        match *self {
+            Resource::Buffer(ref buffer) => buffer.allocation(),
+            Resource::Image(ref image, _) => image.allocation(),
+        }
+    }
+
+    /// Returns `true` if this resource can be copied to a new location.
+    fn is_copyable(&self) -> bool {
+        match *self {
+            Resource::Buffer(ref buffer) => buffer.usage().contains(
+                BufferUsageFlags::TRANSFER_SRC | BufferUsageFlags::TRANSFER_DST),
+            Resource::Image(ref image, _) => image.usage().contains(
+                ImageUsageFlags::TRANSFER_SRC | ImageUsageFlags::TRANSFER_DST),
+        }
+    }
+}
+
+
+/// A resource relocated by a defragmentation pass.
+///
+/// The `new` resource is identical to the `old` one (created with the same
+/// parameters) but is bound to a different range of memory. Image layouts are
+/// preserved.
+#[derive(Debug, Clone)]
+pub struct Relocation {
+    old: Resource,
+    new: Resource,
+}
+
+impl Relocation {
+    /// Returns the resource as it was before being moved.
+    pub fn old_resource(&self) -> &Resource {
+        &self.old
+    }
+
+    /// Returns the resource at its new location.
+    pub fn new_resource(&self) -> &Resource {
+        &self.new
+    }
+}
+
+
+/// Chooses the blocks of a pool to evacuate and allocates a new location
+/// for each of their movable ranges.
+///
+/// `usage` holds the id and allocated byte count of each block of the pool
+/// and `movable` the movable ranges (and their orders) within each block.
+/// `place` allocates a node of the given order for a range outside of the
+/// `excluded` blocks and returns it along with the id of its block.
+///
+/// Returns each evacuated block along with the new allocations of its ranges,
+/// in the order given by `movable`. The number of bytes moved is subtracted
+/// from `budget`.
+///
+/// The emptiest blocks are evacuated first and the fullest never is. A block
+/// which received relocated ranges is never evacuated since its contents are
+/// no longer reflected by `usage`.
+fn plan_pool<R, A, F>(mut usage: Vec<(u64, DeviceSize)>, movable: &HashMap<u64, Vec<(R, u32)>>,
+        budget: &mut DeviceSize, mut place: F) -> Vec<(u64, Vec<A>)>
+        where F: FnMut(&R, u32, &[u64]) -> Option<(u64, A)> {
+    let mut evacuated = Vec::new();
+    if usage.len() < 2 { return evacuated; }
+    usage.sort_by_key(|&(_, allocated_bytes)| allocated_bytes);
+    usage.pop();
+
+    let mut excluded: Vec<u64> = Vec::new();
+    let mut destinations: Vec<u64> = Vec::new();
+    for (block_id, allocated_bytes) in usage {
+        if destinations.contains(&block_id) { continue; }
+        let ranges = match movable.get(&block_id) {
+            Some(ranges) => ranges,
+            None => continue,
+        };
+        let movable_bytes: DeviceSize = ranges.iter()
+            .map(|&(_, order)| MIN_NODE_SIZE << order)
+            .sum();
+        if movable_bytes != allocated_bytes || movable_bytes > *budget { continue; }
+
+        excluded.push(block_id);
+        let mut placed = Vec::with_capacity(ranges.len());
+        for &(ref range, order) in ranges.iter() {
+            match place(range, order, &excluded) {
+                Some(p) => placed.push(p),
+                None => break,
+            }
+        }
+        // Leave the block in place (dropping any new allocations) if its
+        // contents do not fit elsewhere:
+        if placed.len() != ranges.len() {
+            excluded.pop();
+            continue;
+        }
+
+        destinations.extend(placed.iter().map(|&(id, _)| id));
+        *budget -= movable_bytes;
+        evacuated.push((block_id, placed.into_iter().map(|(_, a)| a).collect()));
+    }
+    evacuated
+}
+
+
+/// Plans and records the relocation of resources in order to release sparsely
+/// used blocks of device memory.
+///
+/// Only resources created with `Buffer::new_allocated` or
+/// `Image::new_allocated` from the same `Allocator`, not dedicated, and with
+/// both `TRANSFER_SRC` and `TRANSFER_DST` usage are moved. A block is only
+/// evacuated if every allocation within it belongs to a movable resource.
+///
+/// ## Example
+///
+/// ```text
+/// let mut defrag = Defragmenter::new(&allocator);
+/// defrag.buffer(&vertex_buffer).image(&texture, ImageLayout::ShaderReadOnlyOptimal);
+///
+/// command_buffer.begin(CommandBufferUsageFlags::ONE_TIME_SUBMIT)?;
+/// let mut pass = defrag.record(&command_buffer)?;
+/// command_buffer.end()?;
+/// queue.submit(&[submit_info], Some(fence.handle()))?;
+///
+/// // Later:
+/// pass.poll(&fence, |relocation| {
+///     // Replace `relocation.old_resource()` with `relocation.new_resource()`
+///     // and update any descriptor sets referring to it.
+/// })?;
+/// ```
+///
+#[derive(Debug)]
+pub struct Defragmenter {
+    allocator: Allocator,
+    resources: Vec<Resource>,
+    max_bytes_to_move: DeviceSize,
+}
+
+impl Defragmenter {
+    /// Returns a new `Defragmenter` for resources allocated from `allocator`.
+    pub fn new(allocator: &Allocator) -> Defragmenter {
+        Defragmenter {
+            allocator: allocator.clone(),
+            resources: Vec::new(),
+            max_bytes_to_move: DeviceSize::max_value(),
+        }
+    }
+
+    /// Adds a buffer which may be relocated.
+    pub fn buffer<'s>(&'s mut self, buffer: &Buffer) -> &'s mut Defragmenter {
+        self.resources.push(Resource::Buffer(buffer.clone()));
+        self
+    }
+
+    /// Adds an image which may be relocated. `layout` is the layout the
+    /// image will be in when the recorded commands execute.
+    pub fn image<'s>(&'s mut self, image: &Image, layout: ImageLayout)
+            -> &'s mut Defragmenter {
+        self.resources.push(Resource::Image(image.clone(), layout));
+        self
+    }
+
+    /// Specifies the maximum number of bytes to move in one pass. Defaults
+    /// to no limit.
+    pub fn max_bytes_to_move<'s>(&'s mut self, max_bytes_to_move: DeviceSize)
+            -> &'s mut Defragmenter {
+        self.max_bytes_to_move = max_bytes_to_move;
+        self
+    }
+
+    /// Plans a set of moves, allocating the new locations and creating the
+    /// new resources.
+    fn plan(&self) -> VdResult<Vec<Relocation>> {
+        // Movable resources and their orders grouped by pool and block:
+        let mut movable: HashMap<PoolKey, HashMap<u64, Vec<(&Resource, u32)>>> = HashMap::new();
+        for resource in self.resources.iter() {
+            if !resource.is_copyable() { continue; }
+            if let Some(allocation) = resource.allocation() {
+                if let AllocationKind::Block { ref allocator, key, block_id, order } =
+                        allocation.kind {
+                    if Arc::ptr_eq(&allocator.inner, &self.allocator.inner) {
+                        movable.entry(key).or_insert_with(HashMap::new)
+                            .entry(block_id).or_insert_with(Vec::new)
+                            .push((resource, order));
+                    }
+                }
+            }
+        }
+
+        let mut relocations = Vec::new();
+        let mut budget = self.max_bytes_to_move;
+
+        for (key, usage) in self.allocator.block_usage() {
+            let pool = match movable.get(&key) {
+                Some(pool) => pool,
+                None => continue,
+            };
+            let evacuated = plan_pool(usage, pool, &mut budget, |&resource, order, excluded| {
+                let size = resource.allocation().unwrap().size();
+                self.allocator.allocate_in_pool(key, order, size, excluded)
+            });
+
+            for (block_id, allocations) in evacuated {
+                for (&(resource, _), allocation) in pool[&block_id].iter().zip(allocations) {
+                    let device = self.allocator.device().clone();
+                    let new = match *resource {
+                        Resource::Buffer(ref buffer) => {
+                            Resource::Buffer(Buffer::with_allocation(device,
+                                &buffer.create_info(), |_| Ok(allocation))?)
+                        },
+                        Resource::Image(ref image, layout) => {
+                            Resource::Image(Image::with_allocation(device,
+                                &image.create_info(), |_| Ok(allocation))?, layout)
+                        },
+                    };
+                    relocations.push(Relocation { old: resource.clone(), new });
+                }
+            }
+        }
+        Ok(relocations)
+    }
+
+    /// Plans a defragmentation pass and records the commands which copy the
+    /// contents of each relocated resource into `command_buffer`.
+    ///
+    /// `command_buffer` must be in the recording state and outside of a
+    /// render pass. The returned `Defragmentation` completes once the
+    /// command buffer has executed.
+    pub fn record(&self, command_buffer: &CommandBuffer) -> VdResult<Defragmentation> {
+        let relocations = self.plan()?;
+        let device = self.allocator.device().clone();
+        if relocations.is_empty() { return Ok(Defragmentation { relocations, device }); }
+
+        let mut pre_image_barriers = Vec::new();
+        let mut post_image_barriers = Vec::new();
+
+        for relocation in relocations.iter() {
+            if let (&Resource::Image(ref old, layout), &Resource::Image(ref new, _)) =
+                    (&relocation.old, &relocation.new) {
+                let subresource_range = ImageSubresourceRange::builder()
+                    .aspect_mask(old.format().aspects())
+                    .base_mip_level(0)
+                    .level_count(old.mip_levels())
+                    .base_array_layer(0)
+                    .layer_count(old.array_layers())
+                    .build();
+                let barrier = |image: &Image, old_layout, new_layout, src_access, dst_access| {
+                    ImageMemoryBarrier::builder()
+                        .src_access_mask(src_access)
+                        .dst_access_mask(dst_access)
+                        .old_layout(old_layout)
+                        .new_layout(new_layout)
+                        .src_queue_family_index(QUEUE_FAMILY_IGNORED)
+                        .dst_queue_family_index(QUEUE_FAMILY_IGNORED)
+                        .image(image)
+                        .subresource_range(subresource_range.clone())
+                        .build()
+                };
+
+                pre_image_barriers.push(barrier(old, layout, ImageLayout::TransferSrcOptimal,
+                    AccessFlags::MEMORY_WRITE, AccessFlags::TRANSFER_READ));
+                pre_image_barriers.push(barrier(new, ImageLayout::Undefined,
+                    ImageLayout::TransferDstOptimal, AccessFlags::empty(),
+                    AccessFlags::TRANSFER_WRITE));
+                post_image_barriers.push(barrier(new, ImageLayout::TransferDstOptimal, layout,
+                    AccessFlags::TRANSFER_WRITE,
+                    AccessFlags::MEMORY_READ | AccessFlags::MEMORY_WRITE));
+            }
+        }
+
+        let pre_barrier = MemoryBarrier::builder()
+            .src_access_mask(AccessFlags::MEMORY_WRITE)
+            .dst_access_mask(AccessFlags::TRANSFER_READ)
+            .build();
+        command_buffer.pipeline_barrier(PipelineStageFlags::ALL_COMMANDS,
+            PipelineStageFlags::TRANSFER, DependencyFlags::empty(), &[pre_barrier], &[],
+            &pre_image_barriers);
+
+        for relocation in relocations.iter() {
+            match (&relocation.old, &relocation.new) {
+                (&Resource::Buffer(ref old), &Resource::Buffer(ref new)) => {
+                    let region = BufferCopy::builder()
+                        .src_offset(0)
+                        .dst_offset(0)
+                        .size(old.size())
+                        .build();
+                    unsafe { command_buffer.copy_buffer(old, new, &[region]); }
+                },
+                (&Resource::Image(ref old, _), &Resource::Image(ref new, _)) => {
+                    let regions: Vec<ImageCopy> = (0..old.mip_levels()).map(|level| {
+                        let subresource = ImageSubresourceLayers::builder()
+                            .aspect_mask(old.format().aspects())
+                            .mip_level(level)
+                            .base_array_layer(0)
+                            .layer_count(old.array_layers())
+                            .build();
+                        let extent = Extent3d::builder()
+                            .width(cmp::max(old.extent().width() >> level, 1))
+                            .height(cmp::max(old.extent().height() >> level, 1))
+                            .depth(cmp::max(old.extent().depth() >> level, 1))
+                            .build();
+                        ImageCopy::builder()
+                            .src_subresource(subresource.clone())
+                            .src_offset(Offset3d::builder().x(0).y(0).z(0).build())
+                            .dst_subresource(subresource)
+                            .dst_offset(Offset3d::builder().x(0).y(0).z(0).build())
+                            .extent(extent)
+                            .build()
+                    }).collect();
+                    unsafe {
+                        command_buffer.copy_image(old, ImageLayout::TransferSrcOptimal, new,
+                            ImageLayout::TransferDstOptimal, &regions);
+                    }
+                },
+                _ => unreachable!(),
+            }
+        }
+
+        let post_barrier = MemoryBarrier::builder()
+            .src_access_mask(AccessFlags::TRANSFER_WRITE)
+            .dst_access_mask(AccessFlags::MEMORY_READ | AccessFlags::MEMORY_WRITE)
+            .build();
+        command_buffer.pipeline_barrier(PipelineStageFlags::TRANSFER,
+            PipelineStageFlags::ALL_COMMANDS, DependencyFlags::empty(), &[post_barrier], &[],
+            &post_image_barriers);
+
+        Ok(Defragmentation { relocations, device })
+    }
+}
+
+
+/// A recorded defragmentation pass.
+///
+/// Obtain using `Defragmenter::record`.
+///
+/// The memory previously occupied by each relocated resource is released
+/// once every reference to the old resource has been dropped.
+///
+/// The old and new resources are held until `::poll` observes that the
+/// copies have completed. Dropping a `Defragmentation` before then waits
+/// for the device to become idle, as the fence signaled by the copies is
+/// not known.
+///
+#[derive(Debug)]
+pub struct Defragmentation {
+    relocations: Vec<Relocation>,
+    device: Device,
+}
+
+impl Defragmentation {
+    /// Returns the pending relocations.
+    pub fn relocations(&self) -> &[Relocation] {
+        &self.relocations
+    }
+
+    /// Returns `true` if nothing was moved.
+    pub fn is_empty(&self) -> bool {
+        self.relocations.is_empty()
+    }
+
+    /// Completes the pass if `fence`, which must have been signaled by the
+    /// submission of the recorded commands, is signaled.
+    ///
+    /// Calls `notify` with each relocation so that owners can replace the
+    /// old resource with the new one (and update any descriptor sets or
+    /// command buffers referring to it) and returns `true`. Returns `false`
+    /// without calling `notify` if the fence is not yet signaled.
+    pub fn poll<F>(&mut self, fence: &Fence, mut notify: F) -> VdResult<bool>
+            where F: FnMut(Relocation) {
+        match fence.status()? {
+            FenceStatus::Signaled => {
+                for relocation in mem::replace(&mut self.relocations, Vec::new()) {
+                    notify(relocation);
+                }
+                Ok(true)
+            },
+            FenceStatus::Unsignaled => Ok(false),
+            FenceStatus::Error(res) => Err(ErrorKind::ApiCall(res, "vkGetFenceStatus").into()),
+        }
+    }
+}
+
+impl Drop for Defragmentation {
+    fn drop(&mut self) {
+        if !self.relocations.is_empty() {
+            self.device.wait_idle();
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buddy_splits_and_merges() {
+        let mut buddy = Buddy::new(MIN_NODE_SIZE * 4);
+        assert_eq!(buddy.allocate(0), Some(0));
+        assert_eq!(buddy.allocate(1), Some(MIN_NODE_SIZE * 2));
+        assert_eq!(buddy.allocate(0), Some(MIN_NODE_SIZE));
+        assert_eq!(buddy.allocated_bytes, MIN_NODE_SIZE * 4);
+        assert_eq!(buddy.allocate(0), None);
+
+        buddy.free(0, 0);
+        buddy.free(MIN_NODE_SIZE, 0);
+        assert_eq!(buddy.allocate(1), Some(0));
+        buddy.free(0, 1);
+        buddy.free(MIN_NODE_SIZE * 2, 1);
+        assert!(buddy.is_empty());
+        assert_eq!(buddy.allocate(2), Some(0));
+    }
+
+    #[test]
+    fn buddy_rejects_oversized_orders() {
+        let mut buddy = Buddy::new(MIN_NODE_SIZE * 2);
+        assert_eq!(buddy.allocate(2), None);
+        assert!(buddy.is_empty());
+    }
+
+    #[test]
+    fn order_for_honors_size_and_alignment() {
+        assert_eq!(Buddy::order_for(1, 1), 0);
+        assert_eq!(Buddy::order_for(MIN_NODE_SIZE, 4), 0);
+        assert_eq!(Buddy::order_for(MIN_NODE_SIZE + 1, 4), 1);
+        assert_eq!(Buddy::order_for(16, MIN_NODE_SIZE * 4), 2);
+    }
+
+    /// Simulates a pool for `plan_pool`, placing nodes in the fullest
+    /// non-excluded block as `Allocator::allocate_in_pool` does.
+    fn simulate(blocks: &mut Vec<(u64, Buddy)>, movable: &HashMap<u64, Vec<((), u32)>>,
+            budget: &mut DeviceSize) -> Vec<u64> {
+        let usage = blocks.iter().map(|&(id, ref b)| (id, b.allocated_bytes)).collect();
+        let evacuated = plan_pool(usage, movable, budget, |_, order, excluded| {
+            let mut indices: Vec<usize> = (0..blocks.len())
+                .filter(|&i| !excluded.contains(&blocks[i].0))
+                .collect();
+            indices.sort_by(|&a, &b| {
+                blocks[b].1.allocated_bytes.cmp(&blocks[a].1.allocated_bytes)
+            });
+            for i in indices {
+                if let Some(offset) = blocks[i].1.allocate(order) {
+                    return Some((blocks[i].0, offset));
+                }
+            }
+            None
+        });
+        evacuated.into_iter().map(|(id, _)| id).collect()
+    }
+
+    fn block(orders: &[u32]) -> Buddy {
+        let mut buddy = Buddy::new(MIN_NODE_SIZE * 4);
+        for &order in orders { buddy.allocate(order).unwrap(); }
+        buddy
+    }
+
+    fn ranges(orders: &[u32]) -> Vec<((), u32)> {
+        orders.iter().map(|&order| ((), order)).collect()
+    }
+
+    #[test]
+    fn plan_evacuates_emptiest_blocks() {
+        let mut blocks = vec![(0, block(&[0, 0, 0])), (1, block(&[0]))];
+        let mut movable = HashMap::new();
+        movable.insert(0, ranges(&[0, 0, 0]));
+        movable.insert(1, ranges(&[0]));
+        let mut budget = DeviceSize::max_value();
+
+        assert_eq!(simulate(&mut blocks, &movable, &mut budget), vec![1]);
+        assert_eq!(budget, DeviceSize::max_value() - MIN_NODE_SIZE);
+        assert_eq!(blocks[0].1.allocated_bytes, MIN_NODE_SIZE * 4);
+    }
+
+    #[test]
+    fn plan_skips_partially_movable_blocks_and_respects_budget() {
+        let mut blocks = vec![(0, block(&[0, 0])), (1, block(&[0])), (2, block(&[0]))];
+        // Block 1 holds an unmovable range:
+        let mut movable = HashMap::new();
+        movable.insert(1, Vec::new());
+        movable.insert(2, ranges(&[0]));
+        let mut budget = MIN_NODE_SIZE;
+        assert_eq!(simulate(&mut blocks, &movable, &mut budget), vec![2]);
+        assert_eq!(budget, 0);
+
+        let mut blocks = vec![(0, block(&[0, 0])), (1, block(&[0]))];
+        let mut budget = MIN_NODE_SIZE - 1;
+        movable.insert(1, ranges(&[0]));
+        assert!(simulate(&mut blocks, &movable, &mut budget).is_empty());
+    }
+
+    #[test]
+    fn plan_never_evacuates_destinations() {
+        // Block 0 holds an order 1 node which only fits into block 1. Its
+        // usage is then stale and it must not be evacuated into blocks 2
+        // and 3 (one of which is the fullest and never evacuated).
+        let mut blocks = vec![
+            (0, block(&[1])),
+            (1, block(&[0, 0])),
+            (2, block(&[0, 0, 0])),
+            (3, block(&[0, 0, 0])),
+        ];
+        let mut movable = HashMap::new();
+        movable.insert(0, ranges(&[1]));
+        movable.insert(1, ranges(&[0, 0]));
+        let mut budget = DeviceSize::max_value();
+
+        assert_eq!(simulate(&mut blocks, &movable, &mut budget), vec![0]);
+        assert_eq!(blocks[1].1.allocated_bytes, MIN_NODE_SIZE * 4);
+    }
+}
@@ -1,7 +1,14 @@
 use std::sync::Arc;
+use std::mem;
 use std::marker::PhantomData;
+use smallvec::SmallVec;
 use vks;
-use ::{VdResult, Device, DeviceMemory, Handle};
+use ::{VdResult, Device, DeviceMemory, Handle, MemoryTypeSelector, MemoryRequirements,
+    BufferCreateInfo, BufferCreateFlags, BufferUsageFlags, SharingMode, DeviceSize, Queue,
+    MemoryPropertyFlags, BufferCopy, BufferMemoryBarrier, DependencyFlags, UploadToken, Pod,
+    ErrorKind};
+use alloc::{Allocator, Allocation};
+use upload::{self, UploadBarrier};
 
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -29,6 +36,38 @@ struct Inner {
     handle: BufferHandle,
     memory_requirements: ::MemoryRequirements,
     device: Device,
+    allocation: Option<Allocation>,
+    tracking_id: u64,
+    flags: BufferCreateFlags,
+    size: DeviceSize,
+    usage: BufferUsageFlags,
+    sharing_mode: SharingMode,
+    queue_family_indices: SmallVec<[u32; 4]>,
+}
+
+impl Inner {
+    /// Returns a new `Inner` for a newly created, unbound buffer.
+    fn new(device: Device, handle: BufferHandle, create_info: &BufferCreateInfo) -> Inner {
+        let memory_requirements = unsafe { device.get_buffer_memory_requirements(handle) };
+        let tracking_id = device.memory_tracker().register("Buffer");
+        let queue_family_indices = match create_info.sharing_mode() {
+            SharingMode::Concurrent => create_info.queue_family_indices().iter().cloned().collect(),
+            _ => SmallVec::new(),
+        };
+
+        Inner {
+            handle,
+            memory_requirements,
+            device,
+            allocation: None,
+            tracking_id,
+            flags: create_info.flags(),
+            size: create_info.size(),
+            usage: create_info.usage(),
+            sharing_mode: create_info.sharing_mode(),
+            queue_family_indices,
+        }
+    }
 }
 
 impl Drop for Inner {
@@ -36,6 +75,7 @@ impl Drop for Inner {
         unsafe {
             self.device.destroy_buffer(self.handle, None);
         }
+        self.device.memory_tracker().unregister(self.tracking_id);
     }
 }
 
@@ -59,6 +99,104 @@ impl Buffer {
         BufferBuilder::new()
     }
 
+    /// Creates a new `Buffer` as specified by `builder` and binds it to
+    /// memory sub-allocated from `allocator`.
+    ///
+    /// The allocation is kept alive until the buffer is destroyed.
+    pub fn new_allocated(builder: &BufferBuilder, allocator: &Allocator,
+            memory_type: &MemoryTypeSelector) -> VdResult<Buffer> {
+        Buffer::with_allocation(allocator.device().clone(), &builder.create_info,
+            |requirements| allocator.allocate(requirements, memory_type, true))
+    }
+
+    /// Creates a new device-local `Buffer` containing `data`, for use on
+    /// `queue`.
+    ///
+    /// `data` is copied through a host-visible staging buffer. The copy runs
+    /// on a dedicated transfer queue if the device has one, in which case
+    /// ownership of the buffer is transferred to `queue`'s family. The buffer
+    /// may be used on `queue` once the returned `UploadToken` completes.
+    ///
+    /// `usage` is combined with `BufferUsageFlags::TRANSFER_DST`.
+    ///
+    /// Returns an `InvalidUpload` error if `data` is empty.
+    pub fn from_slice<T: Pod>(device: &Device, usage: BufferUsageFlags, data: &[T], queue: &Queue)
+            -> VdResult<(Buffer, UploadToken)> {
+        let size = (mem::size_of::<T>() * data.len()) as DeviceSize;
+        if size == 0 {
+            return Err(ErrorKind::InvalidUpload { reason: "the data is empty" }.into());
+        }
+        let staging_buffer = upload::staging_buffer(device, data)?;
+
+        let mut builder = Buffer::builder();
+        builder.size(size)
+            .usage(usage | BufferUsageFlags::TRANSFER_DST)
+            .sharing_mode(SharingMode::Exclusive);
+        let buffer = Buffer::with_allocation(device.clone(), &builder.create_info, |requirements| {
+            let memory_type_index = MemoryTypeSelector::new()
+                .required(MemoryPropertyFlags::DEVICE_LOCAL)
+                .select(device, requirements.memory_type_bits())?;
+            Allocation::dedicated(device.clone(), requirements, memory_type_index)
+        })?;
+
+        let token = upload::upload(device, queue, staging_buffer, true,
+            |command_buffer, staging_buffer| {
+                let region = BufferCopy::builder()
+                    .src_offset(0)
+                    .dst_offset(0)
+                    .size(size)
+                    .build();
+                unsafe { command_buffer.copy_buffer(staging_buffer, &buffer, &[region]); }
+            },
+            |command_buffer, barrier: UploadBarrier| {
+                let (src_family, dst_family) = barrier.queue_family_indices();
+                let buffer_barrier = BufferMemoryBarrier::builder()
+                    .src_access_mask(barrier.src_access_mask())
+                    .dst_access_mask(barrier.dst_access_mask())
+                    .src_queue_family_index(src_family)
+                    .dst_queue_family_index(dst_family)
+                    .buffer(&buffer)
+                    .offset(0)
+                    .size(size)
+                    .build();
+                command_buffer.pipeline_barrier(barrier.src_stage_mask(),
+                    barrier.dst_stage_mask(), DependencyFlags::empty(), &[], &[buffer_barrier],
+                    &[]);
+            })?;
+
+        Ok((buffer, token))
+    }
+
+    /// Creates a new `Buffer` and binds it to the allocation returned by
+    /// `allocate`, which is passed the buffer's memory requirements.
+    pub(crate) fn with_allocation<F>(device: Device, create_info: &BufferCreateInfo, allocate: F)
+            -> VdResult<Buffer>
+            where F: FnOnce(&MemoryRequirements) -> VdResult<Allocation> {
+        let handle = unsafe { device.create_buffer(create_info, None)? };
+
+        // Dropping `inner` on error destroys the buffer:
+        let mut inner = Inner::new(device, handle, create_info);
+        let allocation = allocate(&inner.memory_requirements)?;
+        unsafe {
+            inner.device.bind_buffer_memory(handle, allocation.memory().handle(),
+                allocation.offset())?;
+        }
+        inner.allocation = Some(allocation);
+
+        Ok(Buffer { inner: Arc::new(inner) })
+    }
+
+    /// Returns a create info matching the one this buffer was created with.
+    pub(crate) fn create_info<'s>(&'s self) -> BufferCreateInfo<'s> {
+        let mut create_info = BufferCreateInfo::default();
+        create_info.set_flags(self.inner.flags);
+        create_info.set_size(self.inner.size);
+        create_info.set_usage(self.inner.usage);
+        create_info.set_sharing_mode(self.inner.sharing_mode);
+        create_info.set_queue_family_indices(&self.inner.queue_family_indices[..]);
+        create_info
+    }
+
     /// Returns this object's handle.
     pub fn handle(&self) -> BufferHandle {
         self.inner.handle
@@ -69,6 +207,26 @@ impl Buffer {
         &self.inner.memory_requirements
     }
 
+    /// Returns the size in bytes this buffer was created with.
+    pub fn size(&self) -> DeviceSize {
+        self.inner.size
+    }
+
+    /// Returns the usage flags this buffer was created with.
+    pub fn usage(&self) -> BufferUsageFlags {
+        self.inner.usage
+    }
+
+    /// Returns the flags this buffer was created with.
+    pub fn flags(&self) -> BufferCreateFlags {
+        self.inner.flags
+    }
+
+    /// Returns the sharing mode this buffer was created with.
+    pub fn sharing_mode(&self) -> SharingMode {
+        self.inner.sharing_mode
+    }
+
     /// Binds this buffer to device memory. `offset` is the start offset of the
     /// region of memory which is to be bound. The number of bytes returned in
     /// the VkMemoryRequirements::size member in memory, starting from
@@ -84,6 +242,17 @@ impl Buffer {
         self.inner.device.bind_buffer_memory(self.inner.handle, memory.handle(), offset)
     }
 
+    /// Sets the name used to identify this buffer in leak reports.
+    pub fn set_debug_name(&self, name: &str) {
+        self.inner.device.memory_tracker().set_name(self.inner.tracking_id, name);
+    }
+
+    /// Returns the allocation this buffer is bound to if it was created with
+    /// `::new_allocated`.
+    pub fn allocation(&self) -> Option<&Allocation> {
+        self.inner.allocation.as_ref()
+    }
+
     /// Returns a reference to the associated device.
     pub fn device(&self) -> &Device {
         &self.inner.device
@@ -157,14 +326,9 @@ impl<'b> BufferBuilder<'b> {
     /// Creates and returns a new `Buffer`
     pub fn build(&self, device: Device) -> VdResult<Buffer> {
         let handle = unsafe { device.create_buffer(&self.create_info, None)? };
-        let memory_requirements = unsafe { device.get_buffer_memory_requirements(handle) };
 
         Ok(Buffer {
-            inner: Arc::new(Inner {
-                handle,
-                device,
-                memory_requirements,
-            })
+            inner: Arc::new(Inner::new(device, handle, &self.create_info)),
         })
     }
 }
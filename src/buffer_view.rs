@@ -0,0 +1,230 @@
+use std::sync::Arc;
+use vks;
+use ::{VdResult, Device, Buffer, Handle, Format, DeviceSize, BufferViewCreateInfo,
+    BufferUsageFlags, FormatFeatureFlags, ErrorKind, WHOLE_SIZE};
+
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(C)]
+pub struct BufferViewHandle(pub(crate) vks::VkBufferView);
+
+impl BufferViewHandle {
+    #[inline(always)]
+    pub fn to_raw(&self) -> vks::VkBufferView {
+        self.0
+    }
+}
+
+unsafe impl Handle for BufferViewHandle {
+    type Target = BufferViewHandle;
+
+    #[inline(always)]
+    fn handle(&self) -> Self::Target {
+        *self
+    }
+}
+
+
+#[derive(Debug)]
+struct Inner {
+    handle: BufferViewHandle,
+    buffer: Buffer,
+    format: Format,
+    offset: DeviceSize,
+    range: DeviceSize,
+}
+
+impl Drop for Inner {
+    fn drop(&mut self) {
+        unsafe {
+            self.buffer.device().destroy_buffer_view(self.handle, None);
+        }
+    }
+}
+
+
+/// A view of a buffer's contents as formatted texels, for use as a uniform
+/// or storage texel buffer.
+///
+/// The viewed `Buffer` is kept alive for as long as the view exists.
+///
+///
+/// ### Destruction
+///
+/// Dropping this `BufferView` will cause `Device::destroy_buffer_view` to be
+/// called, automatically releasing any resources associated with it.
+///
+#[derive(Debug, Clone)]
+pub struct BufferView {
+    inner: Arc<Inner>,
+}
+
+impl BufferView {
+    /// Returns a new `BufferViewBuilder`.
+    pub fn builder<'b>() -> BufferViewBuilder<'b> {
+        BufferViewBuilder::new()
+    }
+
+    /// Returns this object's handle.
+    pub fn handle(&self) -> BufferViewHandle {
+        self.inner.handle
+    }
+
+    /// Returns the viewed buffer.
+    pub fn buffer(&self) -> &Buffer {
+        &self.inner.buffer
+    }
+
+    /// Returns the format texels are interpreted as.
+    pub fn format(&self) -> Format {
+        self.inner.format
+    }
+
+    /// Returns the offset in bytes of the view from the start of the buffer.
+    pub fn offset(&self) -> DeviceSize {
+        self.inner.offset
+    }
+
+    /// Returns the size in bytes of the view (never `WHOLE_SIZE`).
+    pub fn range(&self) -> DeviceSize {
+        self.inner.range
+    }
+
+    /// Returns a reference to the associated device.
+    pub fn device(&self) -> &Device {
+        self.inner.buffer.device()
+    }
+}
+
+unsafe impl<'h> Handle for &'h BufferView {
+    type Target = BufferViewHandle;
+
+    #[inline(always)]
+    fn handle(&self) -> Self::Target {
+        self.inner.handle
+    }
+}
+
+
+/// A builder for a `BufferView`.
+#[derive(Debug, Clone)]
+pub struct BufferViewBuilder<'b> {
+    create_info: BufferViewCreateInfo<'b>,
+    buffer: Option<&'b Buffer>,
+}
+
+impl<'b> BufferViewBuilder<'b> {
+    /// Returns a new `BufferViewBuilder` viewing the whole buffer.
+    pub fn new() -> BufferViewBuilder<'b> {
+        let mut create_info = BufferViewCreateInfo::default();
+        create_info.set_range(WHOLE_SIZE);
+        BufferViewBuilder { create_info, buffer: None }
+    }
+
+    /// Specifies the buffer on which the view will be created.
+    pub fn buffer<'s>(&'s mut self, buffer: &'b Buffer) -> &'s mut BufferViewBuilder<'b> {
+        self.create_info.set_buffer(buffer);
+        self.buffer = Some(buffer);
+        self
+    }
+
+    /// Specifies the format of the data elements in the buffer.
+    pub fn format<'s>(&'s mut self, format: Format) -> &'s mut BufferViewBuilder<'b> {
+        self.create_info.set_format(format);
+        self
+    }
+
+    /// Specifies the offset in bytes from the base address of the buffer.
+    /// Must be a multiple of the `minTexelBufferOffsetAlignment` limit.
+    pub fn offset<'s>(&'s mut self, offset: DeviceSize) -> &'s mut BufferViewBuilder<'b> {
+        self.create_info.set_offset(offset);
+        self
+    }
+
+    /// Specifies the size in bytes of the view. Defaults to `WHOLE_SIZE`,
+    /// covering the buffer from `offset` to its end.
+    pub fn range<'s>(&'s mut self, range: DeviceSize) -> &'s mut BufferViewBuilder<'b> {
+        self.create_info.set_range(range);
+        self
+    }
+
+    /// Creates and returns a new `BufferView`.
+    ///
+    /// Returns an error if the buffer's texel buffer usages are not supported
+    /// for the view's format, if the offset is misaligned or if the view does
+    /// not fit within the buffer.
+    pub fn build(&self) -> VdResult<BufferView> {
+        let buffer = self.buffer.ok_or(ErrorKind::InvalidBufferView {
+            reason: "no buffer specified" })?;
+        let format = self.create_info.format();
+        let offset = self.create_info.offset();
+        let range = self.validate(buffer, format, offset)?;
+
+        let handle = unsafe { buffer.device().create_buffer_view(&self.create_info, None)? };
+
+        Ok(BufferView {
+            inner: Arc::new(Inner {
+                handle,
+                buffer: buffer.clone(),
+                format,
+                offset,
+                range,
+            })
+        })
+    }
+
+    /// Checks the view against the buffer, format support and device limits,
+    /// returning its range in bytes.
+    fn validate(&self, buffer: &Buffer, format: Format, offset: DeviceSize)
+            -> VdResult<DeviceSize> {
+        let invalid = |reason| Err(ErrorKind::InvalidBufferView { reason }.into());
+        let physical_device = buffer.device().physical_device();
+
+        if format.block_size() == 0 || format.is_compressed() {
+            return invalid("the format is undefined or compressed");
+        }
+
+        let usage = buffer.usage();
+        let mut required_features = FormatFeatureFlags::empty();
+        if usage.contains(BufferUsageFlags::UNIFORM_TEXEL_BUFFER) {
+            required_features |= FormatFeatureFlags::UNIFORM_TEXEL_BUFFER;
+        }
+        if usage.contains(BufferUsageFlags::STORAGE_TEXEL_BUFFER) {
+            required_features |= FormatFeatureFlags::STORAGE_TEXEL_BUFFER;
+        }
+        if required_features.is_empty() {
+            return invalid("the buffer's usage includes neither `UNIFORM_TEXEL_BUFFER` nor \
+                `STORAGE_TEXEL_BUFFER`");
+        }
+        if !physical_device.format_properties(format).buffer_features()
+                .contains(required_features) {
+            return Err(ErrorKind::UnsupportedBufferViewFormat { format, usage }.into());
+        }
+
+        let limits = physical_device.properties().limits().clone();
+        if offset % limits.min_texel_buffer_offset_alignment() != 0 {
+            return invalid("offset is not a multiple of `minTexelBufferOffsetAlignment`");
+        }
+        if offset >= buffer.size() {
+            return invalid("offset is not less than the buffer's size");
+        }
+
+        let range = match self.create_info.range() {
+            WHOLE_SIZE => buffer.size() - offset,
+            range => {
+                if range == 0 || range > buffer.size() - offset {
+                    return invalid("range is zero or exceeds the end of the buffer");
+                }
+                if range % format.block_size() as DeviceSize != 0 {
+                    return invalid("range is not a multiple of the format's texel size");
+                }
+                range
+            },
+        };
+        if range / format.block_size() as DeviceSize >
+                limits.max_texel_buffer_elements() as DeviceSize {
+            return invalid("range exceeds `maxTexelBufferElements` texels");
+        }
+        Ok(range)
+    }
+}
@@ -9,7 +9,8 @@ use ::{VdResult, Device, Handle, CommandPool, CommandBufferUsageFlags, CommandBu
     ImageCopy, IndexType, ImageBlit, Filter, ClearColorValue, ImageSubresourceRange,
     ClearDepthStencilValue, ClearAttachment, ImageResolve, QueryControlFlags, ClearRect,
     BufferHandle, EventHandle,Buffer, Image, Event, QueryPool, PipelineLayout, DescriptorSet,
-    PipelineHandle};
+    PipelineHandle, ComputePipeline, BufferSlice, IndexElement, Pod, MemoryBarrierBuilder,
+    BufferMemoryBarrierBuilder, ImageMemoryBarrierBuilder};
 
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -130,6 +131,16 @@ impl CommandBuffer {
             pipeline.handle()); }
     }
 
+    /// Binds a compute pipeline to this command buffer.
+    ///
+    /// https://www.khronos.org/registry/vulkan/specs/1.0/man/html/vkCmdBindPipeline.html
+    //
+    #[inline]
+    pub fn bind_compute_pipeline(&self, pipeline: &ComputePipeline) {
+        unsafe { self.device().cmd_bind_pipeline(self.handle(), PipelineBindPoint::Compute,
+            pipeline.handle()); }
+    }
+
     /// Sets the viewport on this command buffer.
     ///
     /// https://www.khronos.org/registry/vulkan/specs/1.0/man/html/vkCmdSetViewport.html
@@ -250,6 +261,22 @@ impl CommandBuffer {
             first_binding, &buffer_handles, offsets); }
     }
 
+    /// Binds a typed slice of a buffer as the vertex buffer for
+    /// `binding`.
+    #[inline]
+    pub fn bind_vertex_buffer_slice<T: Pod>(&self, binding: u32, slice: BufferSlice<T>) {
+        unsafe { self.device().cmd_bind_vertex_buffers(self.handle(), binding,
+            &[slice.buffer().handle()], &[slice.offset()]); }
+    }
+
+    /// Binds a typed slice of a buffer of `u16` or `u32` indices as the
+    /// index buffer.
+    #[inline]
+    pub fn bind_index_buffer_slice<I: IndexElement>(&self, slice: BufferSlice<I>) {
+        unsafe { self.device().cmd_bind_index_buffer(self.handle(), slice.buffer().handle(),
+            slice.offset(), I::index_type()); }
+    }
+
     /// Draws primitives.
     ///
     /// https://www.khronos.org/registry/vulkan/specs/1.0/man/html/vkCmdDraw.html
@@ -324,6 +351,23 @@ impl CommandBuffer {
             dst_buffer.handle(), regions);
     }
 
+    /// Copies the elements of `src` to `dst`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `src` and `dst` differ in length.
+    #[inline]
+    pub fn copy_buffer_slice<T: Pod>(&self, src: BufferSlice<T>, dst: BufferSlice<T>) {
+        assert_eq!(src.len(), dst.len(), "source and destination slices differ in length");
+        let region = BufferCopy::builder()
+            .src_offset(src.offset())
+            .dst_offset(dst.offset())
+            .size(src.size())
+            .build();
+        unsafe { self.device().cmd_copy_buffer(self.handle(), src.buffer().handle(),
+            dst.buffer().handle(), &[region]); }
+    }
+
     /// Copies data between images.
     ///
     /// https://www.khronos.org/registry/vulkan/specs/1.0/man/html/vkCmdCopyImage.html
@@ -467,6 +511,37 @@ impl CommandBuffer {
             dst_stage_mask, memory_barriers, buffer_memory_barriers, image_memory_barriers); }
     }
 
+    /// Waits for one or more events with an execution dependency only.
+    #[inline]
+    pub fn wait_events_execution(&self, events: &[&Event], src_stage_mask: PipelineStageFlags,
+            dst_stage_mask: PipelineStageFlags) {
+        self.wait_events(events, src_stage_mask, dst_stage_mask, &[], &[], &[]);
+    }
+
+    /// Waits for one or more events and inserts the global memory barrier
+    /// built by `barrier`.
+    #[inline]
+    pub fn wait_events_memory(&self, events: &[&Event], src_stage_mask: PipelineStageFlags,
+            dst_stage_mask: PipelineStageFlags, barrier: MemoryBarrierBuilder) {
+        self.wait_events(events, src_stage_mask, dst_stage_mask, &[barrier.build()], &[], &[]);
+    }
+
+    /// Waits for one or more events and inserts the buffer memory barrier
+    /// built by `barrier`.
+    #[inline]
+    pub fn wait_events_buffer(&self, events: &[&Event], src_stage_mask: PipelineStageFlags,
+            dst_stage_mask: PipelineStageFlags, barrier: BufferMemoryBarrierBuilder) {
+        self.wait_events(events, src_stage_mask, dst_stage_mask, &[], &[barrier.build()], &[]);
+    }
+
+    /// Waits for one or more events and inserts the image memory barrier
+    /// built by `barrier`.
+    #[inline]
+    pub fn wait_events_image(&self, events: &[&Event], src_stage_mask: PipelineStageFlags,
+            dst_stage_mask: PipelineStageFlags, barrier: ImageMemoryBarrierBuilder) {
+        self.wait_events(events, src_stage_mask, dst_stage_mask, &[], &[], &[barrier.build()]);
+    }
+
     /// Inserts a memory dependency.
     ///
     /// https://www.khronos.org/registry/vulkan/specs/1.0/man/html/vkCmdPipelineBarrier.html
@@ -0,0 +1,589 @@
+//! Headless execution of compute shaders.
+//!
+//! A `Kernel` wraps a compute shader. Binding buffers to it and dispatching
+//! produces a `Job`, to which further dispatches (of the same or other
+//! kernels) can be chained. Dispatches within a job execute in order, each
+//! seeing the writes of the previous ones.
+//!
+//! ```ignore
+//! let kernel = Kernel::new(&device, &spirv)?;
+//! let output: Vec<f32> = kernel.arg(&input).arg(&output)
+//!     .dispatch([n / 64, 1, 1])
+//!     .read(&output)?;
+//! ```
+//!
+//! Pipelines are cached per kernel for each combination of binding types
+//! and push constant size, so a kernel can be dispatched repeatedly without
+//! recompilation.
+
+use std::collections::HashMap;
+use std::ffi::CString;
+use std::mem;
+use std::slice;
+use std::sync::{Arc, Mutex};
+use smallvec::SmallVec;
+use ::{VdResult, Device, Queue, Buffer, CommandBuffer, Fence, FenceStatus, ShaderModule,
+    DescriptorSetLayout, DescriptorPool, DescriptorSet, PipelineLayout, PipelineCache,
+    ComputePipeline, DescriptorType, DescriptorSetLayoutBinding, DescriptorPoolSize,
+    DescriptorBufferInfo, WriteDescriptorSet, PushConstantRange, ShaderStageFlags,
+    PipelineBindPoint, PipelineStageFlags, AccessFlags, DependencyFlags, MemoryBarrier, BufferCopy,
+    CommandBufferHandle, SubmitInfo, FenceCreateFlags, QueueFlags, DeviceSize, BufferUsageFlags,
+    ErrorKind, Pod, WHOLE_SIZE};
+use readback;
+use upload;
+
+
+/// The binding types and push constant size a kernel is dispatched with.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Signature {
+    descriptor_types: SmallVec<[DescriptorType; 8]>,
+    push_constant_size: u32,
+}
+
+
+/// Checks that push constants of `size` bytes can be declared in a pipeline
+/// layout on a device whose `maxPushConstantsSize` is `max_size`.
+fn check_push_constant_size(size: u32, max_size: u32) -> VdResult<()> {
+    let invalid = |reason| Err(ErrorKind::InvalidPushConstants { size, reason }.into());
+    if size % 4 != 0 {
+        return invalid("size is not a multiple of 4");
+    }
+    if size > max_size {
+        return invalid("size exceeds the device's maxPushConstantsSize");
+    }
+    Ok(())
+}
+
+
+/// A pipeline created for one signature of a kernel.
+#[derive(Debug)]
+struct KernelPipeline {
+    pipeline: ComputePipeline,
+    pipeline_layout: PipelineLayout,
+    descriptor_set_layout: Option<DescriptorSetLayout>,
+}
+
+
+#[derive(Debug)]
+struct Inner {
+    device: Device,
+    shader_module: ShaderModule,
+    entry_point: CString,
+    pipeline_cache: Option<PipelineCache>,
+    pipelines: Mutex<HashMap<Signature, Arc<KernelPipeline>>>,
+}
+
+
+/// A compute shader which can be dispatched with buffer arguments.
+///
+/// Buffers passed with `::arg` and `::uniform` are bound, in order, to
+/// bindings `0..n` of descriptor set 0. Push constants are visible at offset
+/// zero.
+#[derive(Debug, Clone)]
+pub struct Kernel {
+    inner: Arc<Inner>,
+}
+
+impl Kernel {
+    /// Creates a kernel from the SPIR-V words of a compute shader with a
+    /// `main` entry point.
+    pub fn new(device: &Device, spirv: &[u32]) -> VdResult<Kernel> {
+        Kernel::with_entry_point(device, spirv, "main", None)
+    }
+
+    /// Creates a kernel from the SPIR-V words of a compute shader with the
+    /// entry point `entry_point`, creating its pipelines using
+    /// `pipeline_cache` if specified.
+    pub fn with_entry_point(device: &Device, spirv: &[u32], entry_point: &str,
+            pipeline_cache: Option<&PipelineCache>) -> VdResult<Kernel> {
+        let shader_module = ShaderModule::new(device.clone(), spirv)?;
+        if !shader_module.has_entry_point(entry_point) {
+            return Err(format!("shader module has no entry point named '{}'", entry_point).into());
+        }
+        Ok(Kernel {
+            inner: Arc::new(Inner {
+                device: device.clone(),
+                shader_module,
+                entry_point: CString::new(entry_point)?,
+                pipeline_cache: pipeline_cache.cloned(),
+                pipelines: Mutex::new(HashMap::new()),
+            })
+        })
+    }
+
+    /// Returns a reference to the associated device.
+    pub fn device(&self) -> &Device {
+        &self.inner.device
+    }
+
+    /// Starts a dispatch of this kernel with `buffer` bound as a storage
+    /// buffer at binding 0.
+    pub fn arg(&self, buffer: &Buffer) -> Dispatch {
+        Dispatch::new(Job::new(self.inner.device.clone()), self.clone()).arg(buffer)
+    }
+
+    /// Starts a dispatch of this kernel with `buffer` bound as a uniform
+    /// buffer at binding 0.
+    pub fn uniform(&self, buffer: &Buffer) -> Dispatch {
+        Dispatch::new(Job::new(self.inner.device.clone()), self.clone()).uniform(buffer)
+    }
+
+    /// Starts a dispatch of this kernel with push constants `values`.
+    pub fn push_constants<T: Pod>(&self, values: &[T]) -> Dispatch {
+        Dispatch::new(Job::new(self.inner.device.clone()), self.clone()).push_constants(values)
+    }
+
+    /// Returns the pipeline for `signature`, creating it on first use.
+    fn pipeline(&self, signature: &Signature) -> VdResult<Arc<KernelPipeline>> {
+        let mut pipelines = self.inner.pipelines.lock().unwrap();
+        if let Some(pipeline) = pipelines.get(signature) {
+            return Ok(pipeline.clone());
+        }
+
+        let device = &self.inner.device;
+        check_push_constant_size(signature.push_constant_size,
+            device.physical_device().properties().limits().max_push_constants_size())?;
+
+        let bindings: SmallVec<[DescriptorSetLayoutBinding; 8]> = signature.descriptor_types
+            .iter().enumerate()
+            .map(|(binding, &descriptor_type)| {
+                DescriptorSetLayoutBinding::builder()
+                    .binding(binding as u32)
+                    .descriptor_type(descriptor_type)
+                    .descriptor_count(1)
+                    .stage_flags(ShaderStageFlags::COMPUTE)
+                    .build()
+            })
+            .collect();
+        let descriptor_set_layout = if bindings.is_empty() {
+            None
+        } else {
+            Some(DescriptorSetLayout::builder()
+                .bindings(&bindings)
+                .build(device.clone())?)
+        };
+
+        let set_layouts: SmallVec<[_; 1]> = descriptor_set_layout.iter()
+            .map(|layout| layout.handle())
+            .collect();
+        let push_constant_ranges: SmallVec<[PushConstantRange; 1]> =
+            if signature.push_constant_size == 0 {
+                SmallVec::new()
+            } else {
+                let mut ranges = SmallVec::new();
+                ranges.push(PushConstantRange::builder()
+                    .stage_flags(ShaderStageFlags::COMPUTE)
+                    .offset(0)
+                    .size(signature.push_constant_size)
+                    .build());
+                ranges
+            };
+        let pipeline_layout = PipelineLayout::builder()
+            .set_layouts(&set_layouts)
+            .push_constant_ranges(&push_constant_ranges)
+            .build(device.clone())?;
+
+        let mut builder = ComputePipeline::builder();
+        builder.shader(&self.inner.shader_module, &self.inner.entry_point)
+            .layout(&pipeline_layout);
+        if let Some(ref pipeline_cache) = self.inner.pipeline_cache {
+            builder.pipeline_cache(pipeline_cache);
+        }
+        let pipeline = builder.build(device.clone())?;
+
+        let pipeline = Arc::new(KernelPipeline { pipeline, pipeline_layout, descriptor_set_layout });
+        pipelines.insert(signature.clone(), pipeline.clone());
+        Ok(pipeline)
+    }
+}
+
+
+/// A buffer bound to a dispatch.
+#[derive(Debug, Clone)]
+struct Arg {
+    buffer: Buffer,
+    descriptor_type: DescriptorType,
+}
+
+
+/// A recorded dispatch of a job.
+#[derive(Debug)]
+struct Step {
+    kernel: Kernel,
+    args: SmallVec<[Arg; 8]>,
+    push_constants: Vec<u8>,
+    group_count: [u32; 3],
+}
+
+impl Step {
+    fn signature(&self) -> Signature {
+        Signature {
+            descriptor_types: self.args.iter().map(|arg| arg.descriptor_type).collect(),
+            push_constant_size: self.push_constants.len() as u32,
+        }
+    }
+}
+
+
+/// A dispatch of a kernel being assembled. Created by `Kernel::arg` and
+/// friends, or by `Job::then` to chain onto an existing job.
+#[derive(Debug)]
+pub struct Dispatch {
+    job: Job,
+    step: Step,
+}
+
+impl Dispatch {
+    fn new(job: Job, kernel: Kernel) -> Dispatch {
+        Dispatch {
+            job,
+            step: Step {
+                kernel,
+                args: SmallVec::new(),
+                push_constants: Vec::new(),
+                group_count: [0; 3],
+            },
+        }
+    }
+
+    /// Binds `buffer` as a storage buffer at the next binding.
+    pub fn arg(mut self, buffer: &Buffer) -> Dispatch {
+        self.step.args.push(Arg { buffer: buffer.clone(),
+            descriptor_type: DescriptorType::StorageBuffer });
+        self
+    }
+
+    /// Binds `buffer` as a uniform buffer at the next binding.
+    pub fn uniform(mut self, buffer: &Buffer) -> Dispatch {
+        self.step.args.push(Arg { buffer: buffer.clone(),
+            descriptor_type: DescriptorType::UniformBuffer });
+        self
+    }
+
+    /// Sets the push constants of this dispatch to `values`.
+    ///
+    /// Submitting the job returns an `InvalidPushConstants` error if their
+    /// size is not a multiple of 4 or exceeds the device's
+    /// `maxPushConstantsSize`.
+    pub fn push_constants<T: Pod>(mut self, values: &[T]) -> Dispatch {
+        let bytes = unsafe {
+            slice::from_raw_parts(values.as_ptr() as *const u8, mem::size_of::<T>() * values.len())
+        };
+        self.step.push_constants = bytes.to_vec();
+        self
+    }
+
+    /// Dispatches `group_count` workgroups and returns the job, to which
+    /// further dispatches may be chained.
+    pub fn dispatch(mut self, group_count: [u32; 3]) -> Job {
+        self.step.group_count = group_count;
+        self.job.steps.push(self.step);
+        self.job
+    }
+}
+
+
+/// A sequence of dispatches, executed in order on submission.
+#[derive(Debug)]
+pub struct Job {
+    device: Device,
+    steps: Vec<Step>,
+}
+
+impl Job {
+    fn new(device: Device) -> Job {
+        Job { device, steps: Vec::new() }
+    }
+
+    /// Chains a dispatch of `kernel`, which will see the writes of the
+    /// preceding dispatches.
+    pub fn then(self, kernel: &Kernel) -> Dispatch {
+        Dispatch::new(self, kernel.clone())
+    }
+
+    /// Submits this job to `queue`, which must support compute operations.
+    pub fn submit(self, queue: &Queue) -> VdResult<Submission> {
+        let command_buffer = upload::begin_commands(&self.device, queue.family_index())?;
+        let resources = self.record(&command_buffer, AccessFlags::HOST_READ,
+            PipelineStageFlags::HOST)?;
+        command_buffer.end()?;
+        let fence = submit(queue, &command_buffer)?;
+
+        Ok(Submission {
+            fence,
+            _command_buffer: command_buffer,
+            _resources: resources,
+            _steps: self.steps,
+        })
+    }
+
+    /// Submits this job to the first compute-capable queue of its device and
+    /// blocks until it has completed.
+    pub fn wait(self) -> VdResult<()> {
+        let queue = compute_queue(&self.device)?;
+        self.submit(&queue)?.wait()
+    }
+
+    /// Submits this job to the first compute-capable queue of its device,
+    /// blocks until it has completed and returns the contents of `buffer`,
+    /// which must have been created with `TRANSFER_SRC` usage.
+    ///
+    /// Any trailing bytes of `buffer` too few to hold a `T` are ignored.
+    /// Returns an error without submitting anything if `T` is zero-sized,
+    /// `buffer` is smaller than a `T`, or lacks `TRANSFER_SRC` usage.
+    pub fn read<T: Pod>(self, buffer: &Buffer) -> VdResult<Vec<T>> {
+        let invalid = |reason| Err(ErrorKind::InvalidReadback { reason }.into());
+        if mem::size_of::<T>() == 0 {
+            return invalid("zero-sized element types can not be read");
+        }
+        if buffer.size() < mem::size_of::<T>() as DeviceSize {
+            return invalid("the buffer is smaller than one element");
+        }
+        if !buffer.usage().contains(BufferUsageFlags::TRANSFER_SRC) {
+            return invalid("the buffer was not created with `TRANSFER_SRC` usage");
+        }
+
+        let queue = compute_queue(&self.device)?;
+        let size = buffer.size() - buffer.size() % mem::size_of::<T>() as DeviceSize;
+        let readback_buffer = readback::readback_buffer(&self.device, size)?;
+
+        let command_buffer = upload::begin_commands(&self.device, queue.family_index())?;
+        let _resources = self.record(&command_buffer, AccessFlags::TRANSFER_READ,
+            PipelineStageFlags::TRANSFER)?;
+        let region = BufferCopy::builder()
+            .src_offset(0)
+            .dst_offset(0)
+            .size(size)
+            .build();
+        unsafe { command_buffer.copy_buffer(buffer, &readback_buffer, &[region]); }
+        command_buffer.pipeline_barrier(PipelineStageFlags::TRANSFER, PipelineStageFlags::HOST,
+            DependencyFlags::empty(), &[MemoryBarrier::builder()
+                .src_access_mask(AccessFlags::TRANSFER_WRITE)
+                .dst_access_mask(AccessFlags::HOST_READ)
+                .build()], &[], &[]);
+        command_buffer.end()?;
+
+        let fence = submit(&queue, &command_buffer)?;
+        unsafe { self.device.wait_for_fences(&[fence.handle()], true, u64::max_value())?; }
+
+        let allocation = readback_buffer.allocation().expect("readback buffer has no allocation");
+        let mapping = allocation.memory().persistent_mapping()?;
+        mapping.read::<T>(allocation.offset()..allocation.offset() + size)
+    }
+
+    /// Records every dispatch of this job into `command_buffer`, separated by
+    /// barriers, followed by a barrier making their writes available to
+    /// `dst_access_mask` at `dst_stage_mask`.
+    fn record(&self, command_buffer: &CommandBuffer, dst_access_mask: AccessFlags,
+            dst_stage_mask: PipelineStageFlags) -> VdResult<JobResources> {
+        let mut pipelines = Vec::with_capacity(self.steps.len());
+        for step in &self.steps {
+            pipelines.push(step.kernel.pipeline(&step.signature())?);
+        }
+
+        let (descriptor_pool, descriptor_sets) = self.descriptor_sets(&pipelines)?;
+
+        for (index, (step, pipeline)) in self.steps.iter().zip(pipelines.iter()).enumerate() {
+            if index > 0 {
+                command_buffer.pipeline_barrier(PipelineStageFlags::COMPUTE_SHADER,
+                    PipelineStageFlags::COMPUTE_SHADER, DependencyFlags::empty(),
+                    &[MemoryBarrier::builder()
+                        .src_access_mask(AccessFlags::SHADER_WRITE)
+                        .dst_access_mask(AccessFlags::UNIFORM_READ | AccessFlags::SHADER_READ |
+                            AccessFlags::SHADER_WRITE)
+                        .build()], &[], &[]);
+            }
+
+            command_buffer.bind_compute_pipeline(&pipeline.pipeline);
+            if let Some(set) = descriptor_sets[index].as_ref() {
+                command_buffer.bind_descriptor_sets(PipelineBindPoint::Compute,
+                    &pipeline.pipeline_layout, 0, &[set], &[]);
+            }
+            if !step.push_constants.is_empty() {
+                command_buffer.push_constants(&pipeline.pipeline_layout,
+                    ShaderStageFlags::COMPUTE, 0, &step.push_constants);
+            }
+            let [x, y, z] = step.group_count;
+            command_buffer.dispatch(x, y, z);
+        }
+
+        command_buffer.pipeline_barrier(PipelineStageFlags::COMPUTE_SHADER, dst_stage_mask,
+            DependencyFlags::empty(), &[MemoryBarrier::builder()
+                .src_access_mask(AccessFlags::SHADER_WRITE)
+                .dst_access_mask(dst_access_mask)
+                .build()], &[], &[]);
+
+        Ok(JobResources { _descriptor_pool: descriptor_pool, _pipelines: pipelines })
+    }
+
+    /// Allocates and writes a descriptor set for each step with arguments.
+    fn descriptor_sets(&self, pipelines: &[Arc<KernelPipeline>])
+            -> VdResult<(Option<DescriptorPool>, Vec<Option<DescriptorSet>>)> {
+        let count = |descriptor_type| self.steps.iter()
+            .flat_map(|step| step.args.iter())
+            .filter(|arg| arg.descriptor_type == descriptor_type)
+            .count() as u32;
+        let pool_sizes: SmallVec<[DescriptorPoolSize; 2]> =
+            [DescriptorType::StorageBuffer, DescriptorType::UniformBuffer].iter()
+                .map(|&descriptor_type| (descriptor_type, count(descriptor_type)))
+                .filter(|&(_, descriptor_count)| descriptor_count > 0)
+                .map(|(descriptor_type, descriptor_count)| {
+                    DescriptorPoolSize::builder()
+                        .type_of(descriptor_type)
+                        .descriptor_count(descriptor_count)
+                        .build()
+                })
+                .collect();
+        if pool_sizes.is_empty() {
+            return Ok((None, self.steps.iter().map(|_| None).collect()));
+        }
+
+        let set_layouts: SmallVec<[_; 8]> = pipelines.iter()
+            .filter_map(|pipeline| pipeline.descriptor_set_layout.as_ref())
+            .map(|layout| layout.handle())
+            .collect();
+        let descriptor_pool = DescriptorPool::builder()
+            .max_sets(set_layouts.len() as u32)
+            .pool_sizes(&pool_sizes)
+            .build(self.device.clone())?;
+        let mut allocated = descriptor_pool.allocate_descriptor_sets(&set_layouts)?.into_iter();
+
+        let mut descriptor_sets = Vec::with_capacity(self.steps.len());
+        for (step, pipeline) in self.steps.iter().zip(pipelines) {
+            if pipeline.descriptor_set_layout.is_none() {
+                descriptor_sets.push(None);
+                continue;
+            }
+            let set = allocated.next().expect("descriptor set count mismatch");
+
+            let buffer_infos: SmallVec<[DescriptorBufferInfo; 8]> = step.args.iter()
+                .map(|arg| {
+                    DescriptorBufferInfo::builder()
+                        .buffer(&arg.buffer)
+                        .offset(0)
+                        .range(WHOLE_SIZE)
+                        .build()
+                })
+                .collect();
+            let writes: SmallVec<[WriteDescriptorSet; 8]> = step.args.iter()
+                .zip(buffer_infos.iter())
+                .enumerate()
+                .map(|(binding, (arg, buffer_info))| {
+                    WriteDescriptorSet::builder()
+                        .dst_set(&set)
+                        .dst_binding(binding as u32)
+                        .dst_array_element(0)
+                        .descriptor_count(1)
+                        .descriptor_type(arg.descriptor_type)
+                        .buffer_info(buffer_info)
+                        .build()
+                })
+                .collect();
+            descriptor_pool.update_descriptor_sets(&writes, &[]);
+            descriptor_sets.push(Some(set));
+        }
+
+        Ok((Some(descriptor_pool), descriptor_sets))
+    }
+}
+
+
+/// Objects used by a recorded job.
+#[derive(Debug)]
+struct JobResources {
+    _descriptor_pool: Option<DescriptorPool>,
+    _pipelines: Vec<Arc<KernelPipeline>>,
+}
+
+
+/// A submitted `Job`.
+///
+///
+/// ### Destruction
+///
+/// Dropping this `Submission` blocks until the job has completed, then frees
+/// its command buffer and descriptor sets.
+///
+#[derive(Debug)]
+pub struct Submission {
+    fence: Fence,
+    _command_buffer: CommandBuffer,
+    _resources: JobResources,
+    _steps: Vec<Step>,
+}
+
+impl Submission {
+    /// Returns true if the job has completed.
+    pub fn is_complete(&self) -> VdResult<bool> {
+        match self.fence.status()? {
+            FenceStatus::Signaled => Ok(true),
+            FenceStatus::Unsignaled => Ok(false),
+            FenceStatus::Error(res) => Err(ErrorKind::ApiCall(res, "vkGetFenceStatus").into()),
+        }
+    }
+
+    /// Blocks until the job has completed.
+    pub fn wait(self) -> VdResult<()> {
+        unsafe { self.fence.device().wait_for_fences(&[self.fence.handle()], true, u64::max_value()) }
+    }
+
+    /// Returns the fence signaled when the job completes.
+    pub fn fence(&self) -> &Fence {
+        &self.fence
+    }
+}
+
+impl Drop for Submission {
+    fn drop(&mut self) {
+        unsafe {
+            self.fence.device().wait_for_fences(&[self.fence.handle()], true, u64::max_value()).ok();
+        }
+    }
+}
+
+
+/// Submits `command_buffer` to `queue`, returning a fence signaled on
+/// completion.
+fn submit(queue: &Queue, command_buffer: &CommandBuffer) -> VdResult<Fence> {
+    let fence = Fence::new(queue.device().clone(), FenceCreateFlags::empty())?;
+    let command_buffers: [CommandBufferHandle; 1] = [command_buffer.handle()];
+    let submit_info = SubmitInfo::builder()
+        .command_buffers(&command_buffers[..])
+        .build();
+    queue.submit(&[submit_info], Some(fence.handle()))?;
+    Ok(fence)
+}
+
+/// Returns the first queue of `device` whose family supports compute
+/// operations.
+fn compute_queue(device: &Device) -> VdResult<Queue> {
+    let families = device.physical_device().queue_family_properties()?;
+    device.queues().iter()
+        .find(|queue| families.get(queue.family_index() as usize)
+            .map(|family| family.queue_flags().contains(QueueFlags::COMPUTE))
+            .unwrap_or(false))
+        .cloned()
+        .ok_or_else(|| ErrorKind::String("the device has no compute-capable queue".to_owned())
+            .into())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_constant_sizes() {
+        assert!(check_push_constant_size(0, 128).is_ok());
+        assert!(check_push_constant_size(16, 128).is_ok());
+        assert!(check_push_constant_size(128, 128).is_ok());
+
+        for &size in &[3, 130] {
+            match *check_push_constant_size(size, 128).unwrap_err().kind() {
+                ErrorKind::InvalidPushConstants { size: s, .. } => assert_eq!(s, size),
+                _ => panic!("unexpected error"),
+            }
+        }
+        assert!(check_push_constant_size(132, 128).is_err());
+    }
+}
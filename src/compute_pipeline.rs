@@ -0,0 +1,216 @@
+use std::sync::Arc;
+use std::ffi::CStr;
+use smallvec::SmallVec;
+use ::{VdResult, Device, PipelineLayoutHandle, PipelineHandle, ShaderModuleHandle, Handle,
+    ComputePipelineCreateInfo, PipelineShaderStageCreateInfo, ShaderStageFlags, SpecializationInfo,
+    PipelineCache, Specialization};
+use pipeline_cache;
+
+
+#[derive(Debug)]
+struct Inner {
+    handle: PipelineHandle,
+    device: Device,
+}
+
+impl Drop for Inner {
+    fn drop(&mut self) {
+        unsafe {
+            self.device.destroy_pipeline(self.handle, None);
+        }
+    }
+}
+
+
+/// A compute pipeline.
+///
+///
+/// ### Destruction
+///
+/// Dropping this `ComputePipeline` will cause `Device::destroy_pipeline` to be called,
+/// automatically releasing any resources associated with it.
+///
+#[derive(Debug, Clone)]
+pub struct ComputePipeline {
+    inner: Arc<Inner>,
+}
+
+impl ComputePipeline {
+    /// Returns a new `ComputePipelineBuilder`.
+    pub fn builder<'b>() -> ComputePipelineBuilder<'b> {
+        ComputePipelineBuilder::new()
+    }
+
+    /// Creates several compute pipelines at once, using the pipeline cache
+    /// specified on the builders.
+    ///
+    /// Returns an `IncompatiblePipelineCache` error if the builders specify
+    /// different pipeline caches.
+    pub fn create<'b, Cpb>(device: &Device, builders: &[Cpb])
+            -> VdResult<SmallVec<[ComputePipeline; 8]>>
+            where Cpb: AsRef<ComputePipelineBuilder<'b>> {
+        let create_infos: SmallVec<[ComputePipelineCreateInfo; 8]> = builders.iter()
+            .map(|builder| builder.as_ref().create_info())
+            .collect();
+        let pipeline_cache = pipeline_cache::batch_cache_handle(
+            builders.iter().map(|builder| builder.as_ref().pipeline_cache))?;
+
+        let pipeline_handles = unsafe {
+            device.create_compute_pipelines(pipeline_cache, &create_infos, None)?
+        };
+
+        Ok(pipeline_handles.into_iter().map(|handle| {
+            ComputePipeline {
+                inner: Arc::new(Inner {
+                    handle,
+                    device: device.clone(),
+                })
+            }
+        }).collect())
+    }
+
+    /// Returns this object's handle.
+    pub fn handle(&self) -> PipelineHandle {
+        self.inner.handle
+    }
+
+    /// Returns a reference to the associated device.
+    pub fn device(&self) -> &Device {
+        &self.inner.device
+    }
+}
+
+unsafe impl<'c> Handle for &'c ComputePipeline {
+    type Target = PipelineHandle;
+
+    fn handle(&self) -> Self::Target {
+        self.inner.handle
+    }
+}
+
+
+/// A builder for `ComputePipeline`.
+#[derive(Debug, Clone)]
+pub struct ComputePipelineBuilder<'b> {
+    create_info: ComputePipelineCreateInfo<'b>,
+    stage: PipelineShaderStageCreateInfo<'b>,
+    pipeline_cache: Option<&'b PipelineCache>,
+}
+
+impl<'b> ComputePipelineBuilder<'b> {
+    /// Returns a new compute pipeline builder.
+    pub fn new() -> ComputePipelineBuilder<'b> {
+        let mut create_info = ComputePipelineCreateInfo::default();
+        create_info.set_base_pipeline_index(-1);
+        let mut stage = PipelineShaderStageCreateInfo::default();
+        stage.set_stage(ShaderStageFlags::COMPUTE);
+
+        ComputePipelineBuilder {
+            create_info,
+            stage,
+            pipeline_cache: None,
+        }
+    }
+
+    /// Specifies how the pipeline will be generated.
+    pub fn flags<'s>(&'s mut self, flags: ::PipelineCreateFlags)
+            -> &'s mut ComputePipelineBuilder<'b> {
+        self.create_info.set_flags(flags);
+        self
+    }
+
+    /// Specifies the shader module containing the compute shader and the
+    /// name of its entry point.
+    pub fn shader<'s, H>(&'s mut self, module: H, entry_point: &'b CStr)
+            -> &'s mut ComputePipelineBuilder<'b>
+            where H: Handle<Target=ShaderModuleHandle> {
+        self.stage.set_module(module);
+        self.stage.set_name(entry_point);
+        self
+    }
+
+    /// Specifies the specialization constants of the compute shader.
+    pub fn specialization_info<'s>(&'s mut self, specialization_info: &'b SpecializationInfo)
+            -> &'s mut ComputePipelineBuilder<'b> {
+        self.stage.set_specialization_info(specialization_info);
+        self
+    }
+
+    /// Specifies the specialization constants of the compute shader from a
+    /// typed `Specialization`.
+    pub fn specialization<'s>(&'s mut self, specialization: &'b Specialization)
+            -> &'s mut ComputePipelineBuilder<'b> {
+        self.stage.set_specialization_info(specialization.info());
+        self
+    }
+
+    /// Specifies the complete shader stage, replacing any set by `::shader`,
+    /// `::specialization_info` and `::specialization`. Its stage must be
+    /// `ShaderStageFlags::COMPUTE`.
+    pub fn stage<'s>(&'s mut self, stage: PipelineShaderStageCreateInfo<'b>)
+            -> &'s mut ComputePipelineBuilder<'b> {
+        self.stage = stage;
+        self
+    }
+
+    /// Specifies the description of binding locations used by both the
+    /// pipeline and descriptor sets used with the pipeline.
+    pub fn layout<'s, H>(&'s mut self, layout: H)
+            -> &'s mut ComputePipelineBuilder<'b>
+            where H: Handle<Target=PipelineLayoutHandle> {
+        self.create_info.set_layout(layout);
+        self
+    }
+
+    /// Specifies the pipeline to derive from.
+    pub fn base_pipeline<'s, H>(&'s mut self, base_pipeline: H)
+            -> &'s mut ComputePipelineBuilder<'b>
+            where H: Handle<Target=PipelineHandle> {
+        self.create_info.set_base_pipeline_handle(base_pipeline);
+        self
+    }
+
+    /// Specifies the index into the pCreateInfos parameter to use as a
+    /// pipeline to derive from.
+    pub fn base_pipeline_index<'s>(&'s mut self, base_pipeline_index: i32)
+            -> &'s mut ComputePipelineBuilder<'b> {
+        self.create_info.set_base_pipeline_index(base_pipeline_index);
+        self
+    }
+
+    /// Specifies a pipeline cache to use when creating the pipeline.
+    pub fn pipeline_cache<'s>(&'s mut self, pipeline_cache: &'b PipelineCache)
+            -> &'s mut ComputePipelineBuilder<'b> {
+        self.pipeline_cache = Some(pipeline_cache);
+        self
+    }
+
+    /// Returns the create info with the shader stage applied.
+    fn create_info(&self) -> ComputePipelineCreateInfo<'b> {
+        let mut create_info = self.create_info.clone();
+        create_info.set_stage(self.stage.clone());
+        create_info
+    }
+
+    /// Creates and returns a new `ComputePipeline`. Use
+    /// `ComputePipeline::create` to create multiple pipelines in one call.
+    pub fn build(&self, device: Device) -> VdResult<ComputePipeline> {
+        let handle = unsafe {
+            device.create_compute_pipelines(self.pipeline_cache.map(|pc| pc.handle()),
+                &[self.create_info()], None)?[0]
+        };
+
+        Ok(ComputePipeline {
+            inner: Arc::new(Inner {
+                handle,
+                device,
+            })
+        })
+    }
+}
+
+impl<'b> AsRef<ComputePipelineBuilder<'b>> for ComputePipelineBuilder<'b> {
+    fn as_ref(&self) -> &ComputePipelineBuilder<'b> {
+        self
+    }
+}
@@ -1,7 +1,9 @@
 use std::sync::Arc;
+use std::slice;
 use std::marker::PhantomData;
+use smallvec::SmallVec;
 use vks;
-use ::{VdResult, Device, Handle};
+use ::{VdResult, Device, Handle, Sampler, SamplerHandle, DescriptorType};
 
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -29,6 +31,7 @@ unsafe impl Handle for DescriptorSetLayoutHandle {
 struct Inner {
     handle: DescriptorSetLayoutHandle,
     device: Device,
+    _immutable_samplers: SmallVec<[Sampler; 4]>,
 }
 
 impl Drop for Inner {
@@ -112,14 +115,46 @@ impl<'b> DescriptorSetLayoutBuilder<'b> {
         self
     }
 
+    /// Returns the samplers from `device`'s sampler cache referenced by the
+    /// immutable samplers of the bindings.
+    fn cached_immutable_samplers(&self, device: &Device) -> SmallVec<[Sampler; 4]> {
+        let mut samplers: SmallVec<[Sampler; 4]> = SmallVec::new();
+        for binding in self.create_info.bindings() {
+            let raw = binding.as_raw();
+            let uses_samplers = match binding.descriptor_type() {
+                DescriptorType::Sampler | DescriptorType::CombinedImageSampler => true,
+                _ => false,
+            };
+            if !uses_samplers || raw.pImmutableSamplers.is_null() { continue; }
+
+            let handles = unsafe {
+                slice::from_raw_parts(raw.pImmutableSamplers as *const SamplerHandle,
+                    raw.descriptorCount as usize)
+            };
+            for &handle in handles {
+                if samplers.iter().any(|s| s.handle() == handle) { continue; }
+                if let Some(sampler) = device.sampler_cache().find(handle) {
+                    samplers.push(sampler);
+                }
+            }
+        }
+        samplers
+    }
+
     /// Creates and returns a new `DescriptorSetLayout`
+    ///
+    /// Immutable samplers of the bindings which were obtained from
+    /// `Device::cached_sampler` are kept alive for as long as the layout.
+    /// Any other immutable samplers must be kept alive by the caller.
     pub fn build(&self, device: Device) -> VdResult<DescriptorSetLayout> {
+        let immutable_samplers = self.cached_immutable_samplers(&device);
         let handle = unsafe { device.create_descriptor_set_layout(&self.create_info, None)? };
 
         Ok(DescriptorSetLayout {
             inner: Arc::new(Inner {
                 handle,
                 device,
+                _immutable_samplers: immutable_samplers,
             })
         })
     }
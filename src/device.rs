@@ -35,7 +35,8 @@ use ::{error, VdResult, Instance, PhysicalDevice, DeviceQueueCreateInfo, CharStr
     FenceGetFdInfoKhr, ImageMemoryRequirementsInfo2Khr, ImageSparseMemoryRequirementsInfo2Khr,
     DebugMarkerObjectTagInfoExt, DebugMarkerObjectNameInfoExt, DisplayPowerInfoExt,
     DisplayKhrHandle, DeviceEventInfoExt, DisplayEventInfoExt, HdrMetadataExt,
-    SurfaceCounterFlagsExt, Queue};
+    SurfaceCounterFlagsExt, Queue, MemoryStats, LiveObject, Sampler, SamplerCache};
+use memory_stats::MemoryTracker;
 
 // #[cfg(feature = "experimental")]
 // use ::{};
@@ -88,10 +89,12 @@ fn get_device_queue(proc_addr_loader: &vks::DeviceProcAddrLoader, device: Device
 struct Inner {
     handle: DeviceHandle,
     physical_device: PhysicalDevice,
-    // features: vks::VkPhysicalDeviceFeatures,
+    enabled_features: PhysicalDeviceFeatures,
     queues: SmallVec<[Queue; 16]>,
     instance: Instance,
     loader: vks::DeviceProcAddrLoader,
+    memory_tracker: MemoryTracker,
+    sampler_cache: SamplerCache,
 }
 
 impl Drop for Inner {
@@ -162,6 +165,46 @@ impl Device {
         &self.inner.instance
     }
 
+    /// Returns the features enabled when this device was created.
+    #[inline]
+    pub fn enabled_features(&self) -> &PhysicalDeviceFeatures {
+        &self.inner.enabled_features
+    }
+
+    /// Returns a snapshot of the device memory currently allocated through
+    /// this device, broken down by heap, memory type, and tag.
+    pub fn memory_stats(&self) -> MemoryStats {
+        self.inner.memory_tracker.stats()
+    }
+
+    /// Returns a list of the tracked child objects (`DeviceMemory`, `Buffer`,
+    /// and `Image`) of this device which are still alive, oldest first.
+    ///
+    /// Called at shutdown, once every tracked child object owned by the
+    /// application is expected to have been dropped, this reports leaks. Each
+    /// `LiveObject` displays as a one-line description.
+    pub fn live_objects(&self) -> Vec<LiveObject> {
+        self.inner.memory_tracker.live_objects()
+    }
+
+    /// Returns a reference to the memory and object tracker.
+    #[inline]
+    pub(crate) fn memory_tracker(&self) -> &MemoryTracker {
+        &self.inner.memory_tracker
+    }
+
+    /// Returns a reference to this device's sampler cache.
+    #[inline]
+    pub fn sampler_cache(&self) -> &SamplerCache {
+        &self.inner.sampler_cache
+    }
+
+    /// Returns a sampler matching `create_info` from this device's sampler
+    /// cache, creating it if no such sampler is alive.
+    pub fn cached_sampler(&self, create_info: &SamplerCreateInfo) -> VdResult<Sampler> {
+        self.inner.sampler_cache.get(self, create_info)
+    }
+
     /// Waits for this device to become idle.
     ///
     /// https://www.khronos.org/registry/vulkan/specs/1.0/man/html/vkDeviceWaitIdle.html
@@ -173,20 +216,15 @@ impl Device {
 
     /// Returns the memory type index on this device matching the provided
     /// type filter and properties.
+    ///
+    /// Use a `MemoryTypeSelector` for preferred or forbidden properties and
+    /// fallbacks.
     //
     pub fn memory_type_index(&self, type_filter: u32, properties: ::MemoryPropertyFlags)
             -> VdResult<u32> {
-        let mem_props = self.physical_device().memory_properties();
-
-        for i in 0..mem_props.memory_type_count() {
-            if (type_filter & (1 << i)) != 0 &&
-                (mem_props.memory_types()[i as usize].property_flags() & properties) == properties
-            {
-                return Ok(i);
-            }
-        }
-        panic!("failed to find suitable memory type index with: type_filter: '{}', properties: '{:?}'",
-            type_filter, properties);
+        ::MemoryTypeSelector::new()
+            .required(properties)
+            .select(self, type_filter)
     }
 
 
@@ -426,12 +464,13 @@ impl Device {
     //
     // *PFN_vkQueueBindSparse)(VkQueue queue, uint32_t bindInfoCount, const
     // VkBindSparseInfo* pBindInfo, VkFence fence);
-    pub unsafe fn queue_bind_sparse<Q, F>(&self, queue: Q, bind_info: &[BindSparseInfo], fence: F)
-            -> VdResult<()>
-            where Q: Handle<Target=QueueHandle>, F: Handle<Target=FenceHandle> {
+    pub unsafe fn queue_bind_sparse<Q>(&self, queue: Q, bind_info: &[BindSparseInfo],
+            fence: Option<FenceHandle>) -> VdResult<()>
+            where Q: Handle<Target=QueueHandle> {
+        let fence_handle_raw = fence.map(|f| f.to_raw()).unwrap_or(0);
         let result = self.proc_addr_loader().vk.vkQueueBindSparse(queue.handle().to_raw(),
             bind_info.len() as u32, bind_info.as_ptr() as *const _ as *const vks::VkBindSparseInfo,
-            fence.handle().to_raw());
+            fence_handle_raw);
         error::check(result, "vkQueueBindSparse", ())
     }
 
@@ -3100,14 +3139,23 @@ impl<'db> DeviceBuilder<'db> {
         }
 
         let instance = physical_device.instance().clone();
+        let memory_tracker = MemoryTracker::new(&physical_device.memory_properties());
+        let enabled_features = if self.create_info.as_raw().pEnabledFeatures.is_null() {
+            PhysicalDeviceFeatures::default()
+        } else {
+            self.create_info.enabled_features().clone()
+        };
 
         let device = Device {
             inner: Arc::new(Inner {
                 handle,
                 physical_device,
+                enabled_features,
                 queues: SmallVec::new(),
                 instance,
                 loader,
+                memory_tracker,
+                sampler_cache: SamplerCache::new(),
             }),
         };
 
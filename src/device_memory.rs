@@ -1,11 +1,13 @@
 
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::mem;
-use std::ops::{Deref, DerefMut};
+use std::ptr;
+use std::ops::{Deref, DerefMut, Range};
 use std::slice;
 use std::marker::PhantomData;
 use vks;
-use ::{VdResult, Device, Handle, MemoryAllocateInfo, MemoryMapFlags};
+use ::{VdResult, Device, Handle, MemoryAllocateInfo, MemoryMapFlags, MemoryPropertyFlags,
+    MappedMemoryRange, DeviceSize, ErrorKind};
 
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -58,17 +60,67 @@ impl<'m, T> DerefMut for MemoryMapping<'m, T> {
 }
 
 
+/// Plain old data: types which can be copied to and from device memory
+/// byte-for-byte.
+///
+/// ## Safety
+///
+/// Implementors must be `#[repr(C)]` (or primitive), contain no padding
+/// bytes, pointers, or references, and must be valid for any bit pattern.
+///
+/// ## Example
+///
+/// ```text
+/// #[derive(Clone, Copy)]
+/// #[repr(C)]
+/// struct Vertex { pos: [f32; 3], color: [f32; 3] }
+///
+/// unsafe impl Pod for Vertex {}
+/// ```
+///
+pub unsafe trait Pod: Copy + 'static {}
+
+macro_rules! impl_pod {
+    ($($ty:ty),*) => { $(unsafe impl Pod for $ty {})* };
+}
+
+macro_rules! impl_pod_arrays {
+    ($($len:expr),*) => { $(unsafe impl<T: Pod> Pod for [T; $len] {})* };
+}
+
+impl_pod!(u8, i8, u16, i16, u32, i32, u64, i64, usize, isize, f32, f64);
+impl_pod_arrays!(1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22,
+    23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 48, 64, 128, 256, 512, 1024);
+
+
+/// The mapping state of a `DeviceMemory`.
+#[derive(Debug)]
+enum MapState {
+    Unmapped,
+    // Mapped by `DeviceMemory::map_to_ptr` or `DeviceMemory::map`.
+    Mapped,
+    // Persistently mapped. The pointer to the start of the memory is stored
+    // as an address to keep `Inner` `Send` and `Sync`.
+    Persistent(usize),
+}
+
+
 #[derive(Debug)]
 struct Inner {
     handle: DeviceMemoryHandle,
     device: Device,
     allocation_size: u64,
     memory_type_index: u32,
+    tag: Option<String>,
+    tracking_id: u64,
+    map_state: Mutex<MapState>,
 }
 
 impl Drop for Inner {
     fn drop(&mut self) {
         unsafe { self.device.free_memory(self.handle, None); }
+        self.device.memory_tracker().remove_allocation(self.tracking_id, self.memory_type_index,
+            self.allocation_size, self.tag.as_ref().map(|t| t.as_str()));
     }
 }
 
@@ -105,20 +157,77 @@ impl DeviceMemory {
     ///
     /// Use `::unmap_ptr` to unmap this memory.
     ///
+    /// Returns an `ErrorKind::MemoryAlreadyMapped` error if this memory is
+    /// already mapped (including persistently, see `::persistent_mapping`).
+    ///
     /// The `flags` argument is reserved for future use and is ignored.
     pub unsafe fn map_to_ptr<T>(&self, offset_bytes: u64, size_bytes: u64,
             flags: MemoryMapFlags)
             -> VdResult<*mut T> {
-        self.inner.device.map_memory(self.inner.handle, offset_bytes, size_bytes, flags)
+        let mut map_state = self.inner.map_state.lock().unwrap();
+        match *map_state {
+            MapState::Unmapped => (),
+            _ => return Err(ErrorKind::MemoryAlreadyMapped.into()),
+        }
+        let ptr = self.inner.device.map_memory(self.inner.handle, offset_bytes, size_bytes,
+            flags)?;
+        *map_state = MapState::Mapped;
+        Ok(ptr)
     }
 
     /// Unmaps memory.
     ///
     /// Do not use this unless memory was mapped using `::map_to_ptr`.
+    /// Persistent mappings are unaffected.
     ///
     /// Use `::unmap` to unmap memory mapped by `::map`.
     pub unsafe fn unmap_ptr(&self) {
-        self.inner.device.unmap_memory(self.inner.handle);
+        let mut map_state = self.inner.map_state.lock().unwrap();
+        if let MapState::Mapped = *map_state {
+            self.inner.device.unmap_memory(self.inner.handle);
+            *map_state = MapState::Unmapped;
+        }
+    }
+
+    /// Returns a mapping of this entire memory object, mapping it the first
+    /// time this is called.
+    ///
+    /// The memory remains mapped until it is freed. All returned mappings
+    /// share the same underlying pointer.
+    ///
+    /// Returns an error if the memory type is not host-visible or if this
+    /// memory is currently mapped using `::map_to_ptr` or `::map`.
+    pub fn persistent_mapping(&self) -> VdResult<PersistentMapping> {
+        let device = &self.inner.device;
+        let property_flags = device.physical_device().memory_properties()
+            .memory_types()[self.inner.memory_type_index as usize].property_flags();
+        if !property_flags.contains(MemoryPropertyFlags::HOST_VISIBLE) {
+            return Err(ErrorKind::MemoryNotHostVisible {
+                memory_type_index: self.inner.memory_type_index }.into());
+        }
+
+        let mut map_state = self.inner.map_state.lock().unwrap();
+        let ptr = match *map_state {
+            MapState::Persistent(addr) => addr as *mut u8,
+            MapState::Mapped => return Err(ErrorKind::MemoryAlreadyMapped.into()),
+            MapState::Unmapped => {
+                let ptr: *mut u8 = unsafe {
+                    device.map_memory(self.inner.handle, 0, self.inner.allocation_size,
+                        MemoryMapFlags::empty())?
+                };
+                *map_state = MapState::Persistent(ptr as usize);
+                ptr
+            },
+        };
+
+        Ok(PersistentMapping {
+            memory: self.clone(),
+            ptr,
+            size: self.inner.allocation_size,
+            is_coherent: property_flags.contains(MemoryPropertyFlags::HOST_COHERENT),
+            non_coherent_atom_size: device.physical_device().properties().limits()
+                .non_coherent_atom_size(),
+        })
     }
 
     /// Maps a region of memory and returns a mutable reference to it.
@@ -176,6 +285,26 @@ impl DeviceMemory {
         self.inner.handle
     }
 
+    /// Returns the size of this allocation in bytes.
+    pub fn allocation_size(&self) -> u64 {
+        self.inner.allocation_size
+    }
+
+    /// Returns the memory type index of this allocation.
+    pub fn memory_type_index(&self) -> u32 {
+        self.inner.memory_type_index
+    }
+
+    /// Returns the tag this memory was allocated with, if any.
+    pub fn tag(&self) -> Option<&str> {
+        self.inner.tag.as_ref().map(|t| t.as_str())
+    }
+
+    /// Sets the name used to identify this memory in leak reports.
+    pub fn set_debug_name(&self, name: &str) {
+        self.inner.device.memory_tracker().set_name(self.inner.tracking_id, name);
+    }
+
     /// Returns a reference to the associated device.
     pub fn device(&self) -> &Device {
         &self.inner.device
@@ -195,6 +324,7 @@ unsafe impl<'h> Handle for &'h DeviceMemory {
 #[derive(Debug, Clone)]
 pub struct DeviceMemoryBuilder<'b> {
     allocate_info: MemoryAllocateInfo<'b>,
+    tag: Option<String>,
 }
 
 impl<'b> DeviceMemoryBuilder<'b> {
@@ -202,6 +332,7 @@ impl<'b> DeviceMemoryBuilder<'b> {
     pub fn new() -> DeviceMemoryBuilder<'b> {
         DeviceMemoryBuilder {
             allocate_info: MemoryAllocateInfo::default(),
+            tag: None,
         }
     }
 
@@ -220,17 +351,233 @@ impl<'b> DeviceMemoryBuilder<'b> {
         self
     }
 
+    /// Specifies a tag under which this allocation is counted in
+    /// `Device::memory_stats`.
+    pub fn tag<'s>(&'s mut self, tag: &str) -> &'s mut DeviceMemoryBuilder<'b> {
+        self.tag = Some(tag.to_owned());
+        self
+    }
+
     /// Creates and returns a new `DeviceMemory`
     pub fn build(&self, device: Device) -> VdResult<DeviceMemory> {
         let handle = unsafe { device.allocate_memory(&self.allocate_info, None)? };
+        let allocation_size = self.allocate_info.allocation_size();
+        let memory_type_index = self.allocate_info.memory_type_index();
+        let tracking_id = device.memory_tracker().add_allocation(memory_type_index,
+            allocation_size, self.tag.as_ref().map(|t| t.as_str()));
 
         Ok(DeviceMemory {
             inner: Arc::new(Inner {
                 handle,
                 device,
-                allocation_size: self.allocate_info.allocation_size(),
-                memory_type_index: self.allocate_info.memory_type_index(),
+                allocation_size,
+                memory_type_index,
+                tag: self.tag.clone(),
+                tracking_id,
+                map_state: Mutex::new(MapState::Unmapped),
             })
         })
     }
-}
\ No newline at end of file
+}
+
+
+/// Ensures that `range` lies within a mapping of `size` bytes, is aligned
+/// for `T`, and holds a whole number of `T`s.
+fn check_range<T: Pod>(size: DeviceSize, range: &Range<DeviceSize>) -> VdResult<()> {
+    let invalid = |reason: &'static str| -> VdResult<()> {
+        Err(ErrorKind::InvalidMappedRange { offset: range.start,
+            size: range.end.saturating_sub(range.start), reason }.into())
+    };
+
+    if range.start > range.end || range.end > size {
+        return invalid("range out of bounds");
+    }
+    if range.start % mem::align_of::<T>() as DeviceSize != 0 {
+        return invalid("offset not aligned to the element type");
+    }
+    if mem::size_of::<T>() != 0 &&
+            (range.end - range.start) % mem::size_of::<T>() as DeviceSize != 0 {
+        return invalid("size not a multiple of the element size");
+    }
+    Ok(())
+}
+
+/// Returns the in-bounds `range` rounded outward to multiples of `atom`
+/// (clamped to `size`).
+fn atom_bounds(size: DeviceSize, atom: DeviceSize, range: &Range<DeviceSize>)
+        -> Range<DeviceSize> {
+    let atom = if atom == 0 { 1 } else { atom };
+    let start = (range.start / atom) * atom;
+    let end = match range.end % atom {
+        0 => range.end,
+        rem => range.end.saturating_add(atom - rem),
+    };
+    start..if end > size { size } else { end }
+}
+
+
+/// A persistent mapping of an entire `DeviceMemory` object.
+///
+/// Obtain using `DeviceMemory::persistent_mapping`. Clones share the same
+/// mapping and keep the memory alive.
+///
+/// Writes are flushed and reads are invalidated automatically when the
+/// memory type is not `HOST_COHERENT`, with ranges rounded outward to
+/// multiples of `nonCoherentAtomSize`.
+///
+/// The caller is responsible for synchronizing access to a range with the
+/// device (e.g. using fences) and with other threads writing to the same
+/// range.
+///
+#[derive(Debug, Clone)]
+pub struct PersistentMapping {
+    memory: DeviceMemory,
+    ptr: *mut u8,
+    size: DeviceSize,
+    is_coherent: bool,
+    non_coherent_atom_size: DeviceSize,
+}
+
+impl PersistentMapping {
+    /// Ensures that `range` lies within the mapping, is aligned for `T`, and
+    /// holds a whole number of `T`s.
+    fn check_range<T: Pod>(&self, range: &Range<DeviceSize>) -> VdResult<()> {
+        check_range::<T>(self.size, range)
+    }
+
+    /// Returns `range` rounded outward to multiples of `nonCoherentAtomSize`
+    /// (clamped to the size of the memory).
+    fn atom_range(&self, range: &Range<DeviceSize>) -> MappedMemoryRange {
+        let Range { start, end } = atom_bounds(self.size, self.non_coherent_atom_size, range);
+        MappedMemoryRange::builder()
+            .memory(&self.memory)
+            .offset(start)
+            .size(end - start)
+            .build()
+    }
+
+    /// Copies `data` to the byte range `range` of the mapped memory.
+    ///
+    /// The length of `range` must equal the size of `data` in bytes.
+    pub fn write<T: Pod>(&self, range: Range<DeviceSize>, data: &[T]) -> VdResult<()> {
+        self.check_range::<T>(&range)?;
+        let len = range.end - range.start;
+        if len != (mem::size_of::<T>() * data.len()) as DeviceSize {
+            return Err(ErrorKind::InvalidMappedRange { offset: range.start, size: len,
+                reason: "size does not match the size of the data" }.into());
+        }
+
+        unsafe {
+            ptr::copy_nonoverlapping(data.as_ptr() as *const u8,
+                self.ptr.offset(range.start as isize), len as usize);
+        }
+        self.flush(range)
+    }
+
+    /// Returns a copy of the contents of the byte range `range` of the
+    /// mapped memory.
+    pub fn read<T: Pod>(&self, range: Range<DeviceSize>) -> VdResult<Vec<T>> {
+        self.check_range::<T>(&range)?;
+        self.invalidate(range.clone())?;
+
+        let len = if mem::size_of::<T>() == 0 {
+            0
+        } else {
+            (range.end - range.start) as usize / mem::size_of::<T>()
+        };
+        let mut data = Vec::with_capacity(len);
+        unsafe {
+            ptr::copy_nonoverlapping(self.ptr.offset(range.start as isize),
+                data.as_mut_ptr() as *mut u8, (range.end - range.start) as usize);
+            data.set_len(len);
+        }
+        Ok(data)
+    }
+
+    /// Flushes host writes to the byte range `range` so that they are
+    /// visible to the device.
+    ///
+    /// Does nothing if the memory is host-coherent.
+    pub fn flush(&self, range: Range<DeviceSize>) -> VdResult<()> {
+        self.check_range::<u8>(&range)?;
+        if self.is_coherent || range.start == range.end { return Ok(()); }
+        let range = self.atom_range(&range);
+        unsafe { self.memory.device().flush_mapped_memory_ranges(&[range]) }
+    }
+
+    /// Invalidates the byte range `range` so that device writes are visible
+    /// to the host.
+    ///
+    /// Does nothing if the memory is host-coherent.
+    pub fn invalidate(&self, range: Range<DeviceSize>) -> VdResult<()> {
+        self.check_range::<u8>(&range)?;
+        if self.is_coherent || range.start == range.end { return Ok(()); }
+        let range = self.atom_range(&range);
+        unsafe { self.memory.device().invalidate_mapped_memory_ranges(&[range]) }
+    }
+
+    /// Returns a pointer to the start of the mapped memory.
+    pub fn as_ptr(&self) -> *mut u8 {
+        self.ptr
+    }
+
+    /// Returns the size of the mapped memory in bytes.
+    pub fn size(&self) -> DeviceSize {
+        self.size
+    }
+
+    /// Returns `true` if the mapped memory is host-coherent.
+    pub fn is_coherent(&self) -> bool {
+        self.is_coherent
+    }
+
+    /// Returns a reference to the mapped memory object.
+    pub fn memory(&self) -> &DeviceMemory {
+        &self.memory
+    }
+}
+
+unsafe impl Send for PersistentMapping {}
+unsafe impl Sync for PersistentMapping {}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn invalid_reason(res: VdResult<()>) -> &'static str {
+        match res {
+            Err(err) => match *err.kind() {
+                ErrorKind::InvalidMappedRange { reason, .. } => reason,
+                _ => panic!("unexpected error: {}", err),
+            },
+            Ok(()) => panic!("invalid range accepted"),
+        }
+    }
+
+    #[test]
+    fn range_checks() {
+        assert!(check_range::<u8>(64, &(0..64)).is_ok());
+        assert!(check_range::<u32>(64, &(4..12)).is_ok());
+        assert!(check_range::<u64>(64, &(64..64)).is_ok());
+
+        assert_eq!(invalid_reason(check_range::<u8>(64, &(0..65))), "range out of bounds");
+        assert_eq!(invalid_reason(check_range::<u8>(64, &(8..4))), "range out of bounds");
+        assert_eq!(invalid_reason(check_range::<u8>(64, &(65..u64::max_value()))),
+            "range out of bounds");
+        assert_eq!(invalid_reason(check_range::<u32>(64, &(2..6))),
+            "offset not aligned to the element type");
+        assert_eq!(invalid_reason(check_range::<u32>(64, &(4..10))),
+            "size not a multiple of the element size");
+    }
+
+    #[test]
+    fn atom_rounding() {
+        assert_eq!(atom_bounds(1024, 64, &(0..64)), 0..64);
+        assert_eq!(atom_bounds(1024, 64, &(65..129)), 64..192);
+        assert_eq!(atom_bounds(1000, 64, &(900..999)), 896..1000);
+        assert_eq!(atom_bounds(1000, 0, &(3..7)), 3..7);
+        assert_eq!(atom_bounds(u64::max_value(), 256, &(0..u64::max_value() - 1)),
+            0..u64::max_value());
+    }
+}
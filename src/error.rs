@@ -1,6 +1,6 @@
 use std::error::Error as StdError;
 use std::result::Result as StdResult;
-use ::CallResult;
+use ::{CallResult, MemoryPropertyFlags, DeviceSize, Format, BufferUsageFlags};
 
 
 pub enum ErrorKind {
@@ -12,7 +12,31 @@ pub enum ErrorKind {
     FromUtf8Error(::std::string::FromUtf8Error),
     UnspecifiedDimensions,
     IntoStringError(::std::ffi::IntoStringError),
-    FromBytesWithNulError(::std::ffi::FromBytesWithNulError)
+    FromBytesWithNulError(::std::ffi::FromBytesWithNulError),
+    NoSuitableMemoryType { type_filter: u32, required: MemoryPropertyFlags,
+        forbidden: MemoryPropertyFlags },
+    OutOfStagingSpace { requested: DeviceSize, capacity: DeviceSize },
+    InvalidUpload { reason: &'static str },
+    MemoryAlreadyMapped,
+    MemoryNotHostVisible { memory_type_index: u32 },
+    InvalidMappedRange { offset: DeviceSize, size: DeviceSize, reason: &'static str },
+    InvalidSparseBinding { reason: &'static str },
+    UndeclaredImage,
+    MipmapGenerationUnsupported { format: Format, reason: &'static str },
+    InvalidTextureData { reason: &'static str },
+    UnsupportedTexture { format: Format, reason: &'static str },
+    InvalidReadback { reason: &'static str },
+    UnsupportedExportFormat { format: Format },
+    InvalidBufferView { reason: &'static str },
+    InvalidTypedBuffer { reason: &'static str },
+    UnsupportedBufferViewFormat { format: Format, usage: BufferUsageFlags },
+    InvalidQuery { reason: &'static str },
+    InvalidWait { reason: &'static str },
+    IncompatiblePipelineCache { reason: &'static str },
+    InvalidSpirV { reason: &'static str },
+    InvalidSpecialization { constant_id: u32, reason: &'static str },
+    InvalidPushConstants { size: u32, reason: &'static str },
+    ShaderCompilation { path: String, output: String },
 }
 
 
@@ -64,6 +88,49 @@ impl self::Error {
             ErrorKind::String(ref desc) => write!(f, "{}", desc),
             ErrorKind::UnspecifiedDimensions => write!(f, "Cannot convert to a valid set of \
                 dimensions. Please specify some dimensions."),
+            ErrorKind::NoSuitableMemoryType { type_filter, required, forbidden } => write!(f,
+                "No suitable memory type found (type_filter: '{:#b}', required: '{:?}', \
+                forbidden: '{:?}')", type_filter, required, forbidden),
+            ErrorKind::OutOfStagingSpace { requested, capacity } => write!(f,
+                "Insufficient staging space (requested: {}, capacity: {})", requested, capacity),
+            ErrorKind::InvalidUpload { reason } => write!(f, "Invalid upload: {}", reason),
+            ErrorKind::MemoryAlreadyMapped => write!(f, "Device memory is already mapped"),
+            ErrorKind::MemoryNotHostVisible { memory_type_index } => write!(f,
+                "Device memory is not host-visible (memory_type_index: {})", memory_type_index),
+            ErrorKind::InvalidMappedRange { offset, size, reason } => write!(f,
+                "Invalid mapped memory range (offset: {}, size: {}): {}", offset, size, reason),
+            ErrorKind::InvalidSparseBinding { reason } => write!(f,
+                "Invalid sparse binding: {}", reason),
+            ErrorKind::UndeclaredImage => write!(f,
+                "Image used by an image tracker without its initial layout being declared"),
+            ErrorKind::MipmapGenerationUnsupported { format, reason } => write!(f,
+                "Unable to generate mipmaps for an image of format {:?}: {}", format, reason),
+            ErrorKind::InvalidTextureData { reason } => write!(f,
+                "Invalid texture data: {}", reason),
+            ErrorKind::UnsupportedTexture { format, reason } => write!(f,
+                "Unsupported texture of format {:?}: {}", format, reason),
+            ErrorKind::InvalidReadback { reason } => write!(f,
+                "Invalid readback: {}", reason),
+            ErrorKind::UnsupportedExportFormat { format } => write!(f,
+                "Unsupported format for image export: {:?}", format),
+            ErrorKind::InvalidBufferView { reason } => write!(f,
+                "Invalid buffer view: {}", reason),
+            ErrorKind::InvalidTypedBuffer { reason } => write!(f,
+                "Invalid typed buffer: {}", reason),
+            ErrorKind::UnsupportedBufferViewFormat { format, usage } => write!(f,
+                "Format {:?} does not support buffer views for usage {:?}", format, usage),
+            ErrorKind::InvalidQuery { reason } => write!(f,
+                "Invalid query: {}", reason),
+            ErrorKind::InvalidWait { reason } => write!(f, "Invalid wait: {}", reason),
+            ErrorKind::IncompatiblePipelineCache { reason } => write!(f,
+                "Incompatible pipeline cache data: {}", reason),
+            ErrorKind::InvalidSpirV { reason } => write!(f, "Invalid SPIR-V module: {}", reason),
+            ErrorKind::InvalidSpecialization { constant_id, reason } => write!(f,
+                "Invalid specialization constant {}: {}", constant_id, reason),
+            ErrorKind::InvalidPushConstants { size, reason } => write!(f,
+                "Invalid push constants of {} bytes: {}", size, reason),
+            ErrorKind::ShaderCompilation { ref path, ref output } => write!(f,
+                "Failed to compile shader '{}':\n{}", path, output),
         }
     }
 
@@ -105,6 +172,29 @@ impl StdError for self::Error {
             ErrorKind::String(ref desc) => desc.as_str(),
             ErrorKind::UnspecifiedDimensions => "Cannot convert to a valid set of dimensions. \
                 Please specify some dimensions.",
+            ErrorKind::NoSuitableMemoryType { .. } => "No suitable memory type found",
+            ErrorKind::OutOfStagingSpace { .. } => "Insufficient staging space",
+            ErrorKind::InvalidUpload { .. } => "Invalid upload",
+            ErrorKind::MemoryAlreadyMapped => "Device memory is already mapped",
+            ErrorKind::MemoryNotHostVisible { .. } => "Device memory is not host-visible",
+            ErrorKind::InvalidMappedRange { .. } => "Invalid mapped memory range",
+            ErrorKind::InvalidSparseBinding { .. } => "Invalid sparse binding",
+            ErrorKind::UndeclaredImage => "Image layout undeclared",
+            ErrorKind::MipmapGenerationUnsupported { .. } => "Mipmap generation unsupported",
+            ErrorKind::InvalidTextureData { .. } => "Invalid texture data",
+            ErrorKind::UnsupportedTexture { .. } => "Unsupported texture",
+            ErrorKind::InvalidReadback { .. } => "Invalid readback",
+            ErrorKind::UnsupportedExportFormat { .. } => "Unsupported format for image export",
+            ErrorKind::InvalidBufferView { .. } => "Invalid buffer view",
+            ErrorKind::InvalidTypedBuffer { .. } => "Invalid typed buffer",
+            ErrorKind::UnsupportedBufferViewFormat { .. } => "Unsupported buffer view format",
+            ErrorKind::InvalidQuery { .. } => "Invalid query",
+            ErrorKind::InvalidWait { .. } => "Invalid wait",
+            ErrorKind::IncompatiblePipelineCache { .. } => "Incompatible pipeline cache data",
+            ErrorKind::InvalidSpirV { .. } => "Invalid SPIR-V module",
+            ErrorKind::InvalidSpecialization { .. } => "Invalid specialization constant",
+            ErrorKind::InvalidPushConstants { .. } => "Invalid push constants",
+            ErrorKind::ShaderCompilation { .. } => "Failed to compile shader",
             // _ => panic!("OclErrorKind::description()"),
         }
     }
@@ -117,6 +207,12 @@ impl StdError for self::Error {
     }
 }
 
+impl From<self::ErrorKind> for self::Error {
+    fn from(kind: self::ErrorKind) -> Self {
+        Error { kind, cause: None }
+    }
+}
+
 impl From<()> for self::Error {
     fn from(_: ()) -> Self {
         Error { kind: self::ErrorKind::Void, cause: None }
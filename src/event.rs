@@ -1,6 +1,9 @@
 use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+use std::cmp;
 use vks;
-use ::{VdResult, CallResult, Handle, Device, EventCreateFlags, EventCreateInfo};
+use ::{VdResult, CallResult, Handle, Device, EventCreateFlags, EventCreateInfo, ErrorKind};
 
 pub enum EventStatus {
     Signaled,
@@ -26,6 +29,65 @@ impl From<i32> for EventStatus {
 
 
 
+/// How a host thread waiting on events pauses between status checks.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Backoff {
+    /// Checks continuously, for the lowest latency at the cost of a core.
+    Spin,
+    /// Yields to the scheduler between checks.
+    Yield,
+    /// Sleeps between checks, starting at `initial` and doubling up to `max`.
+    Exponential { initial: Duration, max: Duration },
+}
+
+impl Default for Backoff {
+    /// Returns an exponential backoff from one microsecond to one
+    /// millisecond.
+    fn default() -> Backoff {
+        Backoff::Exponential { initial: Duration::new(0, 1_000), max: Duration::new(0, 1_000_000) }
+    }
+}
+
+/// Calls `poll` until it returns `Some` or `timeout` nanoseconds have passed,
+/// pausing between calls according to `backoff`.
+fn poll_until<T, F>(timeout: u64, backoff: Backoff, mut poll: F) -> VdResult<Option<T>>
+        where F: FnMut() -> VdResult<Option<T>> {
+    let start = Instant::now();
+    let timeout = if timeout == u64::max_value() {
+        None
+    } else {
+        Some(Duration::new(timeout / 1_000_000_000, (timeout % 1_000_000_000) as u32))
+    };
+    let mut delay = match backoff {
+        Backoff::Exponential { initial, .. } => initial,
+        _ => Duration::new(0, 0),
+    };
+
+    loop {
+        if let Some(result) = poll()? {
+            return Ok(Some(result));
+        }
+        let remaining = match timeout {
+            Some(timeout) => {
+                let elapsed = start.elapsed();
+                if elapsed >= timeout { return Ok(None); }
+                Some(timeout - elapsed)
+            },
+            None => None,
+        };
+
+        match backoff {
+            Backoff::Spin => (),
+            Backoff::Yield => thread::yield_now(),
+            Backoff::Exponential { max, .. } => {
+                thread::sleep(remaining.map(|r| cmp::min(r, delay)).unwrap_or(delay));
+                delay = cmp::min(delay * 2, max);
+            },
+        }
+    }
+}
+
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 #[repr(C)]
 pub struct EventHandle(pub(crate) vks::VkEvent);
@@ -123,6 +185,58 @@ impl Event {
     pub fn status(&self) -> VdResult<EventStatus> {
         unsafe { Ok(self.inner.device.get_event_status(self.handle())?.into()) }
     }
+
+    /// Returns true if this event is signaled.
+    fn is_set(&self) -> VdResult<bool> {
+        match self.status()? {
+            EventStatus::Signaled => Ok(true),
+            EventStatus::Unsignaled => Ok(false),
+            EventStatus::Error(res) => Err(ErrorKind::ApiCall(res, "vkGetEventStatus").into()),
+        }
+    }
+
+    /// Blocks until this event is signaled or `timeout` nanoseconds have
+    /// passed, using the default `Backoff`. Returns false on timeout.
+    ///
+    /// A `timeout` of `u64::max_value()` waits indefinitely.
+    pub fn wait(&self, timeout: u64) -> VdResult<bool> {
+        self.wait_with_backoff(timeout, Backoff::default())
+    }
+
+    /// Blocks until this event is signaled or `timeout` nanoseconds have
+    /// passed, pausing between checks according to `backoff`. Returns false
+    /// on timeout.
+    pub fn wait_with_backoff(&self, timeout: u64, backoff: Backoff) -> VdResult<bool> {
+        poll_until(timeout, backoff, || Ok(if self.is_set()? { Some(()) } else { None }))
+            .map(|set| set.is_some())
+    }
+
+    /// Blocks until any of `events` is signaled or `timeout` nanoseconds
+    /// have passed, using the default `Backoff`. Returns the index of the
+    /// first signaled event, or `None` on timeout.
+    ///
+    /// Returns an error if `events` is empty.
+    pub fn wait_any(events: &[&Event], timeout: u64) -> VdResult<Option<usize>> {
+        Event::wait_any_with_backoff(events, timeout, Backoff::default())
+    }
+
+    /// Blocks until any of `events` is signaled or `timeout` nanoseconds
+    /// have passed, pausing between checks according to `backoff`. Returns
+    /// the index of the first signaled event, or `None` on timeout.
+    ///
+    /// Returns an error if `events` is empty.
+    pub fn wait_any_with_backoff(events: &[&Event], timeout: u64, backoff: Backoff)
+            -> VdResult<Option<usize>> {
+        if events.is_empty() {
+            return Err(ErrorKind::InvalidWait { reason: "no events to wait on" }.into());
+        }
+        poll_until(timeout, backoff, || {
+            for (index, event) in events.iter().enumerate() {
+                if event.is_set()? { return Ok(Some(index)); }
+            }
+            Ok(None)
+        })
+    }
 }
 
 unsafe impl<'h> Handle for &'h Event {
@@ -133,3 +247,40 @@ unsafe impl<'h> Handle for &'h Event {
     }
 }
 
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wait_any_rejects_no_events() {
+        for &timeout in [0, u64::max_value()].iter() {
+            match Event::wait_any_with_backoff(&[], timeout, Backoff::Spin) {
+                Err(err) => match *err.kind() {
+                    ErrorKind::InvalidWait { .. } => (),
+                    _ => panic!("unexpected error: {}", err),
+                },
+                Ok(index) => panic!("returned {:?}", index),
+            }
+        }
+    }
+
+    #[test]
+    fn poll_until_returns_first_value() {
+        let mut polls = 0;
+        let res = poll_until(u64::max_value(), Backoff::Yield, || {
+            polls += 1;
+            Ok(if polls == 3 { Some(polls) } else { None })
+        });
+        assert_eq!(res.unwrap(), Some(3));
+    }
+
+    #[test]
+    fn poll_until_times_out() {
+        let backoff = Backoff::Exponential { initial: Duration::new(0, 1_000),
+            max: Duration::new(0, 100_000) };
+        let res: VdResult<Option<()>> = poll_until(1_000_000, backoff, || Ok(None));
+        assert_eq!(res.unwrap(), None);
+    }
+}
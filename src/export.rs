@@ -0,0 +1,291 @@
+//! PNG and PPM encoding of pixels read back with `Image::read_to_vec`.
+//!
+//! Supported formats are 8-bit `R`, `RGB`, `BGR`, `RGBA` and `BGRA` (`Unorm`
+//! or `Srgb`) and 16-bit `R` and `RGBA` (`Unorm`). PPM output drops alpha and
+//! writes 8-bit single-component formats as greyscale (PGM).
+//!
+//! PNG output is stored without compression.
+
+use std::fs::File;
+use std::io::{Write, BufWriter};
+use std::path::Path;
+use ::{VdResult, Format, ErrorKind};
+
+
+/// The layout of the pixels of a supported format.
+struct PixelLayout {
+    // The number of components per pixel:
+    components: usize,
+    // The size of each component in bytes:
+    component_size: usize,
+    // Whether red and blue are swapped:
+    bgr: bool,
+}
+
+fn pixel_layout(format: Format) -> VdResult<PixelLayout> {
+    let (components, component_size, bgr) = match format {
+        Format::R8Unorm | Format::R8Srgb => (1, 1, false),
+        Format::R8G8B8Unorm | Format::R8G8B8Srgb => (3, 1, false),
+        Format::B8G8R8Unorm | Format::B8G8R8Srgb => (3, 1, true),
+        Format::R8G8B8A8Unorm | Format::R8G8B8A8Srgb => (4, 1, false),
+        Format::B8G8R8A8Unorm | Format::B8G8R8A8Srgb => (4, 1, true),
+        Format::R16Unorm => (1, 2, false),
+        Format::R16G16B16A16Unorm => (4, 2, false),
+        _ => return Err(ErrorKind::UnsupportedExportFormat { format }.into()),
+    };
+    Ok(PixelLayout { components, component_size, bgr })
+}
+
+/// Converts tightly packed `pixels` to RGB(A) order with big-endian
+/// components, optionally dropping alpha.
+fn to_rgb(layout: &PixelLayout, width: u32, height: u32, pixels: &[u8], keep_alpha: bool)
+        -> VdResult<Vec<u8>> {
+    let pixel_size = layout.components * layout.component_size;
+    let pixel_count = width as usize * height as usize;
+    if pixels.len() < pixel_count * pixel_size {
+        return Err(ErrorKind::InvalidReadback { reason: "pixel data is smaller than the image" }
+            .into());
+    }
+
+    let out_components = if keep_alpha || layout.components < 4 { layout.components } else { 3 };
+    let mut out = Vec::with_capacity(pixel_count * out_components * layout.component_size);
+    for pixel in pixels[..pixel_count * pixel_size].chunks(pixel_size) {
+        for out_component in 0..out_components {
+            let component = match out_component {
+                0 if layout.bgr => 2,
+                2 if layout.bgr => 0,
+                c => c,
+            };
+            let bytes = &pixel[component * layout.component_size..
+                (component + 1) * layout.component_size];
+            // Components are little-endian in memory:
+            out.extend(bytes.iter().rev());
+        }
+    }
+    Ok(out)
+}
+
+fn crc32(crc: u32, bytes: &[u8]) -> u32 {
+    let mut crc = !crc;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { 0xEDB8_8320 ^ (crc >> 1) } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+fn write_chunk<W: Write>(writer: &mut W, kind: &[u8; 4], data: &[u8]) -> VdResult<()> {
+    writer.write_all(&(data.len() as u32).to_be_bytes())?;
+    writer.write_all(kind)?;
+    writer.write_all(data)?;
+    writer.write_all(&crc32(crc32(0, kind), data).to_be_bytes())?;
+    Ok(())
+}
+
+/// Wraps `data` in a zlib stream of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const MAX_BLOCK: usize = 0xFFFF;
+    let mut out = Vec::with_capacity(data.len() + data.len() / MAX_BLOCK * 5 + 11);
+    out.extend_from_slice(&[0x78, 0x01]);
+    let mut blocks = data.chunks(MAX_BLOCK).peekable();
+    if blocks.peek().is_none() {
+        out.extend_from_slice(&[0x01, 0x00, 0x00, 0xFF, 0xFF]);
+    }
+    while let Some(block) = blocks.next() {
+        let is_final = blocks.peek().is_none();
+        let len = block.len() as u16;
+        out.push(is_final as u8);
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    out.extend_from_slice(&((b << 16) | a).to_be_bytes());
+    out
+}
+
+/// Encodes tightly packed `pixels` of `format` as a PNG image.
+pub fn write_png<W: Write>(writer: &mut W, format: Format, width: u32, height: u32,
+        pixels: &[u8]) -> VdResult<()> {
+    let layout = pixel_layout(format)?;
+    let rgb = to_rgb(&layout, width, height, pixels, true)?;
+
+    let color_type = match layout.components {
+        1 => 0,
+        3 => 2,
+        _ => 6,
+    };
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&width.to_be_bytes());
+    header.extend_from_slice(&height.to_be_bytes());
+    header.extend_from_slice(&[(layout.component_size * 8) as u8, color_type, 0, 0, 0]);
+
+    // Each row is preceded by its filter type (none):
+    let row_size = width as usize * layout.components * layout.component_size;
+    let mut filtered = Vec::with_capacity((row_size + 1) * height as usize);
+    for row in rgb.chunks(row_size.max(1)).take(height as usize) {
+        filtered.push(0);
+        filtered.extend_from_slice(row);
+    }
+
+    writer.write_all(&[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A])?;
+    write_chunk(writer, b"IHDR", &header)?;
+    write_chunk(writer, b"IDAT", &zlib_stored(&filtered))?;
+    write_chunk(writer, b"IEND", &[])?;
+    Ok(())
+}
+
+/// Encodes tightly packed `pixels` of `format` as a binary PPM (or, for
+/// single-component formats, PGM) image.
+pub fn write_ppm<W: Write>(writer: &mut W, format: Format, width: u32, height: u32,
+        pixels: &[u8]) -> VdResult<()> {
+    let layout = pixel_layout(format)?;
+    let rgb = to_rgb(&layout, width, height, pixels, false)?;
+    let magic = if layout.components == 1 { "P5" } else { "P6" };
+    let max_value = if layout.component_size == 1 { 255 } else { 65535 };
+    write!(writer, "{}\n{} {}\n{}\n", magic, width, height, max_value)?;
+    writer.write_all(&rgb)?;
+    Ok(())
+}
+
+/// Writes tightly packed `pixels` of `format` to a PNG file at `path`.
+pub fn save_png<P: AsRef<Path>>(path: P, format: Format, width: u32, height: u32,
+        pixels: &[u8]) -> VdResult<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    write_png(&mut writer, format, width, height, pixels)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Writes tightly packed `pixels` of `format` to a PPM (or PGM) file at
+/// `path`.
+pub fn save_ppm<P: AsRef<Path>>(path: P, format: Format, width: u32, height: u32,
+        pixels: &[u8]) -> VdResult<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    write_ppm(&mut writer, format, width, height, pixels)?;
+    writer.flush()?;
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Splits a PNG into its chunks, checking each CRC.
+    fn chunks(png: &[u8]) -> Vec<([u8; 4], Vec<u8>)> {
+        assert_eq!(&png[..8], &[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]);
+        let mut chunks = Vec::new();
+        let mut pos = 8;
+        while pos < png.len() {
+            let len = u32::from_be_bytes([png[pos], png[pos + 1], png[pos + 2], png[pos + 3]])
+                as usize;
+            let kind = [png[pos + 4], png[pos + 5], png[pos + 6], png[pos + 7]];
+            let data = png[pos + 8..pos + 8 + len].to_vec();
+            let crc = &png[pos + 8 + len..pos + 12 + len];
+            assert_eq!(crc, &crc32(crc32(0, &kind), &data).to_be_bytes());
+            chunks.push((kind, data));
+            pos += 12 + len;
+        }
+        chunks
+    }
+
+    /// Returns the contents of a zlib stream of stored blocks.
+    fn unstore(zlib: &[u8]) -> Vec<u8> {
+        assert_eq!(&zlib[..2], &[0x78, 0x01]);
+        let mut data = Vec::new();
+        let mut pos = 2;
+        loop {
+            let is_final = zlib[pos] & 1 != 0;
+            let len = u16::from_le_bytes([zlib[pos + 1], zlib[pos + 2]]);
+            let nlen = u16::from_le_bytes([zlib[pos + 3], zlib[pos + 4]]);
+            assert_eq!(len, !nlen);
+            data.extend_from_slice(&zlib[pos + 5..pos + 5 + len as usize]);
+            pos += 5 + len as usize;
+            if is_final { break; }
+        }
+        assert_eq!(pos + 4, zlib.len());
+        data
+    }
+
+    #[test]
+    fn checksums() {
+        assert_eq!(crc32(0, b"123456789"), 0xCBF4_3926);
+        assert_eq!(crc32(crc32(0, b"1234"), b"56789"), 0xCBF4_3926);
+        let zlib = zlib_stored(b"Wikipedia");
+        assert_eq!(&zlib[zlib.len() - 4..], &[0x11, 0xE6, 0x03, 0x98]);
+    }
+
+    #[test]
+    fn zlib_stored_blocks() {
+        assert!(unstore(&zlib_stored(&[])).is_empty());
+
+        let data: Vec<u8> = (0..0x1_0010u32).map(|i| i as u8).collect();
+        let zlib = zlib_stored(&data);
+        assert_eq!(zlib[2], 0);
+        assert_eq!(unstore(&zlib), data);
+    }
+
+    #[test]
+    fn png_rgba() {
+        let pixels = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
+        let mut png = Vec::new();
+        write_png(&mut png, Format::B8G8R8A8Unorm, 1, 3, &pixels).unwrap();
+
+        let chunks = chunks(&png);
+        let kinds: Vec<&[u8]> = chunks.iter().map(|c| &c.0[..]).collect();
+        assert_eq!(kinds, vec![&b"IHDR"[..], &b"IDAT"[..], &b"IEND"[..]]);
+        assert_eq!(chunks[0].1, vec![0, 0, 0, 1, 0, 0, 0, 3, 8, 6, 0, 0, 0]);
+        assert_eq!(unstore(&chunks[1].1),
+            vec![0, 3, 2, 1, 4, 0, 7, 6, 5, 8, 0, 11, 10, 9, 12]);
+        assert!(chunks[2].1.is_empty());
+    }
+
+    #[test]
+    fn png_sixteen_bit_grey() {
+        let mut png = Vec::new();
+        write_png(&mut png, Format::R16Unorm, 2, 1, &[0x34, 0x12, 0xCD, 0xAB]).unwrap();
+        let chunks = chunks(&png);
+        assert_eq!(&chunks[0].1[8..10], &[16, 0]);
+        assert_eq!(unstore(&chunks[1].1), vec![0, 0x12, 0x34, 0xAB, 0xCD]);
+    }
+
+    #[test]
+    fn ppm() {
+        let mut ppm = Vec::new();
+        write_ppm(&mut ppm, Format::B8G8R8A8Srgb, 2, 1, &[1, 2, 3, 4, 5, 6, 7, 8]).unwrap();
+        assert_eq!(ppm, b"P6\n2 1\n255\n\x03\x02\x01\x07\x06\x05".to_vec());
+
+        let mut pgm = Vec::new();
+        write_ppm(&mut pgm, Format::R8Unorm, 1, 2, &[9, 10]).unwrap();
+        assert_eq!(pgm, b"P5\n1 2\n255\n\x09\x0a".to_vec());
+
+        let mut ppm = Vec::new();
+        write_ppm(&mut ppm, Format::R16G16B16A16Unorm, 1, 1, &[1, 2, 3, 4, 5, 6, 7, 8])
+            .unwrap();
+        assert_eq!(ppm, b"P6\n1 1\n65535\n\x02\x01\x04\x03\x06\x05".to_vec());
+    }
+
+    #[test]
+    fn rejects_invalid_input() {
+        let mut out = Vec::new();
+        match write_png(&mut out, Format::R32Sfloat, 1, 1, &[0; 4]) {
+            Err(err) => match *err.kind() {
+                ErrorKind::UnsupportedExportFormat { format } => {
+                    assert_eq!(format, Format::R32Sfloat)
+                },
+                _ => panic!("unexpected error: {}", err),
+            },
+            Ok(()) => panic!("unsupported format accepted"),
+        }
+        assert!(write_ppm(&mut out, Format::R8G8B8A8Unorm, 2, 2, &[0; 15]).is_err());
+        assert!(out.is_empty());
+    }
+}
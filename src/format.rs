@@ -0,0 +1,241 @@
+use ::{Format, ImageAspectFlags, DeviceSize};
+use format_table;
+
+
+/// The numeric type of a format component.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NumericType {
+    Unorm,
+    Snorm,
+    Uscaled,
+    Sscaled,
+    Uint,
+    Sint,
+    Ufloat,
+    Sfloat,
+    Srgb,
+}
+
+
+/// The kind of a format component.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Component {
+    R,
+    G,
+    B,
+    A,
+    Depth,
+    Stencil,
+}
+
+
+/// A component of a format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FormatComponent {
+    pub(crate) component: Component,
+    pub(crate) bits: u32,
+    pub(crate) numeric_type: NumericType,
+}
+
+impl FormatComponent {
+    /// Returns the kind of this component.
+    pub fn component(&self) -> Component {
+        self.component
+    }
+
+    /// Returns the width of this component in bits, or zero for compressed
+    /// formats where it is unspecified.
+    pub fn bits(&self) -> u32 {
+        self.bits
+    }
+
+    /// Returns the numeric type of this component.
+    pub fn numeric_type(&self) -> NumericType {
+        self.numeric_type
+    }
+}
+
+
+/// Metadata describing a format. Generated by `codegen` from `vk.xml`.
+#[derive(Debug)]
+pub(crate) struct FormatInfo {
+    pub(crate) block_size: u32,
+    pub(crate) block_extent: [u32; 3],
+    pub(crate) components: &'static [FormatComponent],
+    pub(crate) numeric_type: Option<NumericType>,
+    pub(crate) color: bool,
+    pub(crate) depth: bool,
+    pub(crate) stencil: bool,
+    pub(crate) compressed: bool,
+    pub(crate) packed: bool,
+    pub(crate) srgb: Option<Format>,
+    pub(crate) linear: Option<Format>,
+}
+
+
+impl Format {
+    fn info(&self) -> &'static FormatInfo {
+        format_table::format_info(*self)
+    }
+
+    /// Returns the size in bytes of a texel block (a single texel for
+    /// uncompressed formats). Returns zero for `Format::Undefined`.
+    pub fn block_size(&self) -> u32 {
+        self.info().block_size
+    }
+
+    /// Returns the width, height and depth in texels of a texel block.
+    pub fn block_extent(&self) -> [u32; 3] {
+        self.info().block_extent
+    }
+
+    /// Returns the components of this format in the order they appear in its
+    /// name. Padding bits and shared exponents are not included.
+    pub fn components(&self) -> &'static [FormatComponent] {
+        self.info().components
+    }
+
+    /// Returns the number of components of this format.
+    pub fn component_count(&self) -> u32 {
+        self.info().components.len() as u32
+    }
+
+    /// Returns the numeric type of this format's first component (the depth
+    /// component for combined depth/stencil formats).
+    pub fn numeric_type(&self) -> Option<NumericType> {
+        self.info().numeric_type
+    }
+
+    /// Returns the aspects of an image of this format which hold data.
+    pub fn aspects(&self) -> ImageAspectFlags {
+        let info = self.info();
+        let mut aspects = ImageAspectFlags::empty();
+        if info.color { aspects |= ImageAspectFlags::COLOR; }
+        if info.depth { aspects |= ImageAspectFlags::DEPTH; }
+        if info.stencil { aspects |= ImageAspectFlags::STENCIL; }
+        aspects
+    }
+
+    /// Returns true if this format has a depth component.
+    pub fn has_depth(&self) -> bool {
+        self.info().depth
+    }
+
+    /// Returns true if this format has a stencil component.
+    pub fn has_stencil(&self) -> bool {
+        self.info().stencil
+    }
+
+    /// Returns true if this is a block-compressed format.
+    pub fn is_compressed(&self) -> bool {
+        self.info().compressed
+    }
+
+    /// Returns true if this is a packed format (with a `Pack*` suffix).
+    pub fn is_packed(&self) -> bool {
+        self.info().packed
+    }
+
+    /// Returns true if this format uses the sRGB transfer function.
+    pub fn is_srgb(&self) -> bool {
+        self.info().numeric_type == Some(NumericType::Srgb)
+    }
+
+    /// Returns the sRGB counterpart of a `*Unorm` format, if one exists.
+    pub fn srgb(&self) -> Option<Format> {
+        self.info().srgb
+    }
+
+    /// Returns the `*Unorm` counterpart of an sRGB format, if one exists.
+    pub fn linear(&self) -> Option<Format> {
+        self.info().linear
+    }
+
+    /// Returns the size in bytes of tightly packed texel data covering an
+    /// extent of `width` x `height` x `depth` texels, rounding up to whole
+    /// texel blocks. Saturates at `DeviceSize::max_value()`.
+    pub fn data_size(&self, width: u32, height: u32, depth: u32) -> DeviceSize {
+        let info = self.info();
+        let blocks = |texels: u32, block: u32| {
+            (texels as DeviceSize + block as DeviceSize - 1) / block as DeviceSize
+        };
+        blocks(width, info.block_extent[0])
+            .saturating_mul(blocks(height, info.block_extent[1]))
+            .saturating_mul(blocks(depth, info.block_extent[2]))
+            .saturating_mul(info.block_size as DeviceSize)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uncompressed_metadata() {
+        let format = Format::B8G8R8A8Srgb;
+        assert_eq!(format.block_size(), 4);
+        assert_eq!(format.block_extent(), [1, 1, 1]);
+        let components: Vec<Component> = format.components().iter()
+            .map(|c| c.component()).collect();
+        assert_eq!(components, vec![Component::B, Component::G, Component::R, Component::A]);
+        assert!(format.components().iter().all(|c| c.bits() == 8));
+        assert_eq!(format.aspects(), ImageAspectFlags::COLOR);
+        assert!(format.is_srgb() && !format.is_compressed() && !format.is_packed());
+        assert_eq!(format.linear(), Some(Format::B8G8R8A8Unorm));
+        assert_eq!(Format::B8G8R8A8Unorm.srgb(), Some(format));
+        assert_eq!(Format::R32G32B32A32Sfloat.srgb(), None);
+
+        let packed = Format::A2B10G10R10UnormPack32;
+        assert!(packed.is_packed());
+        assert_eq!(packed.block_size(), 4);
+        assert_eq!(packed.component_count(), 4);
+        assert_eq!(packed.components()[0].bits(), 2);
+    }
+
+    #[test]
+    fn depth_stencil_metadata() {
+        let format = Format::D24UnormS8Uint;
+        assert_eq!(format.aspects(), ImageAspectFlags::DEPTH | ImageAspectFlags::STENCIL);
+        assert!(format.has_depth() && format.has_stencil());
+        assert_eq!(format.numeric_type(), Some(NumericType::Unorm));
+        assert_eq!(format.components()[1].numeric_type(), NumericType::Uint);
+        assert_eq!(Format::S8Uint.aspects(), ImageAspectFlags::STENCIL);
+        assert_eq!(Format::D32Sfloat.numeric_type(), Some(NumericType::Sfloat));
+    }
+
+    #[test]
+    fn compressed_metadata() {
+        let format = Format::Bc1RgbUnormBlock;
+        assert!(format.is_compressed());
+        assert_eq!(format.block_size(), 8);
+        assert_eq!(format.block_extent(), [4, 4, 1]);
+        assert_eq!(format.srgb(), Some(Format::Bc1RgbSrgbBlock));
+        assert_eq!(Format::Astc12x10UnormBlock.block_extent(), [12, 10, 1]);
+        assert_eq!(Format::Astc12x10UnormBlock.block_size(), 16);
+    }
+
+    #[test]
+    fn undefined_metadata() {
+        assert_eq!(Format::Undefined.block_size(), 0);
+        assert_eq!(Format::Undefined.aspects(), ImageAspectFlags::empty());
+        assert_eq!(Format::Undefined.numeric_type(), None);
+        assert_eq!(Format::Undefined.data_size(16, 16, 1), 0);
+    }
+
+    #[test]
+    fn data_sizes() {
+        assert_eq!(Format::R8G8B8A8Unorm.data_size(3, 5, 2), 3 * 5 * 2 * 4);
+        assert_eq!(Format::Bc1RgbUnormBlock.data_size(4, 4, 1), 8);
+        assert_eq!(Format::Bc1RgbUnormBlock.data_size(5, 1, 1), 2 * 8);
+        assert_eq!(Format::Bc7UnormBlock.data_size(1, 1, 1), 16);
+        assert_eq!(Format::Astc12x10UnormBlock.data_size(25, 21, 1), 3 * 3 * 16);
+        assert_eq!(Format::R8Unorm.data_size(0, 8, 1), 0);
+
+        let max = u32::max_value();
+        assert_eq!(Format::Bc1RgbUnormBlock.data_size(max, 1, 1),
+            (max as DeviceSize + 3) / 4 * 8);
+        assert_eq!(Format::R32G32B32A32Sfloat.data_size(max, max, max),
+            DeviceSize::max_value());
+    }
+}
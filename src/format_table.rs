@@ -0,0 +1,3695 @@
+//! Format metadata.
+//! 
+// 
+// NOTE: Do not edit this file directly; it is auto-generated.
+
+#![allow(non_upper_case_globals)]
+
+use ::Format;
+use format::{FormatInfo, FormatComponent, Component, NumericType};
+
+
+static UNDEFINED: FormatInfo = FormatInfo {
+    block_size: 0,
+    block_extent: [1, 1, 1],
+    components: &[],
+    numeric_type: None,
+    color: false,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R4G4_UNORM_PACK8: FormatInfo = FormatInfo {
+    block_size: 1,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 4, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 4, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: true,
+    srgb: None,
+    linear: None,
+};
+
+static R4G4B4A4_UNORM_PACK16: FormatInfo = FormatInfo {
+    block_size: 2,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 4, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 4, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::B, bits: 4, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::A, bits: 4, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: true,
+    srgb: None,
+    linear: None,
+};
+
+static B4G4R4A4_UNORM_PACK16: FormatInfo = FormatInfo {
+    block_size: 2,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::B, bits: 4, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 4, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::R, bits: 4, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::A, bits: 4, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: true,
+    srgb: None,
+    linear: None,
+};
+
+static R5G6B5_UNORM_PACK16: FormatInfo = FormatInfo {
+    block_size: 2,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 5, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 6, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::B, bits: 5, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: true,
+    srgb: None,
+    linear: None,
+};
+
+static B5G6R5_UNORM_PACK16: FormatInfo = FormatInfo {
+    block_size: 2,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::B, bits: 5, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 6, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::R, bits: 5, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: true,
+    srgb: None,
+    linear: None,
+};
+
+static R5G5B5A1_UNORM_PACK16: FormatInfo = FormatInfo {
+    block_size: 2,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 5, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 5, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::B, bits: 5, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::A, bits: 1, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: true,
+    srgb: None,
+    linear: None,
+};
+
+static B5G5R5A1_UNORM_PACK16: FormatInfo = FormatInfo {
+    block_size: 2,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::B, bits: 5, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 5, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::R, bits: 5, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::A, bits: 1, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: true,
+    srgb: None,
+    linear: None,
+};
+
+static A1R5G5B5_UNORM_PACK16: FormatInfo = FormatInfo {
+    block_size: 2,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::A, bits: 1, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::R, bits: 5, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 5, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::B, bits: 5, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: true,
+    srgb: None,
+    linear: None,
+};
+
+static R8_UNORM: FormatInfo = FormatInfo {
+    block_size: 1,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: Some(Format::R8Srgb),
+    linear: None,
+};
+
+static R8_SNORM: FormatInfo = FormatInfo {
+    block_size: 1,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Snorm },
+    ],
+    numeric_type: Some(NumericType::Snorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R8_USCALED: FormatInfo = FormatInfo {
+    block_size: 1,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Uscaled },
+    ],
+    numeric_type: Some(NumericType::Uscaled),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R8_SSCALED: FormatInfo = FormatInfo {
+    block_size: 1,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Sscaled },
+    ],
+    numeric_type: Some(NumericType::Sscaled),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R8_UINT: FormatInfo = FormatInfo {
+    block_size: 1,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Uint },
+    ],
+    numeric_type: Some(NumericType::Uint),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R8_SINT: FormatInfo = FormatInfo {
+    block_size: 1,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Sint },
+    ],
+    numeric_type: Some(NumericType::Sint),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R8_SRGB: FormatInfo = FormatInfo {
+    block_size: 1,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Srgb },
+    ],
+    numeric_type: Some(NumericType::Srgb),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: Some(Format::R8Unorm),
+};
+
+static R8G8_UNORM: FormatInfo = FormatInfo {
+    block_size: 2,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: Some(Format::R8G8Srgb),
+    linear: None,
+};
+
+static R8G8_SNORM: FormatInfo = FormatInfo {
+    block_size: 2,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Snorm },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Snorm },
+    ],
+    numeric_type: Some(NumericType::Snorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R8G8_USCALED: FormatInfo = FormatInfo {
+    block_size: 2,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Uscaled },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Uscaled },
+    ],
+    numeric_type: Some(NumericType::Uscaled),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R8G8_SSCALED: FormatInfo = FormatInfo {
+    block_size: 2,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Sscaled },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Sscaled },
+    ],
+    numeric_type: Some(NumericType::Sscaled),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R8G8_UINT: FormatInfo = FormatInfo {
+    block_size: 2,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Uint },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Uint },
+    ],
+    numeric_type: Some(NumericType::Uint),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R8G8_SINT: FormatInfo = FormatInfo {
+    block_size: 2,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Sint },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Sint },
+    ],
+    numeric_type: Some(NumericType::Sint),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R8G8_SRGB: FormatInfo = FormatInfo {
+    block_size: 2,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Srgb },
+    ],
+    numeric_type: Some(NumericType::Srgb),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: Some(Format::R8G8Unorm),
+};
+
+static R8G8B8_UNORM: FormatInfo = FormatInfo {
+    block_size: 3,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::B, bits: 8, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: Some(Format::R8G8B8Srgb),
+    linear: None,
+};
+
+static R8G8B8_SNORM: FormatInfo = FormatInfo {
+    block_size: 3,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Snorm },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Snorm },
+        FormatComponent { component: Component::B, bits: 8, numeric_type: NumericType::Snorm },
+    ],
+    numeric_type: Some(NumericType::Snorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R8G8B8_USCALED: FormatInfo = FormatInfo {
+    block_size: 3,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Uscaled },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Uscaled },
+        FormatComponent { component: Component::B, bits: 8, numeric_type: NumericType::Uscaled },
+    ],
+    numeric_type: Some(NumericType::Uscaled),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R8G8B8_SSCALED: FormatInfo = FormatInfo {
+    block_size: 3,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Sscaled },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Sscaled },
+        FormatComponent { component: Component::B, bits: 8, numeric_type: NumericType::Sscaled },
+    ],
+    numeric_type: Some(NumericType::Sscaled),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R8G8B8_UINT: FormatInfo = FormatInfo {
+    block_size: 3,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Uint },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Uint },
+        FormatComponent { component: Component::B, bits: 8, numeric_type: NumericType::Uint },
+    ],
+    numeric_type: Some(NumericType::Uint),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R8G8B8_SINT: FormatInfo = FormatInfo {
+    block_size: 3,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Sint },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Sint },
+        FormatComponent { component: Component::B, bits: 8, numeric_type: NumericType::Sint },
+    ],
+    numeric_type: Some(NumericType::Sint),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R8G8B8_SRGB: FormatInfo = FormatInfo {
+    block_size: 3,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::B, bits: 8, numeric_type: NumericType::Srgb },
+    ],
+    numeric_type: Some(NumericType::Srgb),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: Some(Format::R8G8B8Unorm),
+};
+
+static B8G8R8_UNORM: FormatInfo = FormatInfo {
+    block_size: 3,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::B, bits: 8, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: Some(Format::B8G8R8Srgb),
+    linear: None,
+};
+
+static B8G8R8_SNORM: FormatInfo = FormatInfo {
+    block_size: 3,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::B, bits: 8, numeric_type: NumericType::Snorm },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Snorm },
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Snorm },
+    ],
+    numeric_type: Some(NumericType::Snorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static B8G8R8_USCALED: FormatInfo = FormatInfo {
+    block_size: 3,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::B, bits: 8, numeric_type: NumericType::Uscaled },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Uscaled },
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Uscaled },
+    ],
+    numeric_type: Some(NumericType::Uscaled),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static B8G8R8_SSCALED: FormatInfo = FormatInfo {
+    block_size: 3,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::B, bits: 8, numeric_type: NumericType::Sscaled },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Sscaled },
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Sscaled },
+    ],
+    numeric_type: Some(NumericType::Sscaled),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static B8G8R8_UINT: FormatInfo = FormatInfo {
+    block_size: 3,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::B, bits: 8, numeric_type: NumericType::Uint },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Uint },
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Uint },
+    ],
+    numeric_type: Some(NumericType::Uint),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static B8G8R8_SINT: FormatInfo = FormatInfo {
+    block_size: 3,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::B, bits: 8, numeric_type: NumericType::Sint },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Sint },
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Sint },
+    ],
+    numeric_type: Some(NumericType::Sint),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static B8G8R8_SRGB: FormatInfo = FormatInfo {
+    block_size: 3,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::B, bits: 8, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Srgb },
+    ],
+    numeric_type: Some(NumericType::Srgb),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: Some(Format::B8G8R8Unorm),
+};
+
+static R8G8B8A8_UNORM: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::B, bits: 8, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::A, bits: 8, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: Some(Format::R8G8B8A8Srgb),
+    linear: None,
+};
+
+static R8G8B8A8_SNORM: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Snorm },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Snorm },
+        FormatComponent { component: Component::B, bits: 8, numeric_type: NumericType::Snorm },
+        FormatComponent { component: Component::A, bits: 8, numeric_type: NumericType::Snorm },
+    ],
+    numeric_type: Some(NumericType::Snorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R8G8B8A8_USCALED: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Uscaled },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Uscaled },
+        FormatComponent { component: Component::B, bits: 8, numeric_type: NumericType::Uscaled },
+        FormatComponent { component: Component::A, bits: 8, numeric_type: NumericType::Uscaled },
+    ],
+    numeric_type: Some(NumericType::Uscaled),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R8G8B8A8_SSCALED: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Sscaled },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Sscaled },
+        FormatComponent { component: Component::B, bits: 8, numeric_type: NumericType::Sscaled },
+        FormatComponent { component: Component::A, bits: 8, numeric_type: NumericType::Sscaled },
+    ],
+    numeric_type: Some(NumericType::Sscaled),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R8G8B8A8_UINT: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Uint },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Uint },
+        FormatComponent { component: Component::B, bits: 8, numeric_type: NumericType::Uint },
+        FormatComponent { component: Component::A, bits: 8, numeric_type: NumericType::Uint },
+    ],
+    numeric_type: Some(NumericType::Uint),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R8G8B8A8_SINT: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Sint },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Sint },
+        FormatComponent { component: Component::B, bits: 8, numeric_type: NumericType::Sint },
+        FormatComponent { component: Component::A, bits: 8, numeric_type: NumericType::Sint },
+    ],
+    numeric_type: Some(NumericType::Sint),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R8G8B8A8_SRGB: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::B, bits: 8, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::A, bits: 8, numeric_type: NumericType::Srgb },
+    ],
+    numeric_type: Some(NumericType::Srgb),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: Some(Format::R8G8B8A8Unorm),
+};
+
+static B8G8R8A8_UNORM: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::B, bits: 8, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::A, bits: 8, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: Some(Format::B8G8R8A8Srgb),
+    linear: None,
+};
+
+static B8G8R8A8_SNORM: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::B, bits: 8, numeric_type: NumericType::Snorm },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Snorm },
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Snorm },
+        FormatComponent { component: Component::A, bits: 8, numeric_type: NumericType::Snorm },
+    ],
+    numeric_type: Some(NumericType::Snorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static B8G8R8A8_USCALED: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::B, bits: 8, numeric_type: NumericType::Uscaled },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Uscaled },
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Uscaled },
+        FormatComponent { component: Component::A, bits: 8, numeric_type: NumericType::Uscaled },
+    ],
+    numeric_type: Some(NumericType::Uscaled),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static B8G8R8A8_SSCALED: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::B, bits: 8, numeric_type: NumericType::Sscaled },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Sscaled },
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Sscaled },
+        FormatComponent { component: Component::A, bits: 8, numeric_type: NumericType::Sscaled },
+    ],
+    numeric_type: Some(NumericType::Sscaled),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static B8G8R8A8_UINT: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::B, bits: 8, numeric_type: NumericType::Uint },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Uint },
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Uint },
+        FormatComponent { component: Component::A, bits: 8, numeric_type: NumericType::Uint },
+    ],
+    numeric_type: Some(NumericType::Uint),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static B8G8R8A8_SINT: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::B, bits: 8, numeric_type: NumericType::Sint },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Sint },
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Sint },
+        FormatComponent { component: Component::A, bits: 8, numeric_type: NumericType::Sint },
+    ],
+    numeric_type: Some(NumericType::Sint),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static B8G8R8A8_SRGB: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::B, bits: 8, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::A, bits: 8, numeric_type: NumericType::Srgb },
+    ],
+    numeric_type: Some(NumericType::Srgb),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: Some(Format::B8G8R8A8Unorm),
+};
+
+static A8B8G8R8_UNORM_PACK32: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::A, bits: 8, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::B, bits: 8, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: true,
+    srgb: Some(Format::A8B8G8R8SrgbPack32),
+    linear: None,
+};
+
+static A8B8G8R8_SNORM_PACK32: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::A, bits: 8, numeric_type: NumericType::Snorm },
+        FormatComponent { component: Component::B, bits: 8, numeric_type: NumericType::Snorm },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Snorm },
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Snorm },
+    ],
+    numeric_type: Some(NumericType::Snorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: true,
+    srgb: None,
+    linear: None,
+};
+
+static A8B8G8R8_USCALED_PACK32: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::A, bits: 8, numeric_type: NumericType::Uscaled },
+        FormatComponent { component: Component::B, bits: 8, numeric_type: NumericType::Uscaled },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Uscaled },
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Uscaled },
+    ],
+    numeric_type: Some(NumericType::Uscaled),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: true,
+    srgb: None,
+    linear: None,
+};
+
+static A8B8G8R8_SSCALED_PACK32: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::A, bits: 8, numeric_type: NumericType::Sscaled },
+        FormatComponent { component: Component::B, bits: 8, numeric_type: NumericType::Sscaled },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Sscaled },
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Sscaled },
+    ],
+    numeric_type: Some(NumericType::Sscaled),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: true,
+    srgb: None,
+    linear: None,
+};
+
+static A8B8G8R8_UINT_PACK32: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::A, bits: 8, numeric_type: NumericType::Uint },
+        FormatComponent { component: Component::B, bits: 8, numeric_type: NumericType::Uint },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Uint },
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Uint },
+    ],
+    numeric_type: Some(NumericType::Uint),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: true,
+    srgb: None,
+    linear: None,
+};
+
+static A8B8G8R8_SINT_PACK32: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::A, bits: 8, numeric_type: NumericType::Sint },
+        FormatComponent { component: Component::B, bits: 8, numeric_type: NumericType::Sint },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Sint },
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Sint },
+    ],
+    numeric_type: Some(NumericType::Sint),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: true,
+    srgb: None,
+    linear: None,
+};
+
+static A8B8G8R8_SRGB_PACK32: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::A, bits: 8, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::B, bits: 8, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Srgb },
+    ],
+    numeric_type: Some(NumericType::Srgb),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: true,
+    srgb: None,
+    linear: Some(Format::A8B8G8R8UnormPack32),
+};
+
+static A2R10G10B10_UNORM_PACK32: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::A, bits: 2, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::R, bits: 10, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 10, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::B, bits: 10, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: true,
+    srgb: None,
+    linear: None,
+};
+
+static A2R10G10B10_SNORM_PACK32: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::A, bits: 2, numeric_type: NumericType::Snorm },
+        FormatComponent { component: Component::R, bits: 10, numeric_type: NumericType::Snorm },
+        FormatComponent { component: Component::G, bits: 10, numeric_type: NumericType::Snorm },
+        FormatComponent { component: Component::B, bits: 10, numeric_type: NumericType::Snorm },
+    ],
+    numeric_type: Some(NumericType::Snorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: true,
+    srgb: None,
+    linear: None,
+};
+
+static A2R10G10B10_USCALED_PACK32: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::A, bits: 2, numeric_type: NumericType::Uscaled },
+        FormatComponent { component: Component::R, bits: 10, numeric_type: NumericType::Uscaled },
+        FormatComponent { component: Component::G, bits: 10, numeric_type: NumericType::Uscaled },
+        FormatComponent { component: Component::B, bits: 10, numeric_type: NumericType::Uscaled },
+    ],
+    numeric_type: Some(NumericType::Uscaled),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: true,
+    srgb: None,
+    linear: None,
+};
+
+static A2R10G10B10_SSCALED_PACK32: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::A, bits: 2, numeric_type: NumericType::Sscaled },
+        FormatComponent { component: Component::R, bits: 10, numeric_type: NumericType::Sscaled },
+        FormatComponent { component: Component::G, bits: 10, numeric_type: NumericType::Sscaled },
+        FormatComponent { component: Component::B, bits: 10, numeric_type: NumericType::Sscaled },
+    ],
+    numeric_type: Some(NumericType::Sscaled),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: true,
+    srgb: None,
+    linear: None,
+};
+
+static A2R10G10B10_UINT_PACK32: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::A, bits: 2, numeric_type: NumericType::Uint },
+        FormatComponent { component: Component::R, bits: 10, numeric_type: NumericType::Uint },
+        FormatComponent { component: Component::G, bits: 10, numeric_type: NumericType::Uint },
+        FormatComponent { component: Component::B, bits: 10, numeric_type: NumericType::Uint },
+    ],
+    numeric_type: Some(NumericType::Uint),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: true,
+    srgb: None,
+    linear: None,
+};
+
+static A2R10G10B10_SINT_PACK32: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::A, bits: 2, numeric_type: NumericType::Sint },
+        FormatComponent { component: Component::R, bits: 10, numeric_type: NumericType::Sint },
+        FormatComponent { component: Component::G, bits: 10, numeric_type: NumericType::Sint },
+        FormatComponent { component: Component::B, bits: 10, numeric_type: NumericType::Sint },
+    ],
+    numeric_type: Some(NumericType::Sint),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: true,
+    srgb: None,
+    linear: None,
+};
+
+static A2B10G10R10_UNORM_PACK32: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::A, bits: 2, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::B, bits: 10, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 10, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::R, bits: 10, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: true,
+    srgb: None,
+    linear: None,
+};
+
+static A2B10G10R10_SNORM_PACK32: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::A, bits: 2, numeric_type: NumericType::Snorm },
+        FormatComponent { component: Component::B, bits: 10, numeric_type: NumericType::Snorm },
+        FormatComponent { component: Component::G, bits: 10, numeric_type: NumericType::Snorm },
+        FormatComponent { component: Component::R, bits: 10, numeric_type: NumericType::Snorm },
+    ],
+    numeric_type: Some(NumericType::Snorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: true,
+    srgb: None,
+    linear: None,
+};
+
+static A2B10G10R10_USCALED_PACK32: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::A, bits: 2, numeric_type: NumericType::Uscaled },
+        FormatComponent { component: Component::B, bits: 10, numeric_type: NumericType::Uscaled },
+        FormatComponent { component: Component::G, bits: 10, numeric_type: NumericType::Uscaled },
+        FormatComponent { component: Component::R, bits: 10, numeric_type: NumericType::Uscaled },
+    ],
+    numeric_type: Some(NumericType::Uscaled),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: true,
+    srgb: None,
+    linear: None,
+};
+
+static A2B10G10R10_SSCALED_PACK32: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::A, bits: 2, numeric_type: NumericType::Sscaled },
+        FormatComponent { component: Component::B, bits: 10, numeric_type: NumericType::Sscaled },
+        FormatComponent { component: Component::G, bits: 10, numeric_type: NumericType::Sscaled },
+        FormatComponent { component: Component::R, bits: 10, numeric_type: NumericType::Sscaled },
+    ],
+    numeric_type: Some(NumericType::Sscaled),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: true,
+    srgb: None,
+    linear: None,
+};
+
+static A2B10G10R10_UINT_PACK32: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::A, bits: 2, numeric_type: NumericType::Uint },
+        FormatComponent { component: Component::B, bits: 10, numeric_type: NumericType::Uint },
+        FormatComponent { component: Component::G, bits: 10, numeric_type: NumericType::Uint },
+        FormatComponent { component: Component::R, bits: 10, numeric_type: NumericType::Uint },
+    ],
+    numeric_type: Some(NumericType::Uint),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: true,
+    srgb: None,
+    linear: None,
+};
+
+static A2B10G10R10_SINT_PACK32: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::A, bits: 2, numeric_type: NumericType::Sint },
+        FormatComponent { component: Component::B, bits: 10, numeric_type: NumericType::Sint },
+        FormatComponent { component: Component::G, bits: 10, numeric_type: NumericType::Sint },
+        FormatComponent { component: Component::R, bits: 10, numeric_type: NumericType::Sint },
+    ],
+    numeric_type: Some(NumericType::Sint),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: true,
+    srgb: None,
+    linear: None,
+};
+
+static R16_UNORM: FormatInfo = FormatInfo {
+    block_size: 2,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 16, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R16_SNORM: FormatInfo = FormatInfo {
+    block_size: 2,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 16, numeric_type: NumericType::Snorm },
+    ],
+    numeric_type: Some(NumericType::Snorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R16_USCALED: FormatInfo = FormatInfo {
+    block_size: 2,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 16, numeric_type: NumericType::Uscaled },
+    ],
+    numeric_type: Some(NumericType::Uscaled),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R16_SSCALED: FormatInfo = FormatInfo {
+    block_size: 2,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 16, numeric_type: NumericType::Sscaled },
+    ],
+    numeric_type: Some(NumericType::Sscaled),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R16_UINT: FormatInfo = FormatInfo {
+    block_size: 2,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 16, numeric_type: NumericType::Uint },
+    ],
+    numeric_type: Some(NumericType::Uint),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R16_SINT: FormatInfo = FormatInfo {
+    block_size: 2,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 16, numeric_type: NumericType::Sint },
+    ],
+    numeric_type: Some(NumericType::Sint),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R16_SFLOAT: FormatInfo = FormatInfo {
+    block_size: 2,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 16, numeric_type: NumericType::Sfloat },
+    ],
+    numeric_type: Some(NumericType::Sfloat),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R16G16_UNORM: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 16, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 16, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R16G16_SNORM: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 16, numeric_type: NumericType::Snorm },
+        FormatComponent { component: Component::G, bits: 16, numeric_type: NumericType::Snorm },
+    ],
+    numeric_type: Some(NumericType::Snorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R16G16_USCALED: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 16, numeric_type: NumericType::Uscaled },
+        FormatComponent { component: Component::G, bits: 16, numeric_type: NumericType::Uscaled },
+    ],
+    numeric_type: Some(NumericType::Uscaled),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R16G16_SSCALED: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 16, numeric_type: NumericType::Sscaled },
+        FormatComponent { component: Component::G, bits: 16, numeric_type: NumericType::Sscaled },
+    ],
+    numeric_type: Some(NumericType::Sscaled),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R16G16_UINT: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 16, numeric_type: NumericType::Uint },
+        FormatComponent { component: Component::G, bits: 16, numeric_type: NumericType::Uint },
+    ],
+    numeric_type: Some(NumericType::Uint),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R16G16_SINT: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 16, numeric_type: NumericType::Sint },
+        FormatComponent { component: Component::G, bits: 16, numeric_type: NumericType::Sint },
+    ],
+    numeric_type: Some(NumericType::Sint),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R16G16_SFLOAT: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 16, numeric_type: NumericType::Sfloat },
+        FormatComponent { component: Component::G, bits: 16, numeric_type: NumericType::Sfloat },
+    ],
+    numeric_type: Some(NumericType::Sfloat),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R16G16B16_UNORM: FormatInfo = FormatInfo {
+    block_size: 6,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 16, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 16, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::B, bits: 16, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R16G16B16_SNORM: FormatInfo = FormatInfo {
+    block_size: 6,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 16, numeric_type: NumericType::Snorm },
+        FormatComponent { component: Component::G, bits: 16, numeric_type: NumericType::Snorm },
+        FormatComponent { component: Component::B, bits: 16, numeric_type: NumericType::Snorm },
+    ],
+    numeric_type: Some(NumericType::Snorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R16G16B16_USCALED: FormatInfo = FormatInfo {
+    block_size: 6,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 16, numeric_type: NumericType::Uscaled },
+        FormatComponent { component: Component::G, bits: 16, numeric_type: NumericType::Uscaled },
+        FormatComponent { component: Component::B, bits: 16, numeric_type: NumericType::Uscaled },
+    ],
+    numeric_type: Some(NumericType::Uscaled),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R16G16B16_SSCALED: FormatInfo = FormatInfo {
+    block_size: 6,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 16, numeric_type: NumericType::Sscaled },
+        FormatComponent { component: Component::G, bits: 16, numeric_type: NumericType::Sscaled },
+        FormatComponent { component: Component::B, bits: 16, numeric_type: NumericType::Sscaled },
+    ],
+    numeric_type: Some(NumericType::Sscaled),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R16G16B16_UINT: FormatInfo = FormatInfo {
+    block_size: 6,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 16, numeric_type: NumericType::Uint },
+        FormatComponent { component: Component::G, bits: 16, numeric_type: NumericType::Uint },
+        FormatComponent { component: Component::B, bits: 16, numeric_type: NumericType::Uint },
+    ],
+    numeric_type: Some(NumericType::Uint),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R16G16B16_SINT: FormatInfo = FormatInfo {
+    block_size: 6,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 16, numeric_type: NumericType::Sint },
+        FormatComponent { component: Component::G, bits: 16, numeric_type: NumericType::Sint },
+        FormatComponent { component: Component::B, bits: 16, numeric_type: NumericType::Sint },
+    ],
+    numeric_type: Some(NumericType::Sint),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R16G16B16_SFLOAT: FormatInfo = FormatInfo {
+    block_size: 6,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 16, numeric_type: NumericType::Sfloat },
+        FormatComponent { component: Component::G, bits: 16, numeric_type: NumericType::Sfloat },
+        FormatComponent { component: Component::B, bits: 16, numeric_type: NumericType::Sfloat },
+    ],
+    numeric_type: Some(NumericType::Sfloat),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R16G16B16A16_UNORM: FormatInfo = FormatInfo {
+    block_size: 8,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 16, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 16, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::B, bits: 16, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::A, bits: 16, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R16G16B16A16_SNORM: FormatInfo = FormatInfo {
+    block_size: 8,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 16, numeric_type: NumericType::Snorm },
+        FormatComponent { component: Component::G, bits: 16, numeric_type: NumericType::Snorm },
+        FormatComponent { component: Component::B, bits: 16, numeric_type: NumericType::Snorm },
+        FormatComponent { component: Component::A, bits: 16, numeric_type: NumericType::Snorm },
+    ],
+    numeric_type: Some(NumericType::Snorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R16G16B16A16_USCALED: FormatInfo = FormatInfo {
+    block_size: 8,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 16, numeric_type: NumericType::Uscaled },
+        FormatComponent { component: Component::G, bits: 16, numeric_type: NumericType::Uscaled },
+        FormatComponent { component: Component::B, bits: 16, numeric_type: NumericType::Uscaled },
+        FormatComponent { component: Component::A, bits: 16, numeric_type: NumericType::Uscaled },
+    ],
+    numeric_type: Some(NumericType::Uscaled),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R16G16B16A16_SSCALED: FormatInfo = FormatInfo {
+    block_size: 8,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 16, numeric_type: NumericType::Sscaled },
+        FormatComponent { component: Component::G, bits: 16, numeric_type: NumericType::Sscaled },
+        FormatComponent { component: Component::B, bits: 16, numeric_type: NumericType::Sscaled },
+        FormatComponent { component: Component::A, bits: 16, numeric_type: NumericType::Sscaled },
+    ],
+    numeric_type: Some(NumericType::Sscaled),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R16G16B16A16_UINT: FormatInfo = FormatInfo {
+    block_size: 8,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 16, numeric_type: NumericType::Uint },
+        FormatComponent { component: Component::G, bits: 16, numeric_type: NumericType::Uint },
+        FormatComponent { component: Component::B, bits: 16, numeric_type: NumericType::Uint },
+        FormatComponent { component: Component::A, bits: 16, numeric_type: NumericType::Uint },
+    ],
+    numeric_type: Some(NumericType::Uint),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R16G16B16A16_SINT: FormatInfo = FormatInfo {
+    block_size: 8,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 16, numeric_type: NumericType::Sint },
+        FormatComponent { component: Component::G, bits: 16, numeric_type: NumericType::Sint },
+        FormatComponent { component: Component::B, bits: 16, numeric_type: NumericType::Sint },
+        FormatComponent { component: Component::A, bits: 16, numeric_type: NumericType::Sint },
+    ],
+    numeric_type: Some(NumericType::Sint),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R16G16B16A16_SFLOAT: FormatInfo = FormatInfo {
+    block_size: 8,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 16, numeric_type: NumericType::Sfloat },
+        FormatComponent { component: Component::G, bits: 16, numeric_type: NumericType::Sfloat },
+        FormatComponent { component: Component::B, bits: 16, numeric_type: NumericType::Sfloat },
+        FormatComponent { component: Component::A, bits: 16, numeric_type: NumericType::Sfloat },
+    ],
+    numeric_type: Some(NumericType::Sfloat),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R32_UINT: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 32, numeric_type: NumericType::Uint },
+    ],
+    numeric_type: Some(NumericType::Uint),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R32_SINT: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 32, numeric_type: NumericType::Sint },
+    ],
+    numeric_type: Some(NumericType::Sint),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R32_SFLOAT: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 32, numeric_type: NumericType::Sfloat },
+    ],
+    numeric_type: Some(NumericType::Sfloat),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R32G32_UINT: FormatInfo = FormatInfo {
+    block_size: 8,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 32, numeric_type: NumericType::Uint },
+        FormatComponent { component: Component::G, bits: 32, numeric_type: NumericType::Uint },
+    ],
+    numeric_type: Some(NumericType::Uint),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R32G32_SINT: FormatInfo = FormatInfo {
+    block_size: 8,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 32, numeric_type: NumericType::Sint },
+        FormatComponent { component: Component::G, bits: 32, numeric_type: NumericType::Sint },
+    ],
+    numeric_type: Some(NumericType::Sint),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R32G32_SFLOAT: FormatInfo = FormatInfo {
+    block_size: 8,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 32, numeric_type: NumericType::Sfloat },
+        FormatComponent { component: Component::G, bits: 32, numeric_type: NumericType::Sfloat },
+    ],
+    numeric_type: Some(NumericType::Sfloat),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R32G32B32_UINT: FormatInfo = FormatInfo {
+    block_size: 12,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 32, numeric_type: NumericType::Uint },
+        FormatComponent { component: Component::G, bits: 32, numeric_type: NumericType::Uint },
+        FormatComponent { component: Component::B, bits: 32, numeric_type: NumericType::Uint },
+    ],
+    numeric_type: Some(NumericType::Uint),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R32G32B32_SINT: FormatInfo = FormatInfo {
+    block_size: 12,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 32, numeric_type: NumericType::Sint },
+        FormatComponent { component: Component::G, bits: 32, numeric_type: NumericType::Sint },
+        FormatComponent { component: Component::B, bits: 32, numeric_type: NumericType::Sint },
+    ],
+    numeric_type: Some(NumericType::Sint),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R32G32B32_SFLOAT: FormatInfo = FormatInfo {
+    block_size: 12,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 32, numeric_type: NumericType::Sfloat },
+        FormatComponent { component: Component::G, bits: 32, numeric_type: NumericType::Sfloat },
+        FormatComponent { component: Component::B, bits: 32, numeric_type: NumericType::Sfloat },
+    ],
+    numeric_type: Some(NumericType::Sfloat),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R32G32B32A32_UINT: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 32, numeric_type: NumericType::Uint },
+        FormatComponent { component: Component::G, bits: 32, numeric_type: NumericType::Uint },
+        FormatComponent { component: Component::B, bits: 32, numeric_type: NumericType::Uint },
+        FormatComponent { component: Component::A, bits: 32, numeric_type: NumericType::Uint },
+    ],
+    numeric_type: Some(NumericType::Uint),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R32G32B32A32_SINT: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 32, numeric_type: NumericType::Sint },
+        FormatComponent { component: Component::G, bits: 32, numeric_type: NumericType::Sint },
+        FormatComponent { component: Component::B, bits: 32, numeric_type: NumericType::Sint },
+        FormatComponent { component: Component::A, bits: 32, numeric_type: NumericType::Sint },
+    ],
+    numeric_type: Some(NumericType::Sint),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R32G32B32A32_SFLOAT: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 32, numeric_type: NumericType::Sfloat },
+        FormatComponent { component: Component::G, bits: 32, numeric_type: NumericType::Sfloat },
+        FormatComponent { component: Component::B, bits: 32, numeric_type: NumericType::Sfloat },
+        FormatComponent { component: Component::A, bits: 32, numeric_type: NumericType::Sfloat },
+    ],
+    numeric_type: Some(NumericType::Sfloat),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R64_UINT: FormatInfo = FormatInfo {
+    block_size: 8,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 64, numeric_type: NumericType::Uint },
+    ],
+    numeric_type: Some(NumericType::Uint),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R64_SINT: FormatInfo = FormatInfo {
+    block_size: 8,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 64, numeric_type: NumericType::Sint },
+    ],
+    numeric_type: Some(NumericType::Sint),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R64_SFLOAT: FormatInfo = FormatInfo {
+    block_size: 8,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 64, numeric_type: NumericType::Sfloat },
+    ],
+    numeric_type: Some(NumericType::Sfloat),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R64G64_UINT: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 64, numeric_type: NumericType::Uint },
+        FormatComponent { component: Component::G, bits: 64, numeric_type: NumericType::Uint },
+    ],
+    numeric_type: Some(NumericType::Uint),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R64G64_SINT: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 64, numeric_type: NumericType::Sint },
+        FormatComponent { component: Component::G, bits: 64, numeric_type: NumericType::Sint },
+    ],
+    numeric_type: Some(NumericType::Sint),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R64G64_SFLOAT: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 64, numeric_type: NumericType::Sfloat },
+        FormatComponent { component: Component::G, bits: 64, numeric_type: NumericType::Sfloat },
+    ],
+    numeric_type: Some(NumericType::Sfloat),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R64G64B64_UINT: FormatInfo = FormatInfo {
+    block_size: 24,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 64, numeric_type: NumericType::Uint },
+        FormatComponent { component: Component::G, bits: 64, numeric_type: NumericType::Uint },
+        FormatComponent { component: Component::B, bits: 64, numeric_type: NumericType::Uint },
+    ],
+    numeric_type: Some(NumericType::Uint),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R64G64B64_SINT: FormatInfo = FormatInfo {
+    block_size: 24,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 64, numeric_type: NumericType::Sint },
+        FormatComponent { component: Component::G, bits: 64, numeric_type: NumericType::Sint },
+        FormatComponent { component: Component::B, bits: 64, numeric_type: NumericType::Sint },
+    ],
+    numeric_type: Some(NumericType::Sint),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R64G64B64_SFLOAT: FormatInfo = FormatInfo {
+    block_size: 24,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 64, numeric_type: NumericType::Sfloat },
+        FormatComponent { component: Component::G, bits: 64, numeric_type: NumericType::Sfloat },
+        FormatComponent { component: Component::B, bits: 64, numeric_type: NumericType::Sfloat },
+    ],
+    numeric_type: Some(NumericType::Sfloat),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R64G64B64A64_UINT: FormatInfo = FormatInfo {
+    block_size: 32,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 64, numeric_type: NumericType::Uint },
+        FormatComponent { component: Component::G, bits: 64, numeric_type: NumericType::Uint },
+        FormatComponent { component: Component::B, bits: 64, numeric_type: NumericType::Uint },
+        FormatComponent { component: Component::A, bits: 64, numeric_type: NumericType::Uint },
+    ],
+    numeric_type: Some(NumericType::Uint),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R64G64B64A64_SINT: FormatInfo = FormatInfo {
+    block_size: 32,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 64, numeric_type: NumericType::Sint },
+        FormatComponent { component: Component::G, bits: 64, numeric_type: NumericType::Sint },
+        FormatComponent { component: Component::B, bits: 64, numeric_type: NumericType::Sint },
+        FormatComponent { component: Component::A, bits: 64, numeric_type: NumericType::Sint },
+    ],
+    numeric_type: Some(NumericType::Sint),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static R64G64B64A64_SFLOAT: FormatInfo = FormatInfo {
+    block_size: 32,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 64, numeric_type: NumericType::Sfloat },
+        FormatComponent { component: Component::G, bits: 64, numeric_type: NumericType::Sfloat },
+        FormatComponent { component: Component::B, bits: 64, numeric_type: NumericType::Sfloat },
+        FormatComponent { component: Component::A, bits: 64, numeric_type: NumericType::Sfloat },
+    ],
+    numeric_type: Some(NumericType::Sfloat),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static B10G11R11_UFLOAT_PACK32: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::B, bits: 10, numeric_type: NumericType::Ufloat },
+        FormatComponent { component: Component::G, bits: 11, numeric_type: NumericType::Ufloat },
+        FormatComponent { component: Component::R, bits: 11, numeric_type: NumericType::Ufloat },
+    ],
+    numeric_type: Some(NumericType::Ufloat),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: true,
+    srgb: None,
+    linear: None,
+};
+
+static E5B9G9R9_UFLOAT_PACK32: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::B, bits: 9, numeric_type: NumericType::Ufloat },
+        FormatComponent { component: Component::G, bits: 9, numeric_type: NumericType::Ufloat },
+        FormatComponent { component: Component::R, bits: 9, numeric_type: NumericType::Ufloat },
+    ],
+    numeric_type: Some(NumericType::Ufloat),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: false,
+    packed: true,
+    srgb: None,
+    linear: None,
+};
+
+static D16_UNORM: FormatInfo = FormatInfo {
+    block_size: 2,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::Depth, bits: 16, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: false,
+    depth: true,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static X8_D24_UNORM_PACK32: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::Depth, bits: 24, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: false,
+    depth: true,
+    stencil: false,
+    compressed: false,
+    packed: true,
+    srgb: None,
+    linear: None,
+};
+
+static D32_SFLOAT: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::Depth, bits: 32, numeric_type: NumericType::Sfloat },
+    ],
+    numeric_type: Some(NumericType::Sfloat),
+    color: false,
+    depth: true,
+    stencil: false,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static S8_UINT: FormatInfo = FormatInfo {
+    block_size: 1,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::Stencil, bits: 8, numeric_type: NumericType::Uint },
+    ],
+    numeric_type: Some(NumericType::Uint),
+    color: false,
+    depth: false,
+    stencil: true,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static D16_UNORM_S8_UINT: FormatInfo = FormatInfo {
+    block_size: 3,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::Depth, bits: 16, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::Stencil, bits: 8, numeric_type: NumericType::Uint },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: false,
+    depth: true,
+    stencil: true,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static D24_UNORM_S8_UINT: FormatInfo = FormatInfo {
+    block_size: 4,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::Depth, bits: 24, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::Stencil, bits: 8, numeric_type: NumericType::Uint },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: false,
+    depth: true,
+    stencil: true,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static D32_SFLOAT_S8_UINT: FormatInfo = FormatInfo {
+    block_size: 5,
+    block_extent: [1, 1, 1],
+    components: &[
+        FormatComponent { component: Component::Depth, bits: 32, numeric_type: NumericType::Sfloat },
+        FormatComponent { component: Component::Stencil, bits: 8, numeric_type: NumericType::Uint },
+    ],
+    numeric_type: Some(NumericType::Sfloat),
+    color: false,
+    depth: true,
+    stencil: true,
+    compressed: false,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static BC1_RGB_UNORM_BLOCK: FormatInfo = FormatInfo {
+    block_size: 8,
+    block_extent: [4, 4, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: Some(Format::Bc1RgbSrgbBlock),
+    linear: None,
+};
+
+static BC1_RGB_SRGB_BLOCK: FormatInfo = FormatInfo {
+    block_size: 8,
+    block_extent: [4, 4, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Srgb },
+    ],
+    numeric_type: Some(NumericType::Srgb),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: None,
+    linear: Some(Format::Bc1RgbUnormBlock),
+};
+
+static BC1_RGBA_UNORM_BLOCK: FormatInfo = FormatInfo {
+    block_size: 8,
+    block_extent: [4, 4, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: Some(Format::Bc1RgbaSrgbBlock),
+    linear: None,
+};
+
+static BC1_RGBA_SRGB_BLOCK: FormatInfo = FormatInfo {
+    block_size: 8,
+    block_extent: [4, 4, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Srgb },
+    ],
+    numeric_type: Some(NumericType::Srgb),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: None,
+    linear: Some(Format::Bc1RgbaUnormBlock),
+};
+
+static BC2_UNORM_BLOCK: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [4, 4, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: Some(Format::Bc2SrgbBlock),
+    linear: None,
+};
+
+static BC2_SRGB_BLOCK: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [4, 4, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Srgb },
+    ],
+    numeric_type: Some(NumericType::Srgb),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: None,
+    linear: Some(Format::Bc2UnormBlock),
+};
+
+static BC3_UNORM_BLOCK: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [4, 4, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: Some(Format::Bc3SrgbBlock),
+    linear: None,
+};
+
+static BC3_SRGB_BLOCK: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [4, 4, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Srgb },
+    ],
+    numeric_type: Some(NumericType::Srgb),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: None,
+    linear: Some(Format::Bc3UnormBlock),
+};
+
+static BC4_UNORM_BLOCK: FormatInfo = FormatInfo {
+    block_size: 8,
+    block_extent: [4, 4, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static BC4_SNORM_BLOCK: FormatInfo = FormatInfo {
+    block_size: 8,
+    block_extent: [4, 4, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Snorm },
+    ],
+    numeric_type: Some(NumericType::Snorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static BC5_UNORM_BLOCK: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [4, 4, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static BC5_SNORM_BLOCK: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [4, 4, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Snorm },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Snorm },
+    ],
+    numeric_type: Some(NumericType::Snorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static BC6H_UFLOAT_BLOCK: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [4, 4, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Ufloat },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Ufloat },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Ufloat },
+    ],
+    numeric_type: Some(NumericType::Ufloat),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static BC6H_SFLOAT_BLOCK: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [4, 4, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Sfloat },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Sfloat },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Sfloat },
+    ],
+    numeric_type: Some(NumericType::Sfloat),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static BC7_UNORM_BLOCK: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [4, 4, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: Some(Format::Bc7SrgbBlock),
+    linear: None,
+};
+
+static BC7_SRGB_BLOCK: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [4, 4, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Srgb },
+    ],
+    numeric_type: Some(NumericType::Srgb),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: None,
+    linear: Some(Format::Bc7UnormBlock),
+};
+
+static ETC2_R8G8B8_UNORM_BLOCK: FormatInfo = FormatInfo {
+    block_size: 8,
+    block_extent: [4, 4, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::B, bits: 8, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: Some(Format::Etc2R8G8B8SrgbBlock),
+    linear: None,
+};
+
+static ETC2_R8G8B8_SRGB_BLOCK: FormatInfo = FormatInfo {
+    block_size: 8,
+    block_extent: [4, 4, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::B, bits: 8, numeric_type: NumericType::Srgb },
+    ],
+    numeric_type: Some(NumericType::Srgb),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: None,
+    linear: Some(Format::Etc2R8G8B8UnormBlock),
+};
+
+static ETC2_R8G8B8A1_UNORM_BLOCK: FormatInfo = FormatInfo {
+    block_size: 8,
+    block_extent: [4, 4, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::B, bits: 8, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::A, bits: 1, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: Some(Format::Etc2R8G8B8A1SrgbBlock),
+    linear: None,
+};
+
+static ETC2_R8G8B8A1_SRGB_BLOCK: FormatInfo = FormatInfo {
+    block_size: 8,
+    block_extent: [4, 4, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::B, bits: 8, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::A, bits: 1, numeric_type: NumericType::Srgb },
+    ],
+    numeric_type: Some(NumericType::Srgb),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: None,
+    linear: Some(Format::Etc2R8G8B8A1UnormBlock),
+};
+
+static ETC2_R8G8B8A8_UNORM_BLOCK: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [4, 4, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::B, bits: 8, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::A, bits: 8, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: Some(Format::Etc2R8G8B8A8SrgbBlock),
+    linear: None,
+};
+
+static ETC2_R8G8B8A8_SRGB_BLOCK: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [4, 4, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 8, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::G, bits: 8, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::B, bits: 8, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::A, bits: 8, numeric_type: NumericType::Srgb },
+    ],
+    numeric_type: Some(NumericType::Srgb),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: None,
+    linear: Some(Format::Etc2R8G8B8A8UnormBlock),
+};
+
+static EAC_R11_UNORM_BLOCK: FormatInfo = FormatInfo {
+    block_size: 8,
+    block_extent: [4, 4, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 11, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static EAC_R11_SNORM_BLOCK: FormatInfo = FormatInfo {
+    block_size: 8,
+    block_extent: [4, 4, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 11, numeric_type: NumericType::Snorm },
+    ],
+    numeric_type: Some(NumericType::Snorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static EAC_R11G11_UNORM_BLOCK: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [4, 4, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 11, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 11, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static EAC_R11G11_SNORM_BLOCK: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [4, 4, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 11, numeric_type: NumericType::Snorm },
+        FormatComponent { component: Component::G, bits: 11, numeric_type: NumericType::Snorm },
+    ],
+    numeric_type: Some(NumericType::Snorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: None,
+    linear: None,
+};
+
+static ASTC_4x4_UNORM_BLOCK: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [4, 4, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: Some(Format::Astc4x4SrgbBlock),
+    linear: None,
+};
+
+static ASTC_4x4_SRGB_BLOCK: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [4, 4, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Srgb },
+    ],
+    numeric_type: Some(NumericType::Srgb),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: None,
+    linear: Some(Format::Astc4x4UnormBlock),
+};
+
+static ASTC_5x4_UNORM_BLOCK: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [5, 4, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: Some(Format::Astc5x4SrgbBlock),
+    linear: None,
+};
+
+static ASTC_5x4_SRGB_BLOCK: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [5, 4, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Srgb },
+    ],
+    numeric_type: Some(NumericType::Srgb),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: None,
+    linear: Some(Format::Astc5x4UnormBlock),
+};
+
+static ASTC_5x5_UNORM_BLOCK: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [5, 5, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: Some(Format::Astc5x5SrgbBlock),
+    linear: None,
+};
+
+static ASTC_5x5_SRGB_BLOCK: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [5, 5, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Srgb },
+    ],
+    numeric_type: Some(NumericType::Srgb),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: None,
+    linear: Some(Format::Astc5x5UnormBlock),
+};
+
+static ASTC_6x5_UNORM_BLOCK: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [6, 5, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: Some(Format::Astc6x5SrgbBlock),
+    linear: None,
+};
+
+static ASTC_6x5_SRGB_BLOCK: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [6, 5, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Srgb },
+    ],
+    numeric_type: Some(NumericType::Srgb),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: None,
+    linear: Some(Format::Astc6x5UnormBlock),
+};
+
+static ASTC_6x6_UNORM_BLOCK: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [6, 6, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: Some(Format::Astc6x6SrgbBlock),
+    linear: None,
+};
+
+static ASTC_6x6_SRGB_BLOCK: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [6, 6, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Srgb },
+    ],
+    numeric_type: Some(NumericType::Srgb),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: None,
+    linear: Some(Format::Astc6x6UnormBlock),
+};
+
+static ASTC_8x5_UNORM_BLOCK: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [8, 5, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: Some(Format::Astc8x5SrgbBlock),
+    linear: None,
+};
+
+static ASTC_8x5_SRGB_BLOCK: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [8, 5, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Srgb },
+    ],
+    numeric_type: Some(NumericType::Srgb),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: None,
+    linear: Some(Format::Astc8x5UnormBlock),
+};
+
+static ASTC_8x6_UNORM_BLOCK: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [8, 6, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: Some(Format::Astc8x6SrgbBlock),
+    linear: None,
+};
+
+static ASTC_8x6_SRGB_BLOCK: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [8, 6, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Srgb },
+    ],
+    numeric_type: Some(NumericType::Srgb),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: None,
+    linear: Some(Format::Astc8x6UnormBlock),
+};
+
+static ASTC_8x8_UNORM_BLOCK: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [8, 8, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: Some(Format::Astc8x8SrgbBlock),
+    linear: None,
+};
+
+static ASTC_8x8_SRGB_BLOCK: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [8, 8, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Srgb },
+    ],
+    numeric_type: Some(NumericType::Srgb),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: None,
+    linear: Some(Format::Astc8x8UnormBlock),
+};
+
+static ASTC_10x5_UNORM_BLOCK: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [10, 5, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: Some(Format::Astc10x5SrgbBlock),
+    linear: None,
+};
+
+static ASTC_10x5_SRGB_BLOCK: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [10, 5, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Srgb },
+    ],
+    numeric_type: Some(NumericType::Srgb),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: None,
+    linear: Some(Format::Astc10x5UnormBlock),
+};
+
+static ASTC_10x6_UNORM_BLOCK: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [10, 6, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: Some(Format::Astc10x6SrgbBlock),
+    linear: None,
+};
+
+static ASTC_10x6_SRGB_BLOCK: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [10, 6, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Srgb },
+    ],
+    numeric_type: Some(NumericType::Srgb),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: None,
+    linear: Some(Format::Astc10x6UnormBlock),
+};
+
+static ASTC_10x8_UNORM_BLOCK: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [10, 8, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: Some(Format::Astc10x8SrgbBlock),
+    linear: None,
+};
+
+static ASTC_10x8_SRGB_BLOCK: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [10, 8, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Srgb },
+    ],
+    numeric_type: Some(NumericType::Srgb),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: None,
+    linear: Some(Format::Astc10x8UnormBlock),
+};
+
+static ASTC_10x10_UNORM_BLOCK: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [10, 10, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: Some(Format::Astc10x10SrgbBlock),
+    linear: None,
+};
+
+static ASTC_10x10_SRGB_BLOCK: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [10, 10, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Srgb },
+    ],
+    numeric_type: Some(NumericType::Srgb),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: None,
+    linear: Some(Format::Astc10x10UnormBlock),
+};
+
+static ASTC_12x10_UNORM_BLOCK: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [12, 10, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: Some(Format::Astc12x10SrgbBlock),
+    linear: None,
+};
+
+static ASTC_12x10_SRGB_BLOCK: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [12, 10, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Srgb },
+    ],
+    numeric_type: Some(NumericType::Srgb),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: None,
+    linear: Some(Format::Astc12x10UnormBlock),
+};
+
+static ASTC_12x12_UNORM_BLOCK: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [12, 12, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: Some(Format::Astc12x12SrgbBlock),
+    linear: None,
+};
+
+static ASTC_12x12_SRGB_BLOCK: FormatInfo = FormatInfo {
+    block_size: 16,
+    block_extent: [12, 12, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Srgb },
+    ],
+    numeric_type: Some(NumericType::Srgb),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: None,
+    linear: Some(Format::Astc12x12UnormBlock),
+};
+
+static PVRTC1_2BPP_UNORM_BLOCK_IMG: FormatInfo = FormatInfo {
+    block_size: 8,
+    block_extent: [8, 4, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: Some(Format::Pvrtc12bppSrgbBlockImg),
+    linear: None,
+};
+
+static PVRTC1_4BPP_UNORM_BLOCK_IMG: FormatInfo = FormatInfo {
+    block_size: 8,
+    block_extent: [4, 4, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: Some(Format::Pvrtc14bppSrgbBlockImg),
+    linear: None,
+};
+
+static PVRTC2_2BPP_UNORM_BLOCK_IMG: FormatInfo = FormatInfo {
+    block_size: 8,
+    block_extent: [8, 4, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: Some(Format::Pvrtc22bppSrgbBlockImg),
+    linear: None,
+};
+
+static PVRTC2_4BPP_UNORM_BLOCK_IMG: FormatInfo = FormatInfo {
+    block_size: 8,
+    block_extent: [4, 4, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Unorm },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Unorm },
+    ],
+    numeric_type: Some(NumericType::Unorm),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: Some(Format::Pvrtc24bppSrgbBlockImg),
+    linear: None,
+};
+
+static PVRTC1_2BPP_SRGB_BLOCK_IMG: FormatInfo = FormatInfo {
+    block_size: 8,
+    block_extent: [8, 4, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Srgb },
+    ],
+    numeric_type: Some(NumericType::Srgb),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: None,
+    linear: Some(Format::Pvrtc12bppUnormBlockImg),
+};
+
+static PVRTC1_4BPP_SRGB_BLOCK_IMG: FormatInfo = FormatInfo {
+    block_size: 8,
+    block_extent: [4, 4, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Srgb },
+    ],
+    numeric_type: Some(NumericType::Srgb),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: None,
+    linear: Some(Format::Pvrtc14bppUnormBlockImg),
+};
+
+static PVRTC2_2BPP_SRGB_BLOCK_IMG: FormatInfo = FormatInfo {
+    block_size: 8,
+    block_extent: [8, 4, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Srgb },
+    ],
+    numeric_type: Some(NumericType::Srgb),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: None,
+    linear: Some(Format::Pvrtc22bppUnormBlockImg),
+};
+
+static PVRTC2_4BPP_SRGB_BLOCK_IMG: FormatInfo = FormatInfo {
+    block_size: 8,
+    block_extent: [4, 4, 1],
+    components: &[
+        FormatComponent { component: Component::R, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::G, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::B, bits: 0, numeric_type: NumericType::Srgb },
+        FormatComponent { component: Component::A, bits: 0, numeric_type: NumericType::Srgb },
+    ],
+    numeric_type: Some(NumericType::Srgb),
+    color: true,
+    depth: false,
+    stencil: false,
+    compressed: true,
+    packed: false,
+    srgb: None,
+    linear: Some(Format::Pvrtc24bppUnormBlockImg),
+};
+
+
+/// Returns the metadata of `format`.
+pub(crate) fn format_info(format: Format) -> &'static FormatInfo {
+    match format {
+        Format::Undefined => &UNDEFINED,
+        Format::R4G4UnormPack8 => &R4G4_UNORM_PACK8,
+        Format::R4G4B4B4UnormPack16 => &R4G4B4A4_UNORM_PACK16,
+        Format::B4G4R4A4UnormPack16 => &B4G4R4A4_UNORM_PACK16,
+        Format::R5G6B5UnormPack16 => &R5G6B5_UNORM_PACK16,
+        Format::B5G6R5UnormPack16 => &B5G6R5_UNORM_PACK16,
+        Format::R5G5B5A1UnormPack16 => &R5G5B5A1_UNORM_PACK16,
+        Format::B5G5R5A1UnormPack16 => &B5G5R5A1_UNORM_PACK16,
+        Format::A1R5G5B5UnormPack16 => &A1R5G5B5_UNORM_PACK16,
+        Format::R8Unorm => &R8_UNORM,
+        Format::R8Snorm => &R8_SNORM,
+        Format::R8Uscaled => &R8_USCALED,
+        Format::R8Sscaled => &R8_SSCALED,
+        Format::R8Uint => &R8_UINT,
+        Format::R8Sint => &R8_SINT,
+        Format::R8Srgb => &R8_SRGB,
+        Format::R8G8Unorm => &R8G8_UNORM,
+        Format::R8G8Snorm => &R8G8_SNORM,
+        Format::R8G8Uscaled => &R8G8_USCALED,
+        Format::R8G8Sscaled => &R8G8_SSCALED,
+        Format::R8G8Uint => &R8G8_UINT,
+        Format::R8G8Sint => &R8G8_SINT,
+        Format::R8G8Srgb => &R8G8_SRGB,
+        Format::R8G8B8Unorm => &R8G8B8_UNORM,
+        Format::R8G8B8Snorm => &R8G8B8_SNORM,
+        Format::R8G8B8Uscaled => &R8G8B8_USCALED,
+        Format::R8G8B8Sscaled => &R8G8B8_SSCALED,
+        Format::R8G8B8Uint => &R8G8B8_UINT,
+        Format::R8G8B8Sint => &R8G8B8_SINT,
+        Format::R8G8B8Srgb => &R8G8B8_SRGB,
+        Format::B8G8R8Unorm => &B8G8R8_UNORM,
+        Format::B8G8R8Snorm => &B8G8R8_SNORM,
+        Format::B8G8R8Uscaled => &B8G8R8_USCALED,
+        Format::B8G8R8Sscaled => &B8G8R8_SSCALED,
+        Format::B8G8R8Uint => &B8G8R8_UINT,
+        Format::B8G8R8Sint => &B8G8R8_SINT,
+        Format::B8G8R8Srgb => &B8G8R8_SRGB,
+        Format::R8G8B8A8Unorm => &R8G8B8A8_UNORM,
+        Format::R8G8B8A8Snorm => &R8G8B8A8_SNORM,
+        Format::R8G8B8A8Uscaled => &R8G8B8A8_USCALED,
+        Format::R8G8B8A8Sscaled => &R8G8B8A8_SSCALED,
+        Format::R8G8B8A8Uint => &R8G8B8A8_UINT,
+        Format::R8G8B8A8Sint => &R8G8B8A8_SINT,
+        Format::R8G8B8A8Srgb => &R8G8B8A8_SRGB,
+        Format::B8G8R8A8Unorm => &B8G8R8A8_UNORM,
+        Format::B8G8R8A8Snorm => &B8G8R8A8_SNORM,
+        Format::B8G8R8A8Uscaled => &B8G8R8A8_USCALED,
+        Format::B8G8R8A8Sscaled => &B8G8R8A8_SSCALED,
+        Format::B8G8R8A8Uint => &B8G8R8A8_UINT,
+        Format::B8G8R8A8Sint => &B8G8R8A8_SINT,
+        Format::B8G8R8A8Srgb => &B8G8R8A8_SRGB,
+        Format::A8B8G8R8UnormPack32 => &A8B8G8R8_UNORM_PACK32,
+        Format::A8B8G8R8SnormPack32 => &A8B8G8R8_SNORM_PACK32,
+        Format::A8B8G8R8UscaledPack32 => &A8B8G8R8_USCALED_PACK32,
+        Format::A8B8G8R8SscaledPack32 => &A8B8G8R8_SSCALED_PACK32,
+        Format::A8B8G8R8UintPack32 => &A8B8G8R8_UINT_PACK32,
+        Format::A8B8G8R8SintPack32 => &A8B8G8R8_SINT_PACK32,
+        Format::A8B8G8R8SrgbPack32 => &A8B8G8R8_SRGB_PACK32,
+        Format::A2R10G10B10UnormPack32 => &A2R10G10B10_UNORM_PACK32,
+        Format::A2R10G10B10SnormPack32 => &A2R10G10B10_SNORM_PACK32,
+        Format::A2R10G10B10UscaledPack32 => &A2R10G10B10_USCALED_PACK32,
+        Format::A2R10G10B10SscaledPack32 => &A2R10G10B10_SSCALED_PACK32,
+        Format::A2R10G10B10UintPack32 => &A2R10G10B10_UINT_PACK32,
+        Format::A2R10G10B10SintPack32 => &A2R10G10B10_SINT_PACK32,
+        Format::A2B10G10R10UnormPack32 => &A2B10G10R10_UNORM_PACK32,
+        Format::A2B10G10R10SnormPack32 => &A2B10G10R10_SNORM_PACK32,
+        Format::A2B10G10R10UscaledPack32 => &A2B10G10R10_USCALED_PACK32,
+        Format::A2B10G10R10SscaledPack32 => &A2B10G10R10_SSCALED_PACK32,
+        Format::A2B10G10R10UintPack32 => &A2B10G10R10_UINT_PACK32,
+        Format::A2B10G10R10SintPack32 => &A2B10G10R10_SINT_PACK32,
+        Format::R16Unorm => &R16_UNORM,
+        Format::R16Snorm => &R16_SNORM,
+        Format::R16Uscaled => &R16_USCALED,
+        Format::R16Sscaled => &R16_SSCALED,
+        Format::R16Uint => &R16_UINT,
+        Format::R16Sint => &R16_SINT,
+        Format::R16Sfloat => &R16_SFLOAT,
+        Format::R16G16Unorm => &R16G16_UNORM,
+        Format::R16G16Snorm => &R16G16_SNORM,
+        Format::R16G16Uscaled => &R16G16_USCALED,
+        Format::R16G16Sscaled => &R16G16_SSCALED,
+        Format::R16G16Uint => &R16G16_UINT,
+        Format::R16G16Sint => &R16G16_SINT,
+        Format::R16G16Sfloat => &R16G16_SFLOAT,
+        Format::R16G16B16Unorm => &R16G16B16_UNORM,
+        Format::R16G16B16Snorm => &R16G16B16_SNORM,
+        Format::R16G16B16Uscaled => &R16G16B16_USCALED,
+        Format::R16G16B16Sscaled => &R16G16B16_SSCALED,
+        Format::R16G16B16Uint => &R16G16B16_UINT,
+        Format::R16G16B16Sint => &R16G16B16_SINT,
+        Format::R16G16B16Sfloat => &R16G16B16_SFLOAT,
+        Format::R16G16B16A16Unorm => &R16G16B16A16_UNORM,
+        Format::R16G16B16A16Snorm => &R16G16B16A16_SNORM,
+        Format::R16G16B16A16Uscaled => &R16G16B16A16_USCALED,
+        Format::R16G16B16A16Sscaled => &R16G16B16A16_SSCALED,
+        Format::R16G16B16A16Uint => &R16G16B16A16_UINT,
+        Format::R16G16B16A16Sint => &R16G16B16A16_SINT,
+        Format::R16G16B16A16Sfloat => &R16G16B16A16_SFLOAT,
+        Format::R32Uint => &R32_UINT,
+        Format::R32Sint => &R32_SINT,
+        Format::R32Sfloat => &R32_SFLOAT,
+        Format::R32G32Uint => &R32G32_UINT,
+        Format::R32G32Sint => &R32G32_SINT,
+        Format::R32G32Sfloat => &R32G32_SFLOAT,
+        Format::R32G32B32Uint => &R32G32B32_UINT,
+        Format::R32G32B32Sint => &R32G32B32_SINT,
+        Format::R32G32B32Sfloat => &R32G32B32_SFLOAT,
+        Format::R32G32B32A32Uint => &R32G32B32A32_UINT,
+        Format::R32G32B32A32Sint => &R32G32B32A32_SINT,
+        Format::R32G32B32A32Sfloat => &R32G32B32A32_SFLOAT,
+        Format::R64Uint => &R64_UINT,
+        Format::R64Sint => &R64_SINT,
+        Format::R64Sfloat => &R64_SFLOAT,
+        Format::R64G64Uint => &R64G64_UINT,
+        Format::R64G64Sint => &R64G64_SINT,
+        Format::R64G64Sfloat => &R64G64_SFLOAT,
+        Format::R64G64B64Uint => &R64G64B64_UINT,
+        Format::R64G64B64Sint => &R64G64B64_SINT,
+        Format::R64G64B64Sfloat => &R64G64B64_SFLOAT,
+        Format::R64G64B64A64Uint => &R64G64B64A64_UINT,
+        Format::R64G64B64A64Sint => &R64G64B64A64_SINT,
+        Format::R64G64B64A64Sfloat => &R64G64B64A64_SFLOAT,
+        Format::B10G11R11UfloatPack32 => &B10G11R11_UFLOAT_PACK32,
+        Format::E5B9G9R9UfloatPack32 => &E5B9G9R9_UFLOAT_PACK32,
+        Format::D16Unorm => &D16_UNORM,
+        Format::X8D24UnormPack32 => &X8_D24_UNORM_PACK32,
+        Format::D32Sfloat => &D32_SFLOAT,
+        Format::S8Uint => &S8_UINT,
+        Format::D16UnormS8Uint => &D16_UNORM_S8_UINT,
+        Format::D24UnormS8Uint => &D24_UNORM_S8_UINT,
+        Format::D32SfloatS8Uint => &D32_SFLOAT_S8_UINT,
+        Format::Bc1RgbUnormBlock => &BC1_RGB_UNORM_BLOCK,
+        Format::Bc1RgbSrgbBlock => &BC1_RGB_SRGB_BLOCK,
+        Format::Bc1RgbaUnormBlock => &BC1_RGBA_UNORM_BLOCK,
+        Format::Bc1RgbaSrgbBlock => &BC1_RGBA_SRGB_BLOCK,
+        Format::Bc2UnormBlock => &BC2_UNORM_BLOCK,
+        Format::Bc2SrgbBlock => &BC2_SRGB_BLOCK,
+        Format::Bc3UnormBlock => &BC3_UNORM_BLOCK,
+        Format::Bc3SrgbBlock => &BC3_SRGB_BLOCK,
+        Format::Bc4UnormBlock => &BC4_UNORM_BLOCK,
+        Format::Bc4SnormBlock => &BC4_SNORM_BLOCK,
+        Format::Bc5UnormBlock => &BC5_UNORM_BLOCK,
+        Format::Bc5SnormBlock => &BC5_SNORM_BLOCK,
+        Format::Bc6hUfloatBlock => &BC6H_UFLOAT_BLOCK,
+        Format::Bc6hSfloatBlock => &BC6H_SFLOAT_BLOCK,
+        Format::Bc7UnormBlock => &BC7_UNORM_BLOCK,
+        Format::Bc7SrgbBlock => &BC7_SRGB_BLOCK,
+        Format::Etc2R8G8B8UnormBlock => &ETC2_R8G8B8_UNORM_BLOCK,
+        Format::Etc2R8G8B8SrgbBlock => &ETC2_R8G8B8_SRGB_BLOCK,
+        Format::Etc2R8G8B8A1UnormBlock => &ETC2_R8G8B8A1_UNORM_BLOCK,
+        Format::Etc2R8G8B8A1SrgbBlock => &ETC2_R8G8B8A1_SRGB_BLOCK,
+        Format::Etc2R8G8B8A8UnormBlock => &ETC2_R8G8B8A8_UNORM_BLOCK,
+        Format::Etc2R8G8B8A8SrgbBlock => &ETC2_R8G8B8A8_SRGB_BLOCK,
+        Format::EacR11UnormBlock => &EAC_R11_UNORM_BLOCK,
+        Format::EacR11SnormBlock => &EAC_R11_SNORM_BLOCK,
+        Format::EacR11G11UnormBlock => &EAC_R11G11_UNORM_BLOCK,
+        Format::EacR11G11SnormBlock => &EAC_R11G11_SNORM_BLOCK,
+        Format::Astc4x4UnormBlock => &ASTC_4x4_UNORM_BLOCK,
+        Format::Astc4x4SrgbBlock => &ASTC_4x4_SRGB_BLOCK,
+        Format::Astc5x4UnormBlock => &ASTC_5x4_UNORM_BLOCK,
+        Format::Astc5x4SrgbBlock => &ASTC_5x4_SRGB_BLOCK,
+        Format::Astc5x5UnormBlock => &ASTC_5x5_UNORM_BLOCK,
+        Format::Astc5x5SrgbBlock => &ASTC_5x5_SRGB_BLOCK,
+        Format::Astc6x5UnormBlock => &ASTC_6x5_UNORM_BLOCK,
+        Format::Astc6x5SrgbBlock => &ASTC_6x5_SRGB_BLOCK,
+        Format::Astc6x6UnormBlock => &ASTC_6x6_UNORM_BLOCK,
+        Format::Astc6x6SrgbBlock => &ASTC_6x6_SRGB_BLOCK,
+        Format::Astc8x5UnormBlock => &ASTC_8x5_UNORM_BLOCK,
+        Format::Astc8x5SrgbBlock => &ASTC_8x5_SRGB_BLOCK,
+        Format::Astc8x6UnormBlock => &ASTC_8x6_UNORM_BLOCK,
+        Format::Astc8x6SrgbBlock => &ASTC_8x6_SRGB_BLOCK,
+        Format::Astc8x8UnormBlock => &ASTC_8x8_UNORM_BLOCK,
+        Format::Astc8x8SrgbBlock => &ASTC_8x8_SRGB_BLOCK,
+        Format::Astc10x5UnormBlock => &ASTC_10x5_UNORM_BLOCK,
+        Format::Astc10x5SrgbBlock => &ASTC_10x5_SRGB_BLOCK,
+        Format::Astc10x6UnormBlock => &ASTC_10x6_UNORM_BLOCK,
+        Format::Astc10x6SrgbBlock => &ASTC_10x6_SRGB_BLOCK,
+        Format::Astc10x8UnormBlock => &ASTC_10x8_UNORM_BLOCK,
+        Format::Astc10x8SrgbBlock => &ASTC_10x8_SRGB_BLOCK,
+        Format::Astc10x10UnormBlock => &ASTC_10x10_UNORM_BLOCK,
+        Format::Astc10x10SrgbBlock => &ASTC_10x10_SRGB_BLOCK,
+        Format::Astc12x10UnormBlock => &ASTC_12x10_UNORM_BLOCK,
+        Format::Astc12x10SrgbBlock => &ASTC_12x10_SRGB_BLOCK,
+        Format::Astc12x12UnormBlock => &ASTC_12x12_UNORM_BLOCK,
+        Format::Astc12x12SrgbBlock => &ASTC_12x12_SRGB_BLOCK,
+        Format::Pvrtc12bppUnormBlockImg => &PVRTC1_2BPP_UNORM_BLOCK_IMG,
+        Format::Pvrtc14bppUnormBlockImg => &PVRTC1_4BPP_UNORM_BLOCK_IMG,
+        Format::Pvrtc22bppUnormBlockImg => &PVRTC2_2BPP_UNORM_BLOCK_IMG,
+        Format::Pvrtc24bppUnormBlockImg => &PVRTC2_4BPP_UNORM_BLOCK_IMG,
+        Format::Pvrtc12bppSrgbBlockImg => &PVRTC1_2BPP_SRGB_BLOCK_IMG,
+        Format::Pvrtc14bppSrgbBlockImg => &PVRTC1_4BPP_SRGB_BLOCK_IMG,
+        Format::Pvrtc22bppSrgbBlockImg => &PVRTC2_2BPP_SRGB_BLOCK_IMG,
+        Format::Pvrtc24bppSrgbBlockImg => &PVRTC2_4BPP_SRGB_BLOCK_IMG,
+    }
+}
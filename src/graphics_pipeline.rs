@@ -2,7 +2,8 @@ use std::sync::Arc;
 use std::marker::PhantomData;
 use smallvec::SmallVec;
 use ::{VdResult, Device, PipelineLayoutHandle, PipelineHandle, RenderPassHandle,
-    Handle, GraphicsPipelineCreateInfo};
+    Handle, GraphicsPipelineCreateInfo, PipelineCache};
+use pipeline_cache;
 
 
 #[derive(Debug)]
@@ -40,20 +41,27 @@ impl GraphicsPipeline {
     }
 
     /// Creates several graphics pipelines at once.
+    ///
+    /// Returns an `IncompatiblePipelineCache` error if the builders specify
+    /// different pipeline caches.
     #[deprecated(note = "use `Device::create_graphics_pipelines`")]
     pub fn create<'b, Gpb>(device: &Device, builders: &[Gpb])
             -> VdResult<SmallVec<[GraphicsPipeline; 8]>>
-            where Gpb: AsRef<::GraphicsPipelineCreateInfo<'b>> {
+            where Gpb: AsRef<GraphicsPipelineBuilder<'b>> {
         let mut create_infos = SmallVec::<[GraphicsPipelineCreateInfo; 8]>::new();
         let mut pipelines = SmallVec::<[GraphicsPipeline; 8]>::new();
         create_infos.reserve_exact(builders.len());
         pipelines.reserve_exact(builders.len());
 
         for builder in builders {
-            create_infos.push(builder.as_ref().clone());
+            create_infos.push(builder.as_ref().create_info.clone());
         }
+        let pipeline_cache = pipeline_cache::batch_cache_handle(
+            builders.iter().map(|builder| builder.as_ref().pipeline_cache))?;
 
-        let pipeline_handles = unsafe { device.create_graphics_pipelines(None, &create_infos, None)? };
+        let pipeline_handles = unsafe {
+            device.create_graphics_pipelines(pipeline_cache, &create_infos, None)?
+        };
 
         for handle in pipeline_handles {
             pipelines.push(
@@ -94,6 +102,7 @@ unsafe impl<'g> Handle for &'g GraphicsPipeline {
 #[repr(C)]
 pub struct GraphicsPipelineBuilder<'b> {
     create_info: GraphicsPipelineCreateInfo<'b>,
+    pipeline_cache: Option<&'b PipelineCache>,
     _p: PhantomData<&'b ()>,
 }
 
@@ -102,6 +111,7 @@ impl<'b> GraphicsPipelineBuilder<'b> {
     pub fn new() -> GraphicsPipelineBuilder<'b> {
         GraphicsPipelineBuilder {
             create_info: GraphicsPipelineCreateInfo::default(),
+            pipeline_cache: None,
             _p: PhantomData,
         }
     }
@@ -259,12 +269,21 @@ impl<'b> GraphicsPipelineBuilder<'b> {
         self
     }
 
+    /// Specifies a pipeline cache to use when creating the pipeline.
+    pub fn pipeline_cache<'s>(&'s mut self, pipeline_cache: &'b PipelineCache)
+            -> &'s mut GraphicsPipelineBuilder<'b> {
+        self.pipeline_cache = Some(pipeline_cache);
+        self
+    }
+
     /// Creates and returns a new `GraphicsPipeline`. Use
     /// `GraphicsPipeline::create` to create multiple pipelines in one call.
     pub fn build(&self, device: Device) -> VdResult<GraphicsPipeline> {
         let handle = unsafe {
             let create_infos = ::std::slice::from_raw_parts(&self.create_info, 1);
-            *device.create_graphics_pipelines(None, create_infos, None)?.get_unchecked(0)
+            *device.create_graphics_pipelines(self.pipeline_cache.map(|pc| pc.handle()),
+                create_infos, None)?
+                .get_unchecked(0)
         };
 
         Ok(GraphicsPipeline {
@@ -0,0 +1,425 @@
+//! Shader and pipeline hot reloading for development.
+//!
+//! A `ShaderWatcher` polls shader files for modifications, optionally
+//! compiling sources to SPIR-V with a configurable command. A
+//! `ReloadablePipeline` rebuilds a pipeline whenever one of its shaders
+//! changes, keeping the previous pipeline if compilation or creation fails
+//! and swapping in the new one once in-flight frames have completed:
+//!
+//! ```ignore
+//! let mut pipeline = ReloadablePipeline::new(device.clone(), vec![
+//!     ShaderSource::source("shaders/shader.vert", "shaders/vert.spv", CompileCommand::glslang()),
+//!     ShaderSource::spir_v("shaders/frag.spv"),
+//! ], move |modules| create_graphics_pipeline(&device, &modules[0], &modules[1]))?;
+//!
+//! loop {
+//!     if let ReloadStatus::Failed(err) = pipeline.poll() {
+//!         println!("Shader reload failed: {}", err);
+//!     }
+//!     pipeline.swap_after(&in_flight_fences)?;
+//!     // Record command buffers using `pipeline.pipeline()`...
+//! }
+//! ```
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::SystemTime;
+use smallvec::SmallVec;
+use ::{util, VdResult, Error, ErrorKind, Device, Fence, FenceHandle, ShaderModule};
+
+
+/// Returns the modification time of the file at `path`, or `None` if it can
+/// not be read (for example while an editor is replacing it).
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+
+/// A command compiling a shader source file to SPIR-V.
+///
+/// Occurrences of `{input}` and `{output}` in the arguments are replaced by
+/// the paths of the source and SPIR-V files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompileCommand {
+    program: String,
+    args: Vec<String>,
+}
+
+impl CompileCommand {
+    /// Returns a new command running `program` with no arguments.
+    pub fn new<S: Into<String>>(program: S) -> CompileCommand {
+        CompileCommand { program: program.into(), args: Vec::new() }
+    }
+
+    /// Returns a command compiling GLSL with `glslangValidator`.
+    pub fn glslang() -> CompileCommand {
+        CompileCommand::new("glslangValidator")
+            .arg("-V")
+            .arg("{input}")
+            .arg("-o")
+            .arg("{output}")
+    }
+
+    /// Adds an argument to the command.
+    pub fn arg<S: Into<String>>(mut self, arg: S) -> CompileCommand {
+        self.args.push(arg.into());
+        self
+    }
+
+    /// Runs the command, returning a `ShaderCompilation` error containing
+    /// its output if it fails.
+    fn run(&self, input: &Path, output: &Path) -> VdResult<()> {
+        let input_str = input.to_string_lossy();
+        let output_str = output.to_string_lossy();
+        let args = self.args.iter()
+            .map(|arg| arg.replace("{input}", &input_str).replace("{output}", &output_str));
+
+        let result = Command::new(&self.program).args(args).output()?;
+        if !result.status.success() {
+            let mut output = String::from_utf8_lossy(&result.stdout).into_owned();
+            output.push_str(&String::from_utf8_lossy(&result.stderr));
+            return Err(ErrorKind::ShaderCompilation { path: input.display().to_string(),
+                output }.into());
+        }
+        Ok(())
+    }
+}
+
+
+/// A shader file on disk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShaderSource {
+    /// A SPIR-V module.
+    SpirV(PathBuf),
+    /// A source file compiled to the SPIR-V module at `output` by `command`.
+    Source { path: PathBuf, output: PathBuf, command: CompileCommand },
+}
+
+impl ShaderSource {
+    /// Returns a source for the SPIR-V module at `path`.
+    pub fn spir_v<P: Into<PathBuf>>(path: P) -> ShaderSource {
+        ShaderSource::SpirV(path.into())
+    }
+
+    /// Returns a source for the shader source file at `path`, compiled to
+    /// `output` by `command`.
+    pub fn source<P, Q>(path: P, output: Q, command: CompileCommand) -> ShaderSource
+            where P: Into<PathBuf>, Q: Into<PathBuf> {
+        ShaderSource::Source { path: path.into(), output: output.into(), command }
+    }
+
+    /// Returns the path of the watched file.
+    pub fn path(&self) -> &Path {
+        match *self {
+            ShaderSource::SpirV(ref path) => path,
+            ShaderSource::Source { ref path, .. } => path,
+        }
+    }
+
+    /// Returns the SPIR-V words of the shader, compiling its source first if
+    /// necessary.
+    pub fn load(&self) -> VdResult<Vec<u32>> {
+        match *self {
+            ShaderSource::SpirV(ref path) => util::read_spir_v_file(path),
+            ShaderSource::Source { ref path, ref output, ref command } => {
+                command.run(path, output)?;
+                util::read_spir_v_file(output)
+            },
+        }
+    }
+}
+
+
+/// Watches shader files for modifications by polling their modification
+/// times.
+#[derive(Debug, Clone)]
+pub struct ShaderWatcher {
+    shaders: Vec<(ShaderSource, Option<SystemTime>)>,
+}
+
+impl ShaderWatcher {
+    /// Returns a new watcher with no shaders.
+    pub fn new() -> ShaderWatcher {
+        ShaderWatcher { shaders: Vec::new() }
+    }
+
+    /// Starts watching `source`, returning its index.
+    pub fn watch(&mut self, source: ShaderSource) -> usize {
+        let modified = modified_time(source.path());
+        self.shaders.push((source, modified));
+        self.shaders.len() - 1
+    }
+
+    /// Returns the source at `index`.
+    pub fn source(&self, index: usize) -> &ShaderSource {
+        &self.shaders[index].0
+    }
+
+    /// Returns the number of watched shaders.
+    pub fn len(&self) -> usize {
+        self.shaders.len()
+    }
+
+    /// Returns true if no shaders are watched.
+    pub fn is_empty(&self) -> bool {
+        self.shaders.is_empty()
+    }
+
+    /// Returns the indices of the shaders modified since the last poll.
+    pub fn poll(&mut self) -> SmallVec<[usize; 4]> {
+        let mut changed = SmallVec::new();
+        for (i, &mut (ref source, ref mut modified)) in self.shaders.iter_mut().enumerate() {
+            let current = modified_time(source.path());
+            if current.is_some() && current != *modified {
+                *modified = current;
+                changed.push(i);
+            }
+        }
+        changed
+    }
+
+    /// Loads and creates shader modules for every watched shader, in order.
+    pub fn create_shader_modules(&self, device: &Device) -> VdResult<Vec<ShaderModule>> {
+        self.shaders.iter()
+            .map(|&(ref source, _)| ShaderModule::new(device.clone(), &source.load()?))
+            .collect()
+    }
+}
+
+
+/// The result of `ReloadablePipeline::poll`.
+#[derive(Debug)]
+pub enum ReloadStatus {
+    /// No shaders have changed.
+    Unchanged,
+    /// The pipeline has been rebuilt and will be used after the next
+    /// `ReloadablePipeline::swap_after`.
+    Rebuilt,
+    /// A shader failed to compile or the pipeline could not be created. The
+    /// current pipeline remains in use.
+    Failed(Error),
+}
+
+
+/// Rebuilds a pipeline with `rebuild` if any shader watched by `watcher` has
+/// changed, storing it in `pending` if successful.
+fn reload<P, F>(watcher: &mut ShaderWatcher, pending: &mut Option<P>, rebuild: F) -> ReloadStatus
+        where F: FnOnce(&ShaderWatcher) -> VdResult<P> {
+    if watcher.poll().is_empty() {
+        return ReloadStatus::Unchanged;
+    }
+    match rebuild(watcher) {
+        Ok(pipeline) => {
+            *pending = Some(pipeline);
+            ReloadStatus::Rebuilt
+        },
+        Err(err) => ReloadStatus::Failed(err),
+    }
+}
+
+
+/// A pipeline which is rebuilt when its shaders change.
+///
+/// The pipeline is created by a closure which is passed shader modules
+/// for each watched shader, in the order given, and may capture whatever
+/// other state (layouts, render pass, builder configuration) it needs.
+/// It can create either a `GraphicsPipeline` or a `ComputePipeline`.
+pub struct ReloadablePipeline<P> {
+    device: Device,
+    watcher: ShaderWatcher,
+    build: Box<dyn FnMut(&[ShaderModule]) -> VdResult<P>>,
+    pipeline: P,
+    pending: Option<P>,
+}
+
+impl<P> ReloadablePipeline<P> {
+    /// Loads `sources` and creates the initial pipeline with `build`.
+    ///
+    /// Unlike later reloads, failure to create the initial pipeline is
+    /// returned as an error.
+    pub fn new<F>(device: Device, sources: Vec<ShaderSource>, build: F)
+            -> VdResult<ReloadablePipeline<P>>
+            where F: FnMut(&[ShaderModule]) -> VdResult<P> + 'static {
+        let mut watcher = ShaderWatcher::new();
+        for source in sources {
+            watcher.watch(source);
+        }
+        let mut build = build;
+        let pipeline = build(&watcher.create_shader_modules(&device)?)?;
+
+        Ok(ReloadablePipeline { device, watcher, build: Box::new(build), pipeline, pending: None })
+    }
+
+    /// Returns the pipeline currently in use.
+    pub fn pipeline(&self) -> &P {
+        &self.pipeline
+    }
+
+    /// Returns true if a rebuilt pipeline is waiting to be swapped in.
+    pub fn has_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Returns the shader watcher.
+    pub fn watcher(&self) -> &ShaderWatcher {
+        &self.watcher
+    }
+
+    /// Checks the shaders for modifications, rebuilding the pipeline if any
+    /// have changed.
+    ///
+    /// A rebuilt pipeline does not replace the current one until
+    /// `::swap_after` is called.
+    pub fn poll(&mut self) -> ReloadStatus {
+        let device = &self.device;
+        let build = &mut self.build;
+        reload(&mut self.watcher, &mut self.pending, |watcher| {
+            build(&watcher.create_shader_modules(device)?)
+        })
+    }
+
+    /// Replaces the current pipeline with a rebuilt one, if any, returning
+    /// true if it was replaced.
+    ///
+    /// `fences` must include the fence of every frame in flight which may
+    /// use the current pipeline. They are waited on before the current
+    /// pipeline is dropped, so that no pending command buffer refers to it.
+    pub fn swap_after(&mut self, fences: &[&Fence]) -> VdResult<bool> {
+        let pipeline = match self.pending.take() {
+            Some(pipeline) => pipeline,
+            None => return Ok(false),
+        };
+        if !fences.is_empty() {
+            let fence_handles: SmallVec<[FenceHandle; 4]> = fences.iter()
+                .map(|fence| fence.handle())
+                .collect();
+            let result = unsafe {
+                self.device.wait_for_fences(&fence_handles, true, u64::max_value())
+            };
+            if let Err(err) = result {
+                self.pending = Some(pipeline);
+                return Err(err);
+            }
+        }
+        self.pipeline = pipeline;
+        Ok(true)
+    }
+}
+
+impl<P: fmt::Debug> fmt::Debug for ReloadablePipeline<P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ReloadablePipeline")
+            .field("device", &self.device)
+            .field("watcher", &self.watcher)
+            .field("pipeline", &self.pipeline)
+            .field("pending", &self.pending)
+            .finish()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs::File;
+    use std::process;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    /// Returns an empty directory for the files of test `name`.
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("voodoo-hot-reload-{}-{}", process::id(), name));
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// Writes `bytes` to `path`, setting its modification time to `secs`
+    /// seconds after the epoch (as modification times may be too coarse to
+    /// distinguish consecutive writes).
+    fn write(path: &Path, bytes: &[u8], secs: u64) {
+        fs::write(path, bytes).unwrap();
+        File::options().write(true).open(path).unwrap()
+            .set_modified(UNIX_EPOCH + Duration::from_secs(secs)).unwrap();
+    }
+
+    /// Returns the bytes of an empty SPIR-V module with an ID bound of `bound`.
+    fn module(bound: u32) -> Vec<u8> {
+        [0x0723_0203, 0x0001_0000, 0, bound, 0].iter()
+            .flat_map(|word: &u32| word.to_le_bytes().to_vec())
+            .collect()
+    }
+
+    #[test]
+    fn modifications_are_detected() {
+        let dir = temp_dir("modified");
+        let (a, b) = (dir.join("a.spv"), dir.join("b.spv"));
+        write(&a, &module(1), 1000);
+        write(&b, &module(1), 1000);
+
+        let mut watcher = ShaderWatcher::new();
+        watcher.watch(ShaderSource::spir_v(&a));
+        watcher.watch(ShaderSource::spir_v(&b));
+        assert!(watcher.poll().is_empty());
+
+        write(&b, &module(2), 2000);
+        assert_eq!(&watcher.poll()[..], &[1]);
+        assert!(watcher.poll().is_empty());
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn missing_files_are_skipped() {
+        let dir = temp_dir("missing");
+        let path = dir.join("shader.spv");
+        let mut watcher = ShaderWatcher::new();
+        watcher.watch(ShaderSource::spir_v(&path));
+        assert!(watcher.poll().is_empty());
+
+        write(&path, &module(1), 1000);
+        assert_eq!(&watcher.poll()[..], &[0]);
+
+        // Removed while an editor replaces it:
+        fs::remove_file(&path).unwrap();
+        assert!(watcher.poll().is_empty());
+        write(&path, &module(2), 2000);
+        assert_eq!(&watcher.poll()[..], &[0]);
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn failed_rebuilds_keep_the_current_pipeline() {
+        let dir = temp_dir("failed");
+        let path = dir.join("shader.spv");
+        write(&path, &module(1), 1000);
+        let mut watcher = ShaderWatcher::new();
+        watcher.watch(ShaderSource::spir_v(&path));
+        let load = |watcher: &ShaderWatcher| watcher.source(0).load();
+        let pipeline = load(&watcher).unwrap();
+        let mut pending = None;
+
+        assert!(match reload(&mut watcher, &mut pending, load) {
+            ReloadStatus::Unchanged => true,
+            _ => false,
+        });
+
+        // A partially written module:
+        write(&path, &module(2)[..6], 2000);
+        assert!(match reload(&mut watcher, &mut pending, load) {
+            ReloadStatus::Failed(_) => true,
+            _ => false,
+        });
+        assert_eq!(pending, None);
+        assert_eq!(pipeline[3], 1);
+
+        write(&path, &module(2), 3000);
+        assert!(match reload(&mut watcher, &mut pending, load) {
+            ReloadStatus::Rebuilt => true,
+            _ => false,
+        });
+        assert_eq!(pending.map(|words| words[3]), Some(2));
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -1,7 +1,39 @@
 use std::sync::Arc;
 use std::marker::PhantomData;
+use smallvec::SmallVec;
 use vks;
-use ::{VdResult, Device, DeviceMemory, Handle};
+use ::{VdResult, Device, DeviceMemory, Handle, MemoryTypeSelector, MemoryRequirements,
+    ImageCreateInfo, ImageCreateFlags, ImageType, Format, Extent3d, SampleCountFlags, ImageTiling,
+    ImageUsageFlags, SharingMode, Queue, MemoryPropertyFlags, ImageLayout, AccessFlags,
+    PipelineStageFlags, DependencyFlags, ImageMemoryBarrier, ImageSubresourceRange,
+    ImageSubresourceLayers, BufferImageCopy, Offset3d, UploadToken, CommandBuffer, Filter,
+    GeneratedMipmaps, ImageSubresource, ImageAspectFlags, DeviceSize, ErrorKind,
+    QUEUE_FAMILY_IGNORED};
+use alloc::{Allocator, Allocation};
+use upload::{self, UploadBarrier};
+use mipmap;
+use readback;
+
+
+/// Checks that `len` bytes of tightly packed pixels fill the first mip level
+/// of every array layer of an image created with `create_info` and returns
+/// the single aspect to copy them to.
+fn validate_pixels(create_info: &ImageCreateInfo, len: usize) -> VdResult<ImageAspectFlags> {
+    let format = create_info.format();
+    let aspect_mask = format.aspects();
+    if aspect_mask.bits().count_ones() != 1 {
+        return Err(ErrorKind::InvalidUpload {
+            reason: "the image format must have exactly one aspect" }.into());
+    }
+    let extent = create_info.extent();
+    let expected = format.data_size(extent.width(), extent.height(), extent.depth())
+        .checked_mul(create_info.array_layers() as DeviceSize);
+    if expected != Some(len as DeviceSize) {
+        return Err(ErrorKind::InvalidUpload {
+            reason: "the pixel data size does not match the image extent and format" }.into());
+    }
+    Ok(aspect_mask)
+}
 
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -32,6 +64,58 @@ struct Inner {
     memory_requirements: ::MemoryRequirements,
     device: Device,
     is_swapchain_image: bool,
+    allocation: Option<Allocation>,
+    tracking_id: Option<u64>,
+    flags: ImageCreateFlags,
+    image_type: ImageType,
+    format: Format,
+    extent: Extent3d,
+    mip_levels: u32,
+    array_layers: u32,
+    samples: SampleCountFlags,
+    tiling: ImageTiling,
+    usage: ImageUsageFlags,
+    sharing_mode: SharingMode,
+    queue_family_indices: SmallVec<[u32; 4]>,
+}
+
+impl Inner {
+    /// Returns a new `Inner` for a newly created, unbound image (or a
+    /// swapchain image).
+    fn new(device: Device, handle: ImageHandle, create_info: &ImageCreateInfo,
+            is_swapchain_image: bool) -> Inner {
+        let memory_requirements = unsafe { device.get_image_memory_requirements(handle) };
+        // Swapchain images are owned by the swapchain and are not tracked:
+        let tracking_id = if is_swapchain_image {
+            None
+        } else {
+            Some(device.memory_tracker().register("Image"))
+        };
+        let queue_family_indices = match create_info.sharing_mode() {
+            SharingMode::Concurrent => create_info.queue_family_indices().iter().cloned().collect(),
+            _ => SmallVec::new(),
+        };
+
+        Inner {
+            handle,
+            memory_requirements: memory_requirements.into(),
+            device,
+            is_swapchain_image,
+            allocation: None,
+            tracking_id,
+            flags: create_info.flags(),
+            image_type: create_info.image_type(),
+            format: create_info.format(),
+            extent: create_info.extent().clone(),
+            mip_levels: create_info.mip_levels(),
+            array_layers: create_info.array_layers(),
+            samples: create_info.samples(),
+            tiling: create_info.tiling(),
+            usage: create_info.usage(),
+            sharing_mode: create_info.sharing_mode(),
+            queue_family_indices,
+        }
+    }
 }
 
 impl Drop for Inner {
@@ -41,6 +125,9 @@ impl Drop for Inner {
                 self.device.destroy_image(self.handle, None);
             }
         }
+        if let Some(id) = self.tracking_id {
+            self.device.memory_tracker().unregister(id);
+        }
     }
 }
 
@@ -64,17 +151,212 @@ impl Image {
         ImageBuilder::new()
     }
 
-    pub(crate) unsafe fn from_handle(device: Device, handle: ImageHandle, is_swapchain_image: bool) -> Image {
-        let memory_requirements = device.get_image_memory_requirements(handle);
-
+    /// Returns a new `Image` wrapping `handle`, which must have been created
+    /// with `create_info`.
+    pub(crate) unsafe fn from_handle(device: Device, handle: ImageHandle,
+            create_info: &ImageCreateInfo, is_swapchain_image: bool) -> Image {
         Image {
-            inner: Arc::new(Inner {
-                handle,
-                memory_requirements: memory_requirements.into(),
-                device,
-                is_swapchain_image,
-            })
+            inner: Arc::new(Inner::new(device, handle, create_info, is_swapchain_image)),
+        }
+    }
+
+    /// Creates a new `Image` as specified by `builder` and binds it to
+    /// memory sub-allocated from `allocator`.
+    ///
+    /// The allocation is kept alive until the image is destroyed.
+    pub fn new_allocated(builder: &ImageBuilder, allocator: &Allocator,
+            memory_type: &MemoryTypeSelector) -> VdResult<Image> {
+        let linear = builder.create_info.tiling() == ImageTiling::Linear;
+        Image::with_allocation(allocator.device().clone(), &builder.create_info,
+            |requirements| allocator.allocate(requirements, memory_type, linear))
+    }
+
+    /// Creates a new device-local `Image` as specified by `builder`, fills
+    /// its first mip level (of every array layer) with `pixels`, and
+    /// transitions it for use on `queue`.
+    ///
+    /// `pixels` must be tightly packed and hold exactly one mip level for
+    /// every array layer. The copy runs on a dedicated transfer queue if the
+    /// device has one, in which case ownership of an exclusive image is
+    /// transferred to `queue`'s family. The image may be used on `queue` once
+    /// the returned `UploadToken` completes.
+    ///
+    /// An image with a single mip level is left in
+    /// `ImageLayout::ShaderReadOnlyOptimal` if its usage includes `SAMPLED` or
+    /// `INPUT_ATTACHMENT` and `ImageLayout::General` otherwise. An image with
+    /// several mip levels is left entirely in `ImageLayout::TransferDstOptimal`,
+    /// ready for `::generate_mipmaps`. `ImageUsageFlags::TRANSFER_DST` is added
+    /// to its usage.
+    ///
+    /// Returns an `InvalidUpload` error if the length of `pixels` does not
+    /// match the image or its format has more than one aspect (combined
+    /// depth/stencil formats).
+    pub fn from_pixels(device: &Device, builder: &ImageBuilder, pixels: &[u8], queue: &Queue)
+            -> VdResult<(Image, UploadToken)> {
+        let aspect_mask = validate_pixels(&builder.create_info, pixels.len())?;
+        let staging_buffer = upload::staging_buffer(device, pixels)?;
+
+        let mut create_info = builder.create_info.clone();
+        let usage = create_info.usage() | ImageUsageFlags::TRANSFER_DST;
+        create_info.set_usage(usage);
+        create_info.set_initial_layout(ImageLayout::Undefined);
+        let image = Image::with_allocation(device.clone(), &create_info, |requirements| {
+            let memory_type_index = MemoryTypeSelector::new()
+                .required(MemoryPropertyFlags::DEVICE_LOCAL)
+                .select(device, requirements.memory_type_bits())?;
+            Allocation::dedicated(device.clone(), requirements, memory_type_index)
+        })?;
+
+        let final_layout = if image.mip_levels() > 1 {
+            ImageLayout::TransferDstOptimal
+        } else if usage.intersects(ImageUsageFlags::SAMPLED | ImageUsageFlags::INPUT_ATTACHMENT) {
+            ImageLayout::ShaderReadOnlyOptimal
+        } else {
+            ImageLayout::General
+        };
+        let subresource_range = ImageSubresourceRange::builder()
+            .aspect_mask(aspect_mask)
+            .base_mip_level(0)
+            .level_count(image.mip_levels())
+            .base_array_layer(0)
+            .layer_count(image.array_layers())
+            .build();
+        let exclusive = image.sharing_mode() == SharingMode::Exclusive;
+
+        let token = upload::upload(device, queue, staging_buffer, exclusive,
+            |command_buffer, staging_buffer| {
+                let to_transfer_dst = ImageMemoryBarrier::builder()
+                    .src_access_mask(AccessFlags::empty())
+                    .dst_access_mask(AccessFlags::TRANSFER_WRITE)
+                    .old_layout(ImageLayout::Undefined)
+                    .new_layout(ImageLayout::TransferDstOptimal)
+                    .src_queue_family_index(QUEUE_FAMILY_IGNORED)
+                    .dst_queue_family_index(QUEUE_FAMILY_IGNORED)
+                    .image(&image)
+                    .subresource_range(subresource_range.clone())
+                    .build();
+                command_buffer.pipeline_barrier(PipelineStageFlags::TOP_OF_PIPE,
+                    PipelineStageFlags::TRANSFER, DependencyFlags::empty(), &[], &[],
+                    &[to_transfer_dst]);
+
+                let region = BufferImageCopy::builder()
+                    .buffer_offset(0)
+                    .buffer_row_length(0)
+                    .buffer_image_height(0)
+                    .image_subresource(ImageSubresourceLayers::builder()
+                        .aspect_mask(aspect_mask)
+                        .mip_level(0)
+                        .base_array_layer(0)
+                        .layer_count(image.array_layers())
+                        .build())
+                    .image_offset(Offset3d::builder().x(0).y(0).z(0).build())
+                    .image_extent(image.extent().clone())
+                    .build();
+                unsafe {
+                    command_buffer.copy_buffer_to_image(staging_buffer, &image,
+                        ImageLayout::TransferDstOptimal, &[region]);
+                }
+            },
+            |command_buffer, barrier: UploadBarrier| {
+                let (src_family, dst_family) = barrier.queue_family_indices();
+                let image_barrier = ImageMemoryBarrier::builder()
+                    .src_access_mask(barrier.src_access_mask())
+                    .dst_access_mask(barrier.dst_access_mask())
+                    .old_layout(ImageLayout::TransferDstOptimal)
+                    .new_layout(final_layout)
+                    .src_queue_family_index(src_family)
+                    .dst_queue_family_index(dst_family)
+                    .image(&image)
+                    .subresource_range(subresource_range.clone())
+                    .build();
+                command_buffer.pipeline_barrier(barrier.src_stage_mask(),
+                    barrier.dst_stage_mask(), DependencyFlags::empty(), &[], &[],
+                    &[image_barrier]);
+            })?;
+
+        Ok((image, token))
+    }
+
+    /// Records commands into `command_buffer` which build every mip level of
+    /// this image from its first.
+    ///
+    /// All mip levels must be in `ImageLayout::TransferDstOptimal` when the
+    /// commands execute, with the first level already filled. Every level is
+    /// left in `ImageLayout::ShaderReadOnlyOptimal`.
+    ///
+    /// Levels are built by successive blits when the image's format supports
+    /// blitting with `filter` (and its usage includes `TRANSFER_SRC`).
+    /// Otherwise a compute shader box filter is used, which requires a
+    /// single-sampled 2D image of a floating-point or normalized color format
+    /// with `SAMPLED` and `STORAGE` usage, and the
+    /// `shaderStorageImageWriteWithoutFormat` feature to have been enabled
+    /// when the device was created (see `DeviceBuilder::enabled_features`).
+    ///
+    /// The returned `GeneratedMipmaps` must be kept alive until
+    /// `command_buffer` has finished executing.
+    pub fn generate_mipmaps(&self, command_buffer: &CommandBuffer, filter: Filter)
+            -> VdResult<GeneratedMipmaps> {
+        mipmap::generate(self, command_buffer, filter)
+    }
+
+    /// Reads `subresource` of this image back to host memory, blocking until
+    /// the data is available.
+    ///
+    /// `layout` is the layout the subresource is in when the read is
+    /// submitted to `queue`. It is transitioned to
+    /// `ImageLayout::TransferSrcOptimal` for the copy and back to `layout`
+    /// afterwards (or left in `TransferSrcOptimal` if `layout` is `Undefined`
+    /// or `Preinitialized`). Linear, host-visible images in the `General` or
+    /// `Preinitialized` layout are read directly from their memory once a
+    /// barrier making prior writes visible to the host has executed on
+    /// `queue`.
+    ///
+    /// The returned texel data is tightly packed, without row or slice
+    /// padding. `subresource` must name exactly one aspect; depth data is
+    /// returned as it is copied to a buffer (four bytes per texel for 24-bit
+    /// depth). Multisampled images can not be read back and must be resolved
+    /// first.
+    pub fn read_to_vec(&self, queue: &Queue, layout: ImageLayout, subresource: &ImageSubresource)
+            -> VdResult<Vec<u8>> {
+        readback::read_to_vec(self, queue, layout, subresource)
+    }
+
+    /// Creates a new `Image` and binds it to the allocation returned by
+    /// `allocate`, which is passed the image's memory requirements.
+    pub(crate) fn with_allocation<F>(device: Device, create_info: &ImageCreateInfo, allocate: F)
+            -> VdResult<Image>
+            where F: FnOnce(&MemoryRequirements) -> VdResult<Allocation> {
+        let handle = unsafe { device.create_image(create_info, None)? };
+
+        // Dropping `inner` on error destroys the image:
+        let mut inner = Inner::new(device, handle, create_info, false);
+        let allocation = allocate(&inner.memory_requirements)?;
+        unsafe {
+            inner.device.bind_image_memory(handle, allocation.memory().handle(),
+                allocation.offset())?;
         }
+        inner.allocation = Some(allocation);
+
+        Ok(Image { inner: Arc::new(inner) })
+    }
+
+    /// Returns a create info matching the one this image was created with
+    /// (with an undefined initial layout).
+    pub(crate) fn create_info<'s>(&'s self) -> ImageCreateInfo<'s> {
+        let mut create_info = ImageCreateInfo::default();
+        create_info.set_flags(self.inner.flags);
+        create_info.set_image_type(self.inner.image_type);
+        create_info.set_format(self.inner.format);
+        create_info.set_extent(self.inner.extent.clone());
+        create_info.set_mip_levels(self.inner.mip_levels);
+        create_info.set_array_layers(self.inner.array_layers);
+        create_info.set_samples(self.inner.samples);
+        create_info.set_tiling(self.inner.tiling);
+        create_info.set_usage(self.inner.usage);
+        create_info.set_sharing_mode(self.inner.sharing_mode);
+        create_info.set_queue_family_indices(&self.inner.queue_family_indices[..]);
+        create_info.set_initial_layout(::ImageLayout::Undefined);
+        create_info
     }
 
     /// Returns this object's handle.
@@ -87,6 +369,56 @@ impl Image {
         &self.inner.memory_requirements
     }
 
+    /// Returns the flags this image was created with.
+    pub fn flags(&self) -> ImageCreateFlags {
+        self.inner.flags
+    }
+
+    /// Returns the type (dimensionality) of this image.
+    pub fn image_type(&self) -> ImageType {
+        self.inner.image_type
+    }
+
+    /// Returns the format of this image.
+    pub fn format(&self) -> Format {
+        self.inner.format
+    }
+
+    /// Returns the extent of the base mip level of this image.
+    pub fn extent(&self) -> &Extent3d {
+        &self.inner.extent
+    }
+
+    /// Returns the number of mip levels of this image.
+    pub fn mip_levels(&self) -> u32 {
+        self.inner.mip_levels
+    }
+
+    /// Returns the number of array layers of this image.
+    pub fn array_layers(&self) -> u32 {
+        self.inner.array_layers
+    }
+
+    /// Returns the number of samples per texel of this image.
+    pub fn samples(&self) -> SampleCountFlags {
+        self.inner.samples
+    }
+
+    /// Returns the tiling arrangement of this image.
+    pub fn tiling(&self) -> ImageTiling {
+        self.inner.tiling
+    }
+
+    /// Returns the usage flags this image was created with.
+    pub fn usage(&self) -> ImageUsageFlags {
+        self.inner.usage
+    }
+
+    /// Returns the sharing mode this image was created with.
+    pub fn sharing_mode(&self) -> SharingMode {
+        self.inner.sharing_mode
+    }
+
     /// Binds this image to device memory. `offset` is the start offset of the
     /// region of memory which is to be bound. The number of bytes returned in
     /// the VkMemoryRequirements::size member in memory, starting from
@@ -102,6 +434,19 @@ impl Image {
         self.inner.device.bind_image_memory(self.inner.handle, memory.handle(), offset_bytes)
     }
 
+    /// Sets the name used to identify this image in leak reports.
+    pub fn set_debug_name(&self, name: &str) {
+        if let Some(id) = self.inner.tracking_id {
+            self.inner.device.memory_tracker().set_name(id, name);
+        }
+    }
+
+    /// Returns the allocation this image is bound to if it was created with
+    /// `::new_allocated`.
+    pub fn allocation(&self) -> Option<&Allocation> {
+        self.inner.allocation.as_ref()
+    }
+
     /// Returns a reference to the associated device.
     pub fn device(&self) -> &Device {
         &self.inner.device
@@ -239,7 +584,41 @@ impl<'b> ImageBuilder<'b> {
     pub fn build(&self, device: Device) -> VdResult<Image> {
         unsafe {
             let handle = device.create_image(&self.create_info, None)?;
-            Ok(Image::from_handle(device, handle, false))
+            Ok(Image::from_handle(device, handle, &self.create_info, false))
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_info(format: Format, width: u32, height: u32, array_layers: u32)
+            -> ImageCreateInfo<'static> {
+        let mut create_info = ImageCreateInfo::default();
+        create_info.set_format(format);
+        create_info.set_extent(Extent3d::builder().width(width).height(height).depth(1).build());
+        create_info.set_array_layers(array_layers);
+        create_info
+    }
+
+    #[test]
+    fn validate_pixels_size() {
+        let info = create_info(Format::R8G8B8A8Unorm, 4, 3, 2);
+        assert_eq!(validate_pixels(&info, 4 * 3 * 4 * 2).unwrap(), ImageAspectFlags::COLOR);
+        assert!(validate_pixels(&info, 4 * 3 * 4).is_err());
+        assert!(validate_pixels(&info, 4 * 3 * 4 * 2 + 1).is_err());
+        assert!(validate_pixels(&info, 0).is_err());
+
+        let info = create_info(Format::Bc1RgbUnormBlock, 5, 5, 1);
+        assert!(validate_pixels(&info, 4 * 8).is_ok());
+    }
+
+    #[test]
+    fn validate_pixels_aspects() {
+        let info = create_info(Format::D32Sfloat, 2, 2, 1);
+        assert_eq!(validate_pixels(&info, 16).unwrap(), ImageAspectFlags::DEPTH);
+        let info = create_info(Format::D24UnormS8Uint, 2, 2, 1);
+        assert!(validate_pixels(&info, 16).is_err());
+    }
+}
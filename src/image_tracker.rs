@@ -0,0 +1,560 @@
+use std::cmp;
+use std::ops::Range;
+use ::{VdResult, ErrorKind, CommandBuffer, Image, Buffer, ImageLayout, AccessFlags, PipelineStageFlags,
+    DependencyFlags, ImageMemoryBarrier, ImageSubresourceRange, ImageSubresourceLayers,
+    ImageAspectFlags, ImageCopy, ImageBlit, ImageResolve, BufferImageCopy, Filter,
+    ClearColorValue, ClearDepthStencilValue, QUEUE_FAMILY_IGNORED, REMAINING_MIP_LEVELS,
+    REMAINING_ARRAY_LAYERS};
+
+
+/// Returns all access flags which denote writes.
+fn write_access_mask() -> AccessFlags {
+    AccessFlags::SHADER_WRITE | AccessFlags::COLOR_ATTACHMENT_WRITE |
+        AccessFlags::DEPTH_STENCIL_ATTACHMENT_WRITE | AccessFlags::TRANSFER_WRITE |
+        AccessFlags::HOST_WRITE | AccessFlags::MEMORY_WRITE
+}
+
+/// Returns true if `access` contains any write access.
+fn is_write(access: AccessFlags) -> bool {
+    access.intersects(write_access_mask())
+}
+
+
+/// The last known layout and accesses of a single subresource.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct SubresourceState {
+    layout: ImageLayout,
+    // The stages and accesses of the last write or layout transition, which
+    // later accesses must wait on:
+    write_stage_mask: PipelineStageFlags,
+    write_access_mask: AccessFlags,
+    // The stages and accesses the last write has been made visible to:
+    visible_stage_mask: PipelineStageFlags,
+    visible_access_mask: AccessFlags,
+    // The stages of reads since the last write, which later writes and
+    // layout transitions must wait on:
+    read_stage_mask: PipelineStageFlags,
+    // The barrier batch which last touched this subresource:
+    batch: u64,
+}
+
+impl SubresourceState {
+    fn new(layout: ImageLayout) -> SubresourceState {
+        SubresourceState {
+            layout,
+            write_stage_mask: PipelineStageFlags::empty(),
+            write_access_mask: AccessFlags::empty(),
+            visible_stage_mask: PipelineStageFlags::empty(),
+            visible_access_mask: AccessFlags::empty(),
+            read_stage_mask: PipelineStageFlags::empty(),
+            batch: 0,
+        }
+    }
+
+    /// Returns the state after an access in `layout` with `access_mask` at
+    /// `stage_mask` by the command of barrier batch `batch`, along with the
+    /// source stage and access masks of the barrier required before it, if
+    /// any.
+    fn access(&self, layout: ImageLayout, stage_mask: PipelineStageFlags,
+            access_mask: AccessFlags, batch: u64)
+            -> (SubresourceState, Option<(PipelineStageFlags, AccessFlags)>) {
+        let mut new = SubresourceState { layout, batch, .. *self };
+        if self.batch == batch {
+            // Already accessed by the command being recorded (e.g. several
+            // regions of one copy):
+            if is_write(access_mask) {
+                new.write_stage_mask |= stage_mask;
+                new.write_access_mask |= access_mask & write_access_mask();
+            } else {
+                new.read_stage_mask |= stage_mask;
+            }
+            return (new, None);
+        }
+
+        if self.layout != layout || is_write(access_mask) {
+            // Writes and layout transitions wait on the last write and all
+            // reads since. Only writes need to be made available:
+            let src = (self.write_stage_mask | self.read_stage_mask, self.write_access_mask);
+            new = if is_write(access_mask) {
+                SubresourceState {
+                    write_stage_mask: stage_mask,
+                    write_access_mask: access_mask & write_access_mask(),
+                    .. SubresourceState::new(layout)
+                }
+            } else {
+                // The layout transition is only visible to this read:
+                SubresourceState {
+                    write_stage_mask: stage_mask,
+                    visible_stage_mask: stage_mask,
+                    visible_access_mask: access_mask,
+                    read_stage_mask: stage_mask,
+                    .. SubresourceState::new(layout)
+                }
+            };
+            new.batch = batch;
+            return (new, Some(src));
+        }
+
+        // Reads in the same layout need a barrier only if the last write has
+        // not yet been made visible to them:
+        new.read_stage_mask |= stage_mask;
+        if self.write_stage_mask.is_empty() || access_mask.is_empty() ||
+                (self.visible_stage_mask.contains(stage_mask) &&
+                self.visible_access_mask.contains(access_mask)) {
+            return (new, None);
+        }
+        new.visible_stage_mask |= stage_mask;
+        new.visible_access_mask |= access_mask;
+        (new, Some((self.write_stage_mask, self.write_access_mask)))
+    }
+}
+
+
+#[derive(Debug)]
+struct TrackedImage {
+    image: Image,
+    aspect_mask: ImageAspectFlags,
+    array_layers: u32,
+    // Indexed by `mip_level * array_layers + array_layer`:
+    states: Vec<SubresourceState>,
+    final_layout: Option<ImageLayout>,
+}
+
+impl TrackedImage {
+    fn new(image: &Image, layout: ImageLayout) -> TrackedImage {
+        let count = (image.mip_levels() * image.array_layers()) as usize;
+        TrackedImage {
+            image: image.clone(),
+            aspect_mask: image.format().aspects(),
+            array_layers: image.array_layers(),
+            states: vec![SubresourceState::new(layout); count],
+            final_layout: None,
+        }
+    }
+
+    /// Resolves `REMAINING_*` counts of a subresource range.
+    fn ranges(&self, range: &ImageSubresourceRange) -> (Range<u32>, Range<u32>) {
+        let mip_levels = self.image.mip_levels();
+        let level_end = if range.level_count() == REMAINING_MIP_LEVELS {
+            mip_levels
+        } else {
+            cmp::min(range.base_mip_level() + range.level_count(), mip_levels)
+        };
+        let layer_end = if range.layer_count() == REMAINING_ARRAY_LAYERS {
+            self.array_layers
+        } else {
+            cmp::min(range.base_array_layer() + range.layer_count(), self.array_layers)
+        };
+        (range.base_mip_level()..level_end, range.base_array_layer()..layer_end)
+    }
+
+    /// Returns the index of a subresource in `states`, if it exists.
+    fn state_idx(&self, mip_level: u32, array_layer: u32) -> Option<usize> {
+        if mip_level < self.image.mip_levels() && array_layer < self.array_layers {
+            Some(mip_level as usize * self.array_layers as usize + array_layer as usize)
+        } else {
+            None
+        }
+    }
+
+    fn state(&self, mip_level: u32, array_layer: u32) -> Option<SubresourceState> {
+        self.state_idx(mip_level, array_layer).map(|idx| self.states[idx])
+    }
+
+    fn set_state(&mut self, mip_level: u32, array_layer: u32, state: SubresourceState) {
+        if let Some(idx) = self.state_idx(mip_level, array_layer) {
+            self.states[idx] = state;
+        }
+    }
+}
+
+
+/// Records commands into a `CommandBuffer` while tracking the layout and
+/// last access of every subresource of the images it touches, inserting
+/// the pipeline barriers required before each command automatically.
+///
+/// The layout every image is in when the recorded commands begin executing
+/// must be declared with `::declare` before the image is used. Declare
+/// `ImageLayout::Undefined` to discard existing contents. Using an undeclared
+/// image returns an `ErrorKind::UndeclaredImage` error without recording
+/// anything. Layouts to be left in at the end of recording (e.g.
+/// `ImageLayout::PresentSrcKhr`) are declared with `::final_layout` and
+/// applied by `::finish`.
+///
+/// Barriers only cover work recorded through this tracker. Work submitted
+/// earlier must be synchronized with semaphores or fences as usual.
+///
+#[derive(Debug)]
+pub struct ImageTracker<'c> {
+    command_buffer: &'c CommandBuffer,
+    images: Vec<TrackedImage>,
+    pending_barriers: Vec<ImageMemoryBarrier<'static>>,
+    pending_src_stage_mask: PipelineStageFlags,
+    batch: u64,
+}
+
+impl<'c> ImageTracker<'c> {
+    /// Returns a new tracker recording into `command_buffer`, which must be
+    /// in the recording state.
+    pub fn new(command_buffer: &'c CommandBuffer) -> ImageTracker<'c> {
+        ImageTracker {
+            command_buffer,
+            images: Vec::new(),
+            pending_barriers: Vec::new(),
+            pending_src_stage_mask: PipelineStageFlags::empty(),
+            batch: 1,
+        }
+    }
+
+    /// Returns the index of a declared image.
+    fn image_idx(&self, image: &Image) -> VdResult<usize> {
+        self.images.iter().position(|t| t.image.handle() == image.handle())
+            .ok_or(ErrorKind::UndeclaredImage.into())
+    }
+
+    /// Declares that every subresource of `image` is in `layout` when the
+    /// commands recorded through this tracker begin executing.
+    ///
+    /// Must be called before `image` is first used. Declaring an image again
+    /// replaces the state of all of its subresources.
+    pub fn declare(&mut self, image: &Image, layout: ImageLayout) {
+        match self.image_idx(image) {
+            Ok(idx) => {
+                for state in self.images[idx].states.iter_mut() {
+                    *state = SubresourceState::new(layout);
+                }
+            },
+            Err(_) => self.images.push(TrackedImage::new(image, layout)),
+        }
+    }
+
+    /// Declares the layout `image` is to be transitioned to by `::finish`.
+    pub fn final_layout(&mut self, image: &Image, layout: ImageLayout) -> VdResult<()> {
+        let idx = self.image_idx(image)?;
+        self.images[idx].final_layout = Some(layout);
+        Ok(())
+    }
+
+    /// Returns the current layout of a subresource of `image`, if `image` is
+    /// tracked and has the subresource.
+    pub fn layout(&self, image: &Image, mip_level: u32, array_layer: u32) -> Option<ImageLayout> {
+        self.images.iter().find(|t| t.image.handle() == image.handle())
+            .and_then(|t| t.state(mip_level, array_layer))
+            .map(|state| state.layout)
+    }
+
+    /// Queues the barriers needed before `range` of the image at `idx` is
+    /// accessed in `layout` with `access_mask` at `stage_mask`.
+    fn access(&mut self, idx: usize, range: &ImageSubresourceRange, layout: ImageLayout,
+            stage_mask: PipelineStageFlags, access_mask: AccessFlags) {
+        let image = self.images[idx].image.clone();
+        let (mip_levels, array_layers) = self.images[idx].ranges(range);
+        let aspect_mask = self.images[idx].aspect_mask;
+
+        for mip_level in mip_levels {
+            let mut layer = array_layers.start;
+            while layer < array_layers.end {
+                // Handle runs of layers in the same state with one barrier:
+                let old = match self.images[idx].state(mip_level, layer) {
+                    Some(state) => state,
+                    None => break,
+                };
+                let mut end = layer + 1;
+                while end < array_layers.end &&
+                        self.images[idx].state(mip_level, end) == Some(old) {
+                    end += 1;
+                }
+
+                let (new, barrier) = old.access(layout, stage_mask, access_mask, self.batch);
+                if let Some((src_stage_mask, src_access_mask)) = barrier {
+                    self.pending_barriers.push(ImageMemoryBarrier::builder()
+                        .src_access_mask(src_access_mask)
+                        .dst_access_mask(access_mask)
+                        .old_layout(old.layout)
+                        .new_layout(layout)
+                        .src_queue_family_index(QUEUE_FAMILY_IGNORED)
+                        .dst_queue_family_index(QUEUE_FAMILY_IGNORED)
+                        .image(&image)
+                        .subresource_range(ImageSubresourceRange::builder()
+                            .aspect_mask(aspect_mask)
+                            .base_mip_level(mip_level)
+                            .level_count(1)
+                            .base_array_layer(layer)
+                            .layer_count(end - layer)
+                            .build())
+                        .build());
+                    self.pending_src_stage_mask |= src_stage_mask;
+                }
+
+                for l in layer..end {
+                    self.images[idx].set_state(mip_level, l, new);
+                }
+                layer = end;
+            }
+        }
+    }
+
+    /// Records all queued barriers, completing them before `dst_stage_mask`.
+    fn flush(&mut self, dst_stage_mask: PipelineStageFlags) {
+        self.batch += 1;
+        if self.pending_barriers.is_empty() { return; }
+        let src_stage_mask = if self.pending_src_stage_mask.is_empty() {
+            PipelineStageFlags::TOP_OF_PIPE
+        } else {
+            self.pending_src_stage_mask
+        };
+        self.command_buffer.pipeline_barrier(src_stage_mask, dst_stage_mask,
+            DependencyFlags::empty(), &[], &[], &self.pending_barriers);
+        self.pending_barriers.clear();
+        self.pending_src_stage_mask = PipelineStageFlags::empty();
+    }
+
+    /// Converts subresource layers to a subresource range.
+    fn layers_range(layers: &ImageSubresourceLayers) -> ImageSubresourceRange {
+        ImageSubresourceRange::builder()
+            .aspect_mask(layers.aspect_mask())
+            .base_mip_level(layers.mip_level())
+            .level_count(1)
+            .base_array_layer(layers.base_array_layer())
+            .layer_count(layers.layer_count())
+            .build()
+    }
+
+    /// Transitions `range` of `image` to `layout` for access with
+    /// `access_mask` at `stage_mask` (e.g. before use in a render pass or
+    /// shader), recording a barrier only if one is required.
+    pub fn transition_range(&mut self, image: &Image, range: &ImageSubresourceRange,
+            layout: ImageLayout, stage_mask: PipelineStageFlags, access_mask: AccessFlags)
+            -> VdResult<()> {
+        let idx = self.image_idx(image)?;
+        self.access(idx, range, layout, stage_mask, access_mask);
+        self.flush(stage_mask);
+        Ok(())
+    }
+
+    /// Transitions every subresource of `image`. See `::transition_range`.
+    pub fn transition(&mut self, image: &Image, layout: ImageLayout,
+            stage_mask: PipelineStageFlags, access_mask: AccessFlags) -> VdResult<()> {
+        let range = ImageSubresourceRange::builder()
+            .base_mip_level(0)
+            .level_count(REMAINING_MIP_LEVELS)
+            .base_array_layer(0)
+            .layer_count(REMAINING_ARRAY_LAYERS)
+            .build();
+        self.transition_range(image, &range, layout, stage_mask, access_mask)
+    }
+
+    /// Records a copy between images. See `CommandBuffer::copy_image`.
+    pub unsafe fn copy_image(&mut self, src_image: &Image, dst_image: &Image,
+            regions: &[ImageCopy]) -> VdResult<()> {
+        let (src, dst) = (self.image_idx(src_image)?, self.image_idx(dst_image)?);
+        for region in regions {
+            self.access(src, &ImageTracker::layers_range(region.src_subresource()),
+                ImageLayout::TransferSrcOptimal, PipelineStageFlags::TRANSFER,
+                AccessFlags::TRANSFER_READ);
+            self.access(dst, &ImageTracker::layers_range(region.dst_subresource()),
+                ImageLayout::TransferDstOptimal, PipelineStageFlags::TRANSFER,
+                AccessFlags::TRANSFER_WRITE);
+        }
+        self.flush(PipelineStageFlags::TRANSFER);
+        self.command_buffer.copy_image(src_image, ImageLayout::TransferSrcOptimal, dst_image,
+            ImageLayout::TransferDstOptimal, regions);
+        Ok(())
+    }
+
+    /// Records a blit between images. See `CommandBuffer::blit_image`.
+    pub unsafe fn blit_image(&mut self, src_image: &Image, dst_image: &Image,
+            regions: &[ImageBlit], filter: Filter) -> VdResult<()> {
+        let (src, dst) = (self.image_idx(src_image)?, self.image_idx(dst_image)?);
+        for region in regions {
+            self.access(src, &ImageTracker::layers_range(region.src_subresource()),
+                ImageLayout::TransferSrcOptimal, PipelineStageFlags::TRANSFER,
+                AccessFlags::TRANSFER_READ);
+            self.access(dst, &ImageTracker::layers_range(region.dst_subresource()),
+                ImageLayout::TransferDstOptimal, PipelineStageFlags::TRANSFER,
+                AccessFlags::TRANSFER_WRITE);
+        }
+        self.flush(PipelineStageFlags::TRANSFER);
+        self.command_buffer.blit_image(src_image, ImageLayout::TransferSrcOptimal, dst_image,
+            ImageLayout::TransferDstOptimal, regions, filter);
+        Ok(())
+    }
+
+    /// Records a resolve of a multisample image. See
+    /// `CommandBuffer::resolve_image`.
+    pub unsafe fn resolve_image(&mut self, src_image: &Image, dst_image: &Image,
+            regions: &[ImageResolve]) -> VdResult<()> {
+        let (src, dst) = (self.image_idx(src_image)?, self.image_idx(dst_image)?);
+        for region in regions {
+            self.access(src, &ImageTracker::layers_range(region.src_subresource()),
+                ImageLayout::TransferSrcOptimal, PipelineStageFlags::TRANSFER,
+                AccessFlags::TRANSFER_READ);
+            self.access(dst, &ImageTracker::layers_range(region.dst_subresource()),
+                ImageLayout::TransferDstOptimal, PipelineStageFlags::TRANSFER,
+                AccessFlags::TRANSFER_WRITE);
+        }
+        self.flush(PipelineStageFlags::TRANSFER);
+        self.command_buffer.resolve_image(src_image, ImageLayout::TransferSrcOptimal, dst_image,
+            ImageLayout::TransferDstOptimal, regions);
+        Ok(())
+    }
+
+    /// Records a copy from a buffer to an image. See
+    /// `CommandBuffer::copy_buffer_to_image`.
+    pub unsafe fn copy_buffer_to_image(&mut self, src_buffer: &Buffer, dst_image: &Image,
+            regions: &[BufferImageCopy]) -> VdResult<()> {
+        let dst = self.image_idx(dst_image)?;
+        for region in regions {
+            self.access(dst, &ImageTracker::layers_range(region.image_subresource()),
+                ImageLayout::TransferDstOptimal, PipelineStageFlags::TRANSFER,
+                AccessFlags::TRANSFER_WRITE);
+        }
+        self.flush(PipelineStageFlags::TRANSFER);
+        self.command_buffer.copy_buffer_to_image(src_buffer, dst_image,
+            ImageLayout::TransferDstOptimal, regions);
+        Ok(())
+    }
+
+    /// Records a copy from an image to a buffer. See
+    /// `CommandBuffer::copy_image_to_buffer`.
+    pub unsafe fn copy_image_to_buffer(&mut self, src_image: &Image, dst_buffer: &Buffer,
+            regions: &[BufferImageCopy]) -> VdResult<()> {
+        let src = self.image_idx(src_image)?;
+        for region in regions {
+            self.access(src, &ImageTracker::layers_range(region.image_subresource()),
+                ImageLayout::TransferSrcOptimal, PipelineStageFlags::TRANSFER,
+                AccessFlags::TRANSFER_READ);
+        }
+        self.flush(PipelineStageFlags::TRANSFER);
+        self.command_buffer.copy_image_to_buffer(src_image, ImageLayout::TransferSrcOptimal,
+            dst_buffer, regions);
+        Ok(())
+    }
+
+    /// Records a clear of color image ranges. See
+    /// `CommandBuffer::clear_color_image`.
+    pub unsafe fn clear_color_image(&mut self, image: &Image, color: &ClearColorValue,
+            ranges: &[ImageSubresourceRange]) -> VdResult<()> {
+        let idx = self.image_idx(image)?;
+        for range in ranges {
+            self.access(idx, range, ImageLayout::TransferDstOptimal,
+                PipelineStageFlags::TRANSFER, AccessFlags::TRANSFER_WRITE);
+        }
+        self.flush(PipelineStageFlags::TRANSFER);
+        self.command_buffer.clear_color_image(image, ImageLayout::TransferDstOptimal, color,
+            ranges);
+        Ok(())
+    }
+
+    /// Records a clear of depth/stencil image ranges. See
+    /// `CommandBuffer::clear_depth_stencil_image`.
+    pub unsafe fn clear_depth_stencil_image(&mut self, image: &Image,
+            depth_stencil: &ClearDepthStencilValue, ranges: &[ImageSubresourceRange])
+            -> VdResult<()> {
+        let idx = self.image_idx(image)?;
+        for range in ranges {
+            self.access(idx, range, ImageLayout::TransferDstOptimal,
+                PipelineStageFlags::TRANSFER, AccessFlags::TRANSFER_WRITE);
+        }
+        self.flush(PipelineStageFlags::TRANSFER);
+        self.command_buffer.clear_depth_stencil_image(image, ImageLayout::TransferDstOptimal,
+            depth_stencil, ranges);
+        Ok(())
+    }
+
+    /// Transitions every image with a declared final layout to that layout
+    /// and returns the command buffer.
+    pub fn finish(mut self) -> &'c CommandBuffer {
+        let finals: Vec<(usize, ImageLayout)> = self.images.iter().enumerate()
+            .filter_map(|(idx, t)| t.final_layout.map(|layout| (idx, layout)))
+            .collect();
+        let range = ImageSubresourceRange::builder()
+            .base_mip_level(0)
+            .level_count(REMAINING_MIP_LEVELS)
+            .base_array_layer(0)
+            .layer_count(REMAINING_ARRAY_LAYERS)
+            .build();
+        for (idx, layout) in finals {
+            self.access(idx, &range, layout, PipelineStageFlags::BOTTOM_OF_PIPE,
+                AccessFlags::empty());
+        }
+        self.flush(PipelineStageFlags::BOTTOM_OF_PIPE);
+        self.command_buffer
+    }
+
+    /// Returns the command buffer being recorded into.
+    pub fn command_buffer(&self) -> &'c CommandBuffer {
+        self.command_buffer
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_are_made_visible_to_each_reading_stage() {
+        let state = SubresourceState::new(ImageLayout::General);
+        let (state, barrier) = state.access(ImageLayout::General,
+            PipelineStageFlags::COMPUTE_SHADER, AccessFlags::SHADER_WRITE, 1);
+        assert_eq!(barrier, Some((PipelineStageFlags::empty(), AccessFlags::empty())));
+
+        let (state, barrier) = state.access(ImageLayout::General, PipelineStageFlags::TRANSFER,
+            AccessFlags::TRANSFER_READ, 2);
+        assert_eq!(barrier, Some((PipelineStageFlags::COMPUTE_SHADER, AccessFlags::SHADER_WRITE)));
+
+        // The write was only made visible to transfers:
+        let (state, barrier) = state.access(ImageLayout::General,
+            PipelineStageFlags::FRAGMENT_SHADER, AccessFlags::SHADER_READ, 3);
+        assert_eq!(barrier, Some((PipelineStageFlags::COMPUTE_SHADER, AccessFlags::SHADER_WRITE)));
+
+        let (state, barrier) = state.access(ImageLayout::General,
+            PipelineStageFlags::FRAGMENT_SHADER, AccessFlags::SHADER_READ, 4);
+        assert_eq!(barrier, None);
+        let (state, barrier) = state.access(ImageLayout::General, PipelineStageFlags::TRANSFER,
+            AccessFlags::TRANSFER_READ, 5);
+        assert_eq!(barrier, None);
+
+        // A later write waits on the write and every read since:
+        let (_, barrier) = state.access(ImageLayout::General, PipelineStageFlags::TRANSFER,
+            AccessFlags::TRANSFER_WRITE, 6);
+        assert_eq!(barrier, Some((PipelineStageFlags::COMPUTE_SHADER |
+            PipelineStageFlags::TRANSFER | PipelineStageFlags::FRAGMENT_SHADER,
+            AccessFlags::SHADER_WRITE)));
+    }
+
+    #[test]
+    fn layout_transitions() {
+        let state = SubresourceState::new(ImageLayout::Undefined);
+        let (state, barrier) = state.access(ImageLayout::ShaderReadOnlyOptimal,
+            PipelineStageFlags::FRAGMENT_SHADER, AccessFlags::SHADER_READ, 1);
+        assert_eq!(barrier, Some((PipelineStageFlags::empty(), AccessFlags::empty())));
+        assert_eq!(state.layout, ImageLayout::ShaderReadOnlyOptimal);
+
+        // The transition must be made visible to reads at other stages:
+        let (state, barrier) = state.access(ImageLayout::ShaderReadOnlyOptimal,
+            PipelineStageFlags::FRAGMENT_SHADER, AccessFlags::SHADER_READ, 2);
+        assert_eq!(barrier, None);
+        let (state, barrier) = state.access(ImageLayout::ShaderReadOnlyOptimal,
+            PipelineStageFlags::VERTEX_SHADER, AccessFlags::SHADER_READ, 3);
+        assert_eq!(barrier, Some((PipelineStageFlags::FRAGMENT_SHADER, AccessFlags::empty())));
+
+        let (_, barrier) = state.access(ImageLayout::TransferDstOptimal,
+            PipelineStageFlags::TRANSFER, AccessFlags::TRANSFER_WRITE, 4);
+        assert_eq!(barrier, Some((PipelineStageFlags::FRAGMENT_SHADER |
+            PipelineStageFlags::VERTEX_SHADER, AccessFlags::empty())));
+    }
+
+    #[test]
+    fn accesses_by_one_command_share_a_barrier() {
+        let state = SubresourceState::new(ImageLayout::TransferDstOptimal);
+        let (state, barrier) = state.access(ImageLayout::TransferDstOptimal,
+            PipelineStageFlags::TRANSFER, AccessFlags::TRANSFER_WRITE, 1);
+        assert!(barrier.is_some());
+        let (state, barrier) = state.access(ImageLayout::TransferDstOptimal,
+            PipelineStageFlags::TRANSFER, AccessFlags::TRANSFER_WRITE, 1);
+        assert_eq!(barrier, None);
+        assert_eq!(state.write_access_mask, AccessFlags::TRANSFER_WRITE);
+    }
+}
@@ -28,6 +28,8 @@ mod pipeline_layout;
 mod shader_module;
 mod render_pass;
 mod graphics_pipeline;
+mod compute_pipeline;
+mod pipeline_cache;
 mod framebuffer;
 mod surface;
 mod queue;
@@ -35,11 +37,25 @@ mod command_pool;
 mod command_buffer;
 mod semaphore;
 mod buffer;
+mod buffer_view;
+mod query_pool;
 mod image;
 mod sampler;
 mod device_memory;
+mod memory_type_selector;
+mod memory_stats;
+mod staging_belt;
+mod sparse;
+mod upload;
+mod typed_buffer;
+mod image_tracker;
+mod mipmap;
+mod readback;
+mod format;
+mod format_table;
 mod descriptor_set_layout;
 mod descriptor_pool;
+mod specialization;
 mod structs;
 mod enums;
 mod bitflags;
@@ -47,6 +63,12 @@ mod event;
 mod fence;
 mod device;
 pub mod util;
+pub mod alloc;
+pub mod texture;
+pub mod export;
+pub mod compute;
+pub mod reflect;
+pub mod hot_reload;
 
 pub mod vks {
     pub use vks_::*;
@@ -176,19 +198,35 @@ pub use shader_module::{ShaderModuleHandle, ShaderModule};
 pub use pipeline_layout::{PipelineLayoutHandle, PipelineLayout, PipelineLayoutBuilder};
 pub use render_pass::{RenderPassHandle, RenderPass, RenderPassBuilder};
 pub use graphics_pipeline::{GraphicsPipeline, GraphicsPipelineBuilder};
+pub use compute_pipeline::{ComputePipeline, ComputePipelineBuilder};
+pub use pipeline_cache::{PipelineCacheHandle, PipelineCache};
 pub use framebuffer::{FramebufferHandle, Framebuffer, FramebufferBuilder};
 pub use command_pool::{CommandPoolHandle, CommandPool, CommandPoolBuilder};
 pub use command_buffer::{CommandBufferHandle, CommandBuffer};
 pub use semaphore::{SemaphoreHandle, Semaphore};
 pub use buffer::{BufferHandle, Buffer, BufferBuilder};
+pub use buffer_view::{BufferViewHandle, BufferView, BufferViewBuilder};
+pub use query_pool::{QueryPoolHandle, QueryPool, QueryPoolBuilder, PipelineStatistics};
 pub use image::{ImageHandle, Image, ImageBuilder};
-pub use sampler::{SamplerHandle, Sampler, SamplerBuilder};
-pub use device_memory::{DeviceMemoryHandle, DeviceMemory, DeviceMemoryBuilder, MemoryMapping};
+pub use sampler::{SamplerHandle, Sampler, SamplerBuilder, SamplerCache};
+pub use device_memory::{DeviceMemoryHandle, DeviceMemory, DeviceMemoryBuilder, MemoryMapping,
+    PersistentMapping, Pod};
+pub use memory_type_selector::MemoryTypeSelector;
+pub use memory_stats::{MemoryStats, MemoryUsage, LiveObject};
+pub use staging_belt::StagingBelt;
+pub use upload::UploadToken;
+pub use typed_buffer::{TypedBuffer, BufferSlice, IndexElement};
+pub use image_tracker::ImageTracker;
+pub use mipmap::{GeneratedMipmaps, MipmapMethod};
+pub use format::{NumericType, Component, FormatComponent};
+pub use sparse::{SparseBindBatch, SparseBindToken, SparseBuffer, SparseImage, SparseImageAspect,
+    SparseImageRegion};
 pub use descriptor_set_layout::{DescriptorSetLayoutHandle, DescriptorSetLayout,
     DescriptorSetLayoutBuilder};
 pub use descriptor_pool::{DescriptorPoolHandle, DescriptorPool, DescriptorPoolBuilder};
 pub use fence::{FenceHandle, Fence, FenceStatus};
-pub use event::{EventHandle, Event, EventStatus};
+pub use event::{EventHandle, Event, EventStatus, Backoff};
+pub use specialization::{SpecConstantType, SpecConstantField, SpecializationData, Specialization};
 pub use structs::*;
 pub use enums::*;
 pub use bitflags::*;
@@ -232,66 +270,6 @@ pub unsafe trait Handle {
 }
 
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-#[repr(C)]
-pub struct QueryPoolHandle(pub(crate) vks::VkQueryPool);
-
-impl QueryPoolHandle {
-    #[inline(always)]
-    pub fn to_raw(&self) -> vks::VkQueryPool {
-        self.0
-    }
-}
-
-unsafe impl Handle for QueryPoolHandle {
-    type Target = QueryPoolHandle;
-
-    fn handle(&self) -> Self::Target {
-        *self
-    }
-}
-
-
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-#[repr(C)]
-pub struct BufferViewHandle(pub(crate) vks::VkBufferView);
-
-impl BufferViewHandle {
-    #[inline(always)]
-    pub fn to_raw(&self) -> vks::VkBufferView {
-        self.0
-    }
-}
-
-unsafe impl Handle for BufferViewHandle {
-    type Target = BufferViewHandle;
-
-    fn handle(&self) -> Self::Target {
-        *self
-    }
-}
-
-
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-#[repr(C)]
-pub struct PipelineCacheHandle(pub(crate) vks::VkPipelineCache);
-
-impl PipelineCacheHandle {
-    #[inline(always)]
-    pub fn to_raw(&self) -> vks::VkPipelineCache {
-        self.0
-    }
-}
-
-unsafe impl Handle for PipelineCacheHandle {
-    type Target = PipelineCacheHandle;
-
-    fn handle(&self) -> Self::Target {
-        *self
-    }
-}
-
-
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 #[repr(C)]
 pub struct PipelineHandle(pub(crate) vks::VkPipeline);
@@ -542,24 +520,6 @@ unsafe impl<'h> Handle for &'h DescriptorSet {
 }
 
 
-#[derive(Clone, Debug)]
-pub struct BufferView(BufferViewHandle);
-
-impl BufferView {
-    pub fn handle(&self) -> BufferViewHandle {
-        self.0
-    }
-}
-
-unsafe impl<'h> Handle for &'h BufferView {
-    type Target = BufferViewHandle;
-
-    fn handle(&self) -> Self::Target {
-        self.0
-    }
-}
-
-
 #[derive(Clone, Debug)]
 pub struct Pipeline(PipelineHandle);
 
@@ -578,24 +538,6 @@ unsafe impl<'h> Handle for &'h Pipeline {
 }
 
 
-#[derive(Clone, Debug)]
-pub struct QueryPool(QueryPoolHandle);
-
-impl QueryPool {
-    pub fn handle(&self) -> QueryPoolHandle {
-        self.0
-    }
-}
-
-unsafe impl<'h> Handle for &'h QueryPool {
-    type Target = QueryPoolHandle;
-
-    fn handle(&self) -> Self::Target {
-        self.0
-    }
-}
-
-
 #[derive(Clone, Debug)]
 pub struct DisplayModeKhr(DisplayModeKhrHandle);
 
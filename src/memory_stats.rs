@@ -0,0 +1,294 @@
+use std::fmt;
+use std::sync::Mutex;
+use std::collections::{BTreeMap, HashMap};
+use ::{DeviceSize, PhysicalDeviceMemoryProperties};
+
+
+/// Allocation counts and sizes for a heap, memory type, or tag.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryUsage {
+    allocation_count: usize,
+    bytes: DeviceSize,
+    peak_bytes: DeviceSize,
+}
+
+impl MemoryUsage {
+    fn add(&mut self, bytes: DeviceSize) {
+        self.allocation_count += 1;
+        self.bytes += bytes;
+        if self.bytes > self.peak_bytes { self.peak_bytes = self.bytes; }
+    }
+
+    fn remove(&mut self, bytes: DeviceSize) {
+        self.allocation_count -= 1;
+        self.bytes -= bytes;
+    }
+
+    /// Returns the number of live `DeviceMemory` allocations.
+    pub fn allocation_count(&self) -> usize {
+        self.allocation_count
+    }
+
+    /// Returns the number of bytes currently allocated.
+    pub fn bytes(&self) -> DeviceSize {
+        self.bytes
+    }
+
+    /// Returns the highest number of bytes allocated at any one time.
+    pub fn peak_bytes(&self) -> DeviceSize {
+        self.peak_bytes
+    }
+}
+
+
+/// A snapshot of the device memory allocated through a `Device`.
+///
+/// Obtain using `Device::memory_stats`.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStats {
+    total: MemoryUsage,
+    heaps: Vec<MemoryUsage>,
+    memory_types: Vec<MemoryUsage>,
+    tags: BTreeMap<String, MemoryUsage>,
+}
+
+impl MemoryStats {
+    /// Returns the usage summed over all heaps.
+    pub fn total(&self) -> &MemoryUsage {
+        &self.total
+    }
+
+    /// Returns the usage of each memory heap, indexed by heap index.
+    pub fn heaps(&self) -> &[MemoryUsage] {
+        &self.heaps
+    }
+
+    /// Returns the usage of each memory type, indexed by memory type index.
+    pub fn memory_types(&self) -> &[MemoryUsage] {
+        &self.memory_types
+    }
+
+    /// Returns the usage of allocations made with each tag (see
+    /// `DeviceMemoryBuilder::tag`).
+    pub fn tags(&self) -> &BTreeMap<String, MemoryUsage> {
+        &self.tags
+    }
+
+    /// Returns the usage of allocations made with `tag`, if any.
+    pub fn tag(&self, tag: &str) -> Option<&MemoryUsage> {
+        self.tags.get(tag)
+    }
+}
+
+
+/// A child object of a `Device` which is still alive.
+///
+/// Obtain using `Device::live_objects`.
+#[derive(Debug, Clone)]
+pub struct LiveObject {
+    id: u64,
+    kind: &'static str,
+    name: Option<String>,
+    size: Option<DeviceSize>,
+}
+
+impl LiveObject {
+    /// Returns the kind of object, e.g. `"Buffer"`.
+    pub fn kind(&self) -> &'static str {
+        self.kind
+    }
+
+    /// Returns the debug name of the object, if set.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_ref().map(|n| n.as_str())
+    }
+
+    /// Returns the allocation size of the object (`DeviceMemory` only).
+    pub fn size(&self) -> Option<DeviceSize> {
+        self.size
+    }
+}
+
+impl fmt::Display for LiveObject {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.kind)?;
+        if let Some(ref name) = self.name { write!(f, " '{}'", name)?; }
+        if let Some(size) = self.size { write!(f, " ({} bytes)", size)?; }
+        Ok(())
+    }
+}
+
+
+#[derive(Debug, Default)]
+struct State {
+    stats: MemoryStats,
+    heap_indices: Vec<u32>,
+    live_objects: HashMap<u64, LiveObject>,
+    next_id: u64,
+}
+
+
+/// Tracks device memory usage and live child objects for a `Device`.
+#[derive(Debug)]
+pub(crate) struct MemoryTracker {
+    state: Mutex<State>,
+}
+
+impl MemoryTracker {
+    pub(crate) fn new(memory_properties: &PhysicalDeviceMemoryProperties) -> MemoryTracker {
+        let type_count = memory_properties.memory_type_count() as usize;
+        let heap_count = memory_properties.memory_heap_count() as usize;
+        let heap_indices = memory_properties.memory_types()[..type_count].iter()
+            .map(|mt| mt.heap_index())
+            .collect();
+
+        let state = State {
+            stats: MemoryStats {
+                total: MemoryUsage::default(),
+                heaps: vec![MemoryUsage::default(); heap_count],
+                memory_types: vec![MemoryUsage::default(); type_count],
+                tags: BTreeMap::new(),
+            },
+            heap_indices,
+            live_objects: HashMap::new(),
+            next_id: 0,
+        };
+
+        MemoryTracker { state: Mutex::new(state) }
+    }
+
+    /// Registers a live child object and returns its tracking id.
+    pub(crate) fn register(&self, kind: &'static str) -> u64 {
+        let mut state = self.state.lock().unwrap();
+        let id = state.next_id;
+        state.next_id += 1;
+        state.live_objects.insert(id, LiveObject { id, kind, name: None, size: None });
+        id
+    }
+
+    /// Unregisters a child object.
+    pub(crate) fn unregister(&self, id: u64) {
+        self.state.lock().unwrap().live_objects.remove(&id);
+    }
+
+    /// Sets the debug name of a registered child object.
+    pub(crate) fn set_name(&self, id: u64, name: &str) {
+        if let Some(obj) = self.state.lock().unwrap().live_objects.get_mut(&id) {
+            obj.name = Some(name.to_owned());
+        }
+    }
+
+    /// Records a new memory allocation and returns its tracking id.
+    pub(crate) fn add_allocation(&self, memory_type_index: u32, size: DeviceSize,
+            tag: Option<&str>) -> u64 {
+        let mut state = self.state.lock().unwrap();
+        let id = state.next_id;
+        state.next_id += 1;
+        state.live_objects.insert(id, LiveObject { id, kind: "DeviceMemory", name: None,
+            size: Some(size) });
+
+        let heap_idx = state.heap_indices[memory_type_index as usize] as usize;
+        state.stats.total.add(size);
+        state.stats.heaps[heap_idx].add(size);
+        state.stats.memory_types[memory_type_index as usize].add(size);
+        if let Some(tag) = tag {
+            state.stats.tags.entry(tag.to_owned()).or_insert_with(MemoryUsage::default).add(size);
+        }
+        id
+    }
+
+    /// Records the release of a memory allocation.
+    pub(crate) fn remove_allocation(&self, id: u64, memory_type_index: u32, size: DeviceSize,
+            tag: Option<&str>) {
+        let mut state = self.state.lock().unwrap();
+        state.live_objects.remove(&id);
+
+        let heap_idx = state.heap_indices[memory_type_index as usize] as usize;
+        state.stats.total.remove(size);
+        state.stats.heaps[heap_idx].remove(size);
+        state.stats.memory_types[memory_type_index as usize].remove(size);
+        if let Some(tag) = tag {
+            if let Some(usage) = state.stats.tags.get_mut(tag) { usage.remove(size); }
+        }
+    }
+
+    /// Returns a snapshot of the current statistics.
+    pub(crate) fn stats(&self) -> MemoryStats {
+        self.state.lock().unwrap().stats.clone()
+    }
+
+    /// Returns a list of all live child objects, oldest first.
+    pub(crate) fn live_objects(&self) -> Vec<LiveObject> {
+        let mut objects: Vec<_> = self.state.lock().unwrap().live_objects.values()
+            .cloned().collect();
+        objects.sort_by_key(|obj| obj.id);
+        objects
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use ::{MemoryType, MemoryHeap, MemoryPropertyFlags};
+    use super::*;
+
+    fn tracker() -> MemoryTracker {
+        let mut memory_types: [MemoryType; 32] = Default::default();
+        memory_types[0] = MemoryType::builder().heap_index(0)
+            .property_flags(MemoryPropertyFlags::DEVICE_LOCAL).build();
+        memory_types[1] = MemoryType::builder().heap_index(1)
+            .property_flags(MemoryPropertyFlags::HOST_VISIBLE).build();
+        let mut memory_heaps: [MemoryHeap; 16] = Default::default();
+        memory_heaps[0] = MemoryHeap::builder().size(1 << 30).build();
+        memory_heaps[1] = MemoryHeap::builder().size(1 << 28).build();
+        MemoryTracker::new(&PhysicalDeviceMemoryProperties::builder()
+            .memory_type_count(2)
+            .memory_types(memory_types)
+            .memory_heap_count(2)
+            .memory_heaps(memory_heaps)
+            .build())
+    }
+
+    #[test]
+    fn allocation_usage() {
+        let tracker = tracker();
+        let a = tracker.add_allocation(0, 256, Some("textures"));
+        let b = tracker.add_allocation(1, 64, None);
+        tracker.remove_allocation(a, 0, 256, Some("textures"));
+        let c = tracker.add_allocation(0, 128, Some("textures"));
+
+        let stats = tracker.stats();
+        assert_eq!(stats.total().allocation_count(), 2);
+        assert_eq!(stats.total().bytes(), 192);
+        assert_eq!(stats.total().peak_bytes(), 320);
+        assert_eq!(stats.heaps()[0].bytes(), 128);
+        assert_eq!(stats.heaps()[0].peak_bytes(), 256);
+        assert_eq!(stats.memory_types()[1].bytes(), 64);
+        assert_eq!(stats.tag("textures").unwrap().allocation_count(), 1);
+        assert!(stats.tag("buffers").is_none());
+
+        tracker.remove_allocation(b, 1, 64, None);
+        tracker.remove_allocation(c, 0, 128, Some("textures"));
+        assert_eq!(tracker.stats().total().bytes(), 0);
+        assert!(tracker.live_objects().is_empty());
+    }
+
+    #[test]
+    fn live_objects() {
+        let tracker = tracker();
+        let buffer = tracker.register("Buffer");
+        let memory = tracker.add_allocation(0, 1024, None);
+        let image = tracker.register("Image");
+        tracker.set_name(image, "depth");
+        tracker.unregister(buffer);
+
+        let objects = tracker.live_objects();
+        assert_eq!(objects.len(), 2);
+        assert_eq!(objects[0].to_string(), "DeviceMemory (1024 bytes)");
+        assert_eq!(objects[1].to_string(), "Image 'depth'");
+
+        tracker.unregister(image);
+        tracker.remove_allocation(memory, 0, 1024, None);
+        assert!(tracker.live_objects().is_empty());
+    }
+}
@@ -0,0 +1,270 @@
+use smallvec::SmallVec;
+use ::{VdResult, Device, Error, ErrorKind, MemoryPropertyFlags, MemoryHeapFlags,
+    PhysicalDeviceMemoryProperties};
+
+
+/// Selects a memory type index according to a set of required, preferred,
+/// and forbidden properties.
+///
+/// Candidate memory types are those permitted by the type filter (usually
+/// `MemoryRequirements::memory_type_bits`) which have all of the required
+/// property flags and none of the forbidden ones. Candidates are ranked by
+/// the number of preferred property flags they have, then by how well their
+/// heap matches the heap preferences. Ties are resolved in favor of the
+/// lowest index, following the ordering guarantees of the Vulkan
+/// specification.
+///
+/// If no candidate is found, the fallback selector (if any) is tried before
+/// an `ErrorKind::NoSuitableMemoryType` error is returned.
+///
+/// ## Example
+///
+/// ```text
+/// // Device-local and host-visible if available, otherwise host-visible
+/// // and cached:
+/// let memory_type_index = MemoryTypeSelector::new()
+///     .required(MemoryPropertyFlags::DEVICE_LOCAL | MemoryPropertyFlags::HOST_VISIBLE)
+///     .fallback(MemoryTypeSelector::new()
+///         .required(MemoryPropertyFlags::HOST_VISIBLE)
+///         .preferred(MemoryPropertyFlags::HOST_CACHED))
+///     .select(&device, memory_requirements.memory_type_bits())?;
+/// ```
+///
+#[derive(Debug, Clone, Default)]
+pub struct MemoryTypeSelector {
+    required: MemoryPropertyFlags,
+    preferred: MemoryPropertyFlags,
+    forbidden: MemoryPropertyFlags,
+    preferred_heap_flags: MemoryHeapFlags,
+    prefer_larger_heaps: bool,
+    fallback: Option<Box<MemoryTypeSelector>>,
+}
+
+impl MemoryTypeSelector {
+    /// Returns a new `MemoryTypeSelector` which accepts any memory type.
+    pub fn new() -> MemoryTypeSelector {
+        MemoryTypeSelector::default()
+    }
+
+    /// Specifies property flags which a memory type must have.
+    pub fn required<'s>(&'s mut self, required: MemoryPropertyFlags)
+            -> &'s mut MemoryTypeSelector {
+        self.required = required;
+        self
+    }
+
+    /// Specifies property flags which a memory type should have if possible.
+    pub fn preferred<'s>(&'s mut self, preferred: MemoryPropertyFlags)
+            -> &'s mut MemoryTypeSelector {
+        self.preferred = preferred;
+        self
+    }
+
+    /// Specifies property flags which a memory type must not have.
+    pub fn forbidden<'s>(&'s mut self, forbidden: MemoryPropertyFlags)
+            -> &'s mut MemoryTypeSelector {
+        self.forbidden = forbidden;
+        self
+    }
+
+    /// Specifies flags which the heap of a memory type should have if
+    /// possible.
+    pub fn preferred_heap_flags<'s>(&'s mut self, preferred_heap_flags: MemoryHeapFlags)
+            -> &'s mut MemoryTypeSelector {
+        self.preferred_heap_flags = preferred_heap_flags;
+        self
+    }
+
+    /// Specifies whether or not memory types belonging to larger heaps should
+    /// be ranked higher than otherwise equivalent ones.
+    pub fn prefer_larger_heaps<'s>(&'s mut self, prefer_larger_heaps: bool)
+            -> &'s mut MemoryTypeSelector {
+        self.prefer_larger_heaps = prefer_larger_heaps;
+        self
+    }
+
+    /// Specifies a selector to use when no memory type satisfies this one.
+    pub fn fallback<'s>(&'s mut self, fallback: &MemoryTypeSelector)
+            -> &'s mut MemoryTypeSelector {
+        self.fallback = Some(Box::new(fallback.clone()));
+        self
+    }
+
+    /// Returns the required property flags.
+    pub fn required_flags(&self) -> MemoryPropertyFlags {
+        self.required
+    }
+
+    /// Returns the preferred property flags.
+    pub fn preferred_flags(&self) -> MemoryPropertyFlags {
+        self.preferred
+    }
+
+    /// Returns the forbidden property flags.
+    pub fn forbidden_flags(&self) -> MemoryPropertyFlags {
+        self.forbidden
+    }
+
+    /// Returns `true` if the memory type at `memory_type_index` satisfies the
+    /// required and forbidden flags of this selector (ignoring any fallback).
+    pub fn is_acceptable(&self, memory_properties: &PhysicalDeviceMemoryProperties,
+            type_filter: u32, memory_type_index: u32) -> bool {
+        if memory_type_index >= memory_properties.memory_type_count() ||
+                (type_filter & (1 << memory_type_index)) == 0 {
+            return false;
+        }
+        let flags = memory_properties.memory_types()[memory_type_index as usize].property_flags();
+        flags.contains(self.required) && (flags & self.forbidden).is_empty()
+    }
+
+    /// Returns the score of an acceptable memory type. Higher is better.
+    fn score(&self, memory_properties: &PhysicalDeviceMemoryProperties, memory_type_index: u32)
+            -> (u32, u32, u64) {
+        let memory_type = &memory_properties.memory_types()[memory_type_index as usize];
+        let heap = &memory_properties.memory_heaps()[memory_type.heap_index() as usize];
+        let preferred_count = (memory_type.property_flags() & self.preferred).bits().count_ones();
+        let heap_flag_count = (heap.flags() & self.preferred_heap_flags).bits().count_ones();
+        let heap_size = if self.prefer_larger_heaps { heap.size() } else { 0 };
+        (preferred_count, heap_flag_count, heap_size)
+    }
+
+    /// Returns the indices of all acceptable memory types, best first,
+    /// followed by those of the fallback selector (if any).
+    pub fn rank(&self, memory_properties: &PhysicalDeviceMemoryProperties, type_filter: u32)
+            -> SmallVec<[u32; 32]> {
+        let mut candidates: SmallVec<[(u32, (u32, u32, u64)); 32]> = SmallVec::new();
+
+        for i in 0..memory_properties.memory_type_count() {
+            if self.is_acceptable(memory_properties, type_filter, i) {
+                candidates.push((i, self.score(memory_properties, i)));
+            }
+        }
+
+        // Stable sort (descending score) keeps lower indices first on ties:
+        candidates.sort_by(|a, b| b.1.cmp(&a.1));
+        let mut ranked: SmallVec<[u32; 32]> = candidates.iter().map(|&(i, _)| i).collect();
+
+        if let Some(ref fallback) = self.fallback {
+            for i in fallback.rank(memory_properties, type_filter) {
+                if !ranked.contains(&i) { ranked.push(i); }
+            }
+        }
+        ranked
+    }
+
+    /// Returns the best memory type index among the given memory properties.
+    pub fn select_from(&self, memory_properties: &PhysicalDeviceMemoryProperties,
+            type_filter: u32) -> VdResult<u32> {
+        match self.rank(memory_properties, type_filter).first() {
+            Some(&idx) => Ok(idx),
+            None => Err(Error::from(ErrorKind::NoSuitableMemoryType {
+                type_filter,
+                required: self.required,
+                forbidden: self.forbidden,
+            })),
+        }
+    }
+
+    /// Returns the best memory type index available on `device`.
+    pub fn select(&self, device: &Device, type_filter: u32) -> VdResult<u32> {
+        self.select_from(&device.physical_device().memory_properties(), type_filter)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::{MemoryType, MemoryHeap};
+
+    /// Returns properties with three heaps (a small device-local heap, a
+    /// large system heap and a larger device-local heap) and five types.
+    fn memory_properties() -> PhysicalDeviceMemoryProperties {
+        let mut heaps: [MemoryHeap; 16] = Default::default();
+        let heap_descs = [(256 << 20, MemoryHeapFlags::DEVICE_LOCAL),
+            (8 << 30, MemoryHeapFlags::empty()), (4 << 30, MemoryHeapFlags::DEVICE_LOCAL)];
+        for (heap, &(size, flags)) in heaps.iter_mut().zip(heap_descs.iter()) {
+            heap.set_size(size);
+            heap.set_flags(flags);
+        }
+
+        let host = MemoryPropertyFlags::HOST_VISIBLE | MemoryPropertyFlags::HOST_COHERENT;
+        let mut types: [MemoryType; 32] = Default::default();
+        let type_descs = [(MemoryPropertyFlags::DEVICE_LOCAL, 0), (host, 1),
+            (host | MemoryPropertyFlags::HOST_CACHED, 1),
+            (host | MemoryPropertyFlags::DEVICE_LOCAL, 0), (MemoryPropertyFlags::DEVICE_LOCAL, 2)];
+        for (ty, &(flags, heap_index)) in types.iter_mut().zip(type_descs.iter()) {
+            ty.set_property_flags(flags);
+            ty.set_heap_index(heap_index);
+        }
+
+        let mut properties = PhysicalDeviceMemoryProperties::default();
+        properties.set_memory_heap_count(heap_descs.len() as u32);
+        properties.set_memory_heaps(heaps);
+        properties.set_memory_type_count(type_descs.len() as u32);
+        properties.set_memory_types(types);
+        properties
+    }
+
+    fn rank(selector: &MemoryTypeSelector, type_filter: u32) -> Vec<u32> {
+        selector.rank(&memory_properties(), type_filter).to_vec()
+    }
+
+    #[test]
+    fn ties_keep_index_order() {
+        assert_eq!(rank(&MemoryTypeSelector::new(), !0), vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn required_forbidden_and_filter() {
+        assert_eq!(rank(MemoryTypeSelector::new()
+            .required(MemoryPropertyFlags::HOST_VISIBLE)
+            .forbidden(MemoryPropertyFlags::DEVICE_LOCAL), !0), vec![1, 2]);
+        assert_eq!(rank(MemoryTypeSelector::new()
+            .required(MemoryPropertyFlags::DEVICE_LOCAL), 0b1001_0110), vec![4]);
+        assert!(!MemoryTypeSelector::new().is_acceptable(&memory_properties(), !0, 5));
+    }
+
+    #[test]
+    fn preferred_flags_rank_first() {
+        assert_eq!(rank(MemoryTypeSelector::new()
+            .required(MemoryPropertyFlags::HOST_VISIBLE)
+            .preferred(MemoryPropertyFlags::HOST_CACHED), !0), vec![2, 1, 3]);
+        assert_eq!(rank(MemoryTypeSelector::new()
+            .preferred(MemoryPropertyFlags::DEVICE_LOCAL | MemoryPropertyFlags::HOST_VISIBLE),
+            !0), vec![3, 0, 1, 2, 4]);
+    }
+
+    #[test]
+    fn heap_preferences() {
+        assert_eq!(rank(MemoryTypeSelector::new()
+            .preferred_heap_flags(MemoryHeapFlags::DEVICE_LOCAL), !0), vec![0, 3, 4, 1, 2]);
+        assert_eq!(rank(MemoryTypeSelector::new()
+            .required(MemoryPropertyFlags::DEVICE_LOCAL)
+            .prefer_larger_heaps(true), !0), vec![4, 0, 3]);
+        // Property flags outrank heap preferences:
+        assert_eq!(rank(MemoryTypeSelector::new()
+            .preferred(MemoryPropertyFlags::HOST_VISIBLE)
+            .prefer_larger_heaps(true), !0), vec![1, 2, 3, 4, 0]);
+    }
+
+    #[test]
+    fn fallback_and_errors() {
+        let mut selector = MemoryTypeSelector::new();
+        selector.required(MemoryPropertyFlags::DEVICE_LOCAL | MemoryPropertyFlags::HOST_CACHED);
+        match selector.select_from(&memory_properties(), !0) {
+            Err(err) => match *err.kind() {
+                ErrorKind::NoSuitableMemoryType { type_filter, required, .. } => {
+                    assert_eq!(type_filter, !0);
+                    assert_eq!(required, selector.required_flags());
+                },
+                _ => panic!("unexpected error: {}", err),
+            },
+            Ok(idx) => panic!("selected {}", idx),
+        }
+
+        selector.fallback(MemoryTypeSelector::new().required(MemoryPropertyFlags::HOST_VISIBLE));
+        assert_eq!(rank(&selector, !0), vec![1, 2, 3]);
+        assert_eq!(selector.select_from(&memory_properties(), 0b1000).unwrap(), 3);
+    }
+}
@@ -0,0 +1,476 @@
+use std::cmp;
+use std::slice;
+use std::ffi::CStr;
+use smallvec::SmallVec;
+use ::{VdResult, Image, ImageView, CommandBuffer, ShaderModule, DescriptorSetLayout,
+    DescriptorPool, DescriptorSet, PipelineLayout, ComputePipeline, Filter, Format,
+    FormatFeatureFlags, ImageUsageFlags, ImageType, ImageTiling, ImageLayout, ImageViewType,
+    ImageAspectFlags, SampleCountFlags, ImageBlit, ImageSubresourceLayers, ImageSubresourceRange,
+    ImageMemoryBarrier, Offset3d, ComponentMapping, DescriptorType, DescriptorSetLayoutBinding,
+    DescriptorPoolSize, DescriptorImageInfo, WriteDescriptorSet, PushConstantRange,
+    ShaderStageFlags, PipelineBindPoint, PipelineStageFlags, AccessFlags, DependencyFlags,
+    ErrorKind, NumericType, QUEUE_FAMILY_IGNORED};
+
+
+/// The width and height of each workgroup of `DOWNSAMPLE_SPIRV`.
+const DOWNSAMPLE_GROUP_SIZE: u32 = 8;
+
+/// A compute shader averaging each 2x2 block of texels of the sampled image
+/// at binding 0 into one texel of the storage image at binding 1.
+///
+/// Invoked in 8x8 workgroups covering the destination level. Push constants
+/// hold the largest valid source coordinate, to which fetches are clamped,
+/// and the destination extent, outside of which invocations return early.
+/// Equivalent to:
+///
+/// ```glsl
+/// #version 450
+/// layout(local_size_x = 8, local_size_y = 8) in;
+/// layout(binding = 0) uniform texture2D src;
+/// layout(binding = 1) uniform writeonly image2D dst;
+/// layout(push_constant) uniform PushConstants { ivec2 src_max; ivec2 dst_size; };
+///
+/// void main() {
+///     ivec2 p = ivec2(gl_GlobalInvocationID.xy);
+///     if (any(greaterThanEqual(p, dst_size))) { return; }
+///     ivec2 s = p + p;
+///     vec4 sum = texelFetch(src, min(s, src_max), 0)
+///         + texelFetch(src, min(s + ivec2(1, 0), src_max), 0)
+///         + texelFetch(src, min(s + ivec2(0, 1), src_max), 0)
+///         + texelFetch(src, min(s + ivec2(1, 1), src_max), 0);
+///     imageStore(dst, p, sum * 0.25);
+/// }
+/// ```
+static DOWNSAMPLE_SPIRV: &'static [u32] = &[
+    0x07230203, 0x00010000, 0x00000000, 0x0000003e, 0x00000000, 0x00020011,
+    0x00000001, 0x00020011, 0x00000038, 0x0006000b, 0x00000001, 0x4c534c47,
+    0x6474732e, 0x3035342e, 0x00000000, 0x0003000e, 0x00000000, 0x00000001,
+    0x0006000f, 0x00000005, 0x00000002, 0x6e69616d, 0x00000000, 0x00000003,
+    0x00060010, 0x00000002, 0x00000011, 0x00000008, 0x00000008, 0x00000001,
+    0x00040047, 0x00000003, 0x0000000b, 0x0000001c, 0x00040047, 0x00000004,
+    0x00000022, 0x00000000, 0x00040047, 0x00000004, 0x00000021, 0x00000000,
+    0x00040047, 0x00000005, 0x00000022, 0x00000000, 0x00040047, 0x00000005,
+    0x00000021, 0x00000001, 0x00030047, 0x00000005, 0x00000019, 0x00050048,
+    0x00000006, 0x00000000, 0x00000023, 0x00000000, 0x00050048, 0x00000006,
+    0x00000001, 0x00000023, 0x00000008, 0x00030047, 0x00000006, 0x00000002,
+    0x00020013, 0x00000007, 0x00030021, 0x00000008, 0x00000007, 0x00030016,
+    0x00000009, 0x00000020, 0x00040015, 0x0000000a, 0x00000020, 0x00000001,
+    0x00040015, 0x0000000b, 0x00000020, 0x00000000, 0x00020014, 0x00000036,
+    0x00040017, 0x00000037, 0x00000036, 0x00000002, 0x00040017, 0x0000000c,
+    0x0000000a, 0x00000002, 0x00040017, 0x0000000d, 0x0000000b, 0x00000002,
+    0x00040017, 0x0000000e, 0x0000000b, 0x00000003, 0x00040017, 0x0000000f,
+    0x00000009, 0x00000004, 0x00040020, 0x00000010, 0x00000001, 0x0000000e,
+    0x0004003b, 0x00000010, 0x00000003, 0x00000001, 0x00090019, 0x00000011,
+    0x00000009, 0x00000001, 0x00000000, 0x00000000, 0x00000000, 0x00000001,
+    0x00000000, 0x00040020, 0x00000012, 0x00000000, 0x00000011, 0x0004003b,
+    0x00000012, 0x00000004, 0x00000000, 0x00090019, 0x00000013, 0x00000009,
+    0x00000001, 0x00000000, 0x00000000, 0x00000000, 0x00000002, 0x00000000,
+    0x00040020, 0x00000014, 0x00000000, 0x00000013, 0x0004003b, 0x00000014,
+    0x00000005, 0x00000000, 0x0004001e, 0x00000006, 0x0000000c, 0x0000000c,
+    0x00040020, 0x00000015, 0x00000009, 0x00000006, 0x0004003b, 0x00000015,
+    0x00000016, 0x00000009, 0x00040020, 0x00000017, 0x00000009, 0x0000000c,
+    0x0004002b, 0x0000000a, 0x00000018, 0x00000000, 0x0004002b, 0x0000000a,
+    0x00000019, 0x00000001, 0x0005002c, 0x0000000c, 0x0000001a, 0x00000019,
+    0x00000018, 0x0005002c, 0x0000000c, 0x0000001b, 0x00000018, 0x00000019,
+    0x0005002c, 0x0000000c, 0x0000001c, 0x00000019, 0x00000019, 0x0004002b,
+    0x00000009, 0x0000001d, 0x3e800000, 0x00050036, 0x00000007, 0x00000002,
+    0x00000000, 0x00000008, 0x000200f8, 0x0000001e, 0x0004003d, 0x0000000e,
+    0x0000001f, 0x00000003, 0x0007004f, 0x0000000d, 0x00000020, 0x0000001f,
+    0x0000001f, 0x00000000, 0x00000001, 0x0004007c, 0x0000000c, 0x00000021,
+    0x00000020, 0x00050041, 0x00000017, 0x00000038, 0x00000016, 0x00000019,
+    0x0004003d, 0x0000000c, 0x00000039, 0x00000038, 0x000500af, 0x00000037,
+    0x0000003a, 0x00000021, 0x00000039, 0x0004009a, 0x00000036, 0x0000003b,
+    0x0000003a, 0x000300f7, 0x0000003d, 0x00000000, 0x000400fa, 0x0000003b,
+    0x0000003c, 0x0000003d, 0x000200f8, 0x0000003c, 0x000100fd, 0x000200f8,
+    0x0000003d, 0x00050080, 0x0000000c, 0x00000022, 0x00000021, 0x00000021,
+    0x00050041, 0x00000017, 0x00000023, 0x00000016, 0x00000018, 0x0004003d,
+    0x0000000c, 0x00000024, 0x00000023, 0x0004003d, 0x00000011, 0x00000025,
+    0x00000004, 0x0007000c, 0x0000000c, 0x00000026, 0x00000001, 0x00000027,
+    0x00000022, 0x00000024, 0x00050080, 0x0000000c, 0x00000027, 0x00000022,
+    0x0000001a, 0x0007000c, 0x0000000c, 0x00000028, 0x00000001, 0x00000027,
+    0x00000027, 0x00000024, 0x00050080, 0x0000000c, 0x00000029, 0x00000022,
+    0x0000001b, 0x0007000c, 0x0000000c, 0x0000002a, 0x00000001, 0x00000027,
+    0x00000029, 0x00000024, 0x00050080, 0x0000000c, 0x0000002b, 0x00000022,
+    0x0000001c, 0x0007000c, 0x0000000c, 0x0000002c, 0x00000001, 0x00000027,
+    0x0000002b, 0x00000024, 0x0007005f, 0x0000000f, 0x0000002d, 0x00000025,
+    0x00000026, 0x00000002, 0x00000018, 0x0007005f, 0x0000000f, 0x0000002e,
+    0x00000025, 0x00000028, 0x00000002, 0x00000018, 0x0007005f, 0x0000000f,
+    0x0000002f, 0x00000025, 0x0000002a, 0x00000002, 0x00000018, 0x0007005f,
+    0x0000000f, 0x00000030, 0x00000025, 0x0000002c, 0x00000002, 0x00000018,
+    0x00050081, 0x0000000f, 0x00000031, 0x0000002d, 0x0000002e, 0x00050081,
+    0x0000000f, 0x00000032, 0x0000002f, 0x00000030, 0x00050081, 0x0000000f,
+    0x00000033, 0x00000031, 0x00000032, 0x0005008e, 0x0000000f, 0x00000034,
+    0x00000033, 0x0000001d, 0x0004003d, 0x00000013, 0x00000035, 0x00000005,
+    0x00040063, 0x00000035, 0x00000021, 0x00000034, 0x000100fd, 0x00010038,
+];
+
+
+/// The method used by `Image::generate_mipmaps` to build a mip chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MipmapMethod {
+    /// Successive `CommandBuffer::blit_image` calls.
+    Blit,
+    /// A compute shader box filter, used when the image's format does not
+    /// support blitting with the requested filter.
+    Compute,
+}
+
+
+/// Resources used by the compute downsampler.
+#[derive(Debug)]
+struct ComputeResources {
+    pipeline: ComputePipeline,
+    descriptor_sets: SmallVec<[DescriptorSet; 8]>,
+    _descriptor_pool: DescriptorPool,
+    pipeline_layout: PipelineLayout,
+    _descriptor_set_layout: DescriptorSetLayout,
+    _shader_module: ShaderModule,
+    _image_views: Vec<ImageView>,
+}
+
+
+/// The result of `Image::generate_mipmaps`.
+///
+///
+/// ### Destruction
+///
+/// This object owns any resources referenced by the recorded commands and
+/// must be kept alive until the command buffer has finished executing.
+///
+#[derive(Debug)]
+pub struct GeneratedMipmaps {
+    method: MipmapMethod,
+    _image: Image,
+    _compute: Option<ComputeResources>,
+}
+
+impl GeneratedMipmaps {
+    /// Returns the method used to build the mip chain.
+    pub fn method(&self) -> MipmapMethod {
+        self.method
+    }
+}
+
+
+/// Returns the extent of mip level `level` of `image`.
+fn level_extent(image: &Image, level: u32) -> [u32; 3] {
+    let extent = image.extent();
+    [cmp::max(extent.width() >> level, 1), cmp::max(extent.height() >> level, 1),
+        cmp::max(extent.depth() >> level, 1)]
+}
+
+fn unsupported(format: Format, reason: &'static str) -> ::Error {
+    ErrorKind::MipmapGenerationUnsupported { format, reason }.into()
+}
+
+/// Returns a barrier transitioning mip level `level` of every layer of `image`.
+fn level_barrier<'i>(image: &'i Image, level: u32, old_layout: ImageLayout,
+        new_layout: ImageLayout, src_access_mask: AccessFlags, dst_access_mask: AccessFlags)
+        -> ImageMemoryBarrier<'i> {
+    ImageMemoryBarrier::builder()
+        .src_access_mask(src_access_mask)
+        .dst_access_mask(dst_access_mask)
+        .old_layout(old_layout)
+        .new_layout(new_layout)
+        .src_queue_family_index(QUEUE_FAMILY_IGNORED)
+        .dst_queue_family_index(QUEUE_FAMILY_IGNORED)
+        .image(image)
+        .subresource_range(ImageSubresourceRange::builder()
+            .aspect_mask(image.format().aspects())
+            .base_mip_level(level)
+            .level_count(1)
+            .base_array_layer(0)
+            .layer_count(image.array_layers())
+            .build())
+        .build()
+}
+
+/// Records commands building the mip chain of `image`. See
+/// `Image::generate_mipmaps`.
+pub(crate) fn generate(image: &Image, command_buffer: &CommandBuffer, filter: Filter)
+        -> VdResult<GeneratedMipmaps> {
+    let format = image.format();
+    if !image.usage().contains(ImageUsageFlags::TRANSFER_DST) {
+        return Err(unsupported(format, "image usage does not include `TRANSFER_DST`"));
+    }
+
+    let format_properties = image.device().physical_device().format_properties(format);
+    let features = match image.tiling() {
+        ImageTiling::Linear => format_properties.linear_tiling_features(),
+        _ => format_properties.optimal_tiling_features(),
+    };
+    let filter_feature = match filter {
+        Filter::Nearest => FormatFeatureFlags::empty(),
+        Filter::Linear => FormatFeatureFlags::SAMPLED_IMAGE_FILTER_LINEAR,
+        Filter::CubicImg => FormatFeatureFlags::SAMPLED_IMAGE_FILTER_CUBIC_IMG,
+    };
+
+    let blit_supported = image.usage().contains(ImageUsageFlags::TRANSFER_SRC) &&
+        features.contains(FormatFeatureFlags::BLIT_SRC | FormatFeatureFlags::BLIT_DST |
+            filter_feature);
+
+    if blit_supported || image.mip_levels() == 1 {
+        record_blits(image, command_buffer, filter);
+        Ok(GeneratedMipmaps { method: MipmapMethod::Blit, _image: image.clone(), _compute: None })
+    } else {
+        let compute = record_compute(image, command_buffer, features)?;
+        Ok(GeneratedMipmaps {
+            method: MipmapMethod::Compute,
+            _image: image.clone(),
+            _compute: Some(compute),
+        })
+    }
+}
+
+/// Builds each level by blitting from the previous one.
+fn record_blits(image: &Image, command_buffer: &CommandBuffer, filter: Filter) {
+    let aspect_mask = image.format().aspects();
+    let subresource = |level| {
+        ImageSubresourceLayers::builder()
+            .aspect_mask(aspect_mask)
+            .mip_level(level)
+            .base_array_layer(0)
+            .layer_count(image.array_layers())
+            .build()
+    };
+    let offsets = |extent: [u32; 3]| {
+        [Offset3d::builder().x(0).y(0).z(0).build(),
+            Offset3d::builder().x(extent[0] as i32).y(extent[1] as i32).z(extent[2] as i32)
+                .build()]
+    };
+
+    for level in 1..image.mip_levels() {
+        let to_transfer_src = level_barrier(image, level - 1, ImageLayout::TransferDstOptimal,
+            ImageLayout::TransferSrcOptimal, AccessFlags::TRANSFER_WRITE,
+            AccessFlags::TRANSFER_READ);
+        command_buffer.pipeline_barrier(PipelineStageFlags::TRANSFER,
+            PipelineStageFlags::TRANSFER, DependencyFlags::empty(), &[], &[], &[to_transfer_src]);
+
+        let blit = ImageBlit::builder()
+            .src_subresource(subresource(level - 1))
+            .src_offsets(offsets(level_extent(image, level - 1)))
+            .dst_subresource(subresource(level))
+            .dst_offsets(offsets(level_extent(image, level)))
+            .build();
+        unsafe {
+            command_buffer.blit_image(image, ImageLayout::TransferSrcOptimal, image,
+                ImageLayout::TransferDstOptimal, &[blit], filter);
+        }
+
+        let to_shader_read = level_barrier(image, level - 1, ImageLayout::TransferSrcOptimal,
+            ImageLayout::ShaderReadOnlyOptimal, AccessFlags::TRANSFER_READ,
+            AccessFlags::SHADER_READ);
+        command_buffer.pipeline_barrier(PipelineStageFlags::TRANSFER,
+            PipelineStageFlags::ALL_COMMANDS, DependencyFlags::empty(), &[], &[],
+            &[to_shader_read]);
+    }
+
+    let last_to_shader_read = level_barrier(image, image.mip_levels() - 1,
+        ImageLayout::TransferDstOptimal, ImageLayout::ShaderReadOnlyOptimal,
+        AccessFlags::TRANSFER_WRITE, AccessFlags::SHADER_READ);
+    command_buffer.pipeline_barrier(PipelineStageFlags::TRANSFER,
+        PipelineStageFlags::ALL_COMMANDS, DependencyFlags::empty(), &[], &[],
+        &[last_to_shader_read]);
+}
+
+/// Builds each level with the compute downsampler, one dispatch per level
+/// and array layer.
+fn record_compute(image: &Image, command_buffer: &CommandBuffer, features: FormatFeatureFlags)
+        -> VdResult<ComputeResources> {
+    let format = image.format();
+    let device = image.device();
+
+    if image.image_type() != ImageType::Type2d || image.samples() != SampleCountFlags::COUNT_1 {
+        return Err(unsupported(format, "linear blit is unsupported and the compute fallback \
+            requires a single-sampled 2D image"));
+    }
+    if format.aspects() != ImageAspectFlags::COLOR {
+        return Err(unsupported(format, "linear blit is unsupported and the compute fallback \
+            requires a color format"));
+    }
+    if !image.usage().contains(ImageUsageFlags::SAMPLED | ImageUsageFlags::STORAGE) ||
+            !features.contains(FormatFeatureFlags::SAMPLED_IMAGE | FormatFeatureFlags::STORAGE_IMAGE) {
+        return Err(unsupported(format, "linear blit is unsupported and the compute fallback \
+            requires `SAMPLED` and `STORAGE` usage and format support"));
+    }
+    match format.numeric_type() {
+        Some(NumericType::Uint) | Some(NumericType::Sint) => return Err(unsupported(format,
+            "linear blit is unsupported and the compute fallback requires a floating-point or \
+            normalized format")),
+        _ => (),
+    }
+    if !device.enabled_features().shader_storage_image_write_without_format() {
+        return Err(unsupported(format, "linear blit is unsupported and the compute fallback \
+            requires the `shaderStorageImageWriteWithoutFormat` feature to be enabled"));
+    }
+
+    let levels = image.mip_levels();
+    let layers = image.array_layers();
+
+    let mut image_views = Vec::with_capacity((levels * layers) as usize);
+    for level in 0..levels {
+        for layer in 0..layers {
+            image_views.push(ImageView::builder()
+                .image(image)
+                .view_type(ImageViewType::Type2d)
+                .format(format)
+                .components(ComponentMapping::default())
+                .subresource_range(ImageSubresourceRange::builder()
+                    .aspect_mask(ImageAspectFlags::COLOR)
+                    .base_mip_level(level)
+                    .level_count(1)
+                    .base_array_layer(layer)
+                    .layer_count(1)
+                    .build())
+                .build(device.clone(), None)?);
+        }
+    }
+    let view = |level: u32, layer: u32| &image_views[(level * layers + layer) as usize];
+
+    let bindings = [
+        DescriptorSetLayoutBinding::builder()
+            .binding(0)
+            .descriptor_type(DescriptorType::SampledImage)
+            .descriptor_count(1)
+            .stage_flags(ShaderStageFlags::COMPUTE)
+            .build(),
+        DescriptorSetLayoutBinding::builder()
+            .binding(1)
+            .descriptor_type(DescriptorType::StorageImage)
+            .descriptor_count(1)
+            .stage_flags(ShaderStageFlags::COMPUTE)
+            .build(),
+    ];
+    let descriptor_set_layout = DescriptorSetLayout::builder()
+        .bindings(&bindings)
+        .build(device.clone())?;
+
+    let set_count = (levels - 1) * layers;
+    let pool_sizes = [
+        DescriptorPoolSize::builder()
+            .type_of(DescriptorType::SampledImage)
+            .descriptor_count(set_count)
+            .build(),
+        DescriptorPoolSize::builder()
+            .type_of(DescriptorType::StorageImage)
+            .descriptor_count(set_count)
+            .build(),
+    ];
+    let descriptor_pool = DescriptorPool::builder()
+        .max_sets(set_count)
+        .pool_sizes(&pool_sizes)
+        .build(device.clone())?;
+    let set_layouts: Vec<_> = (0..set_count).map(|_| descriptor_set_layout.handle()).collect();
+    let descriptor_sets = descriptor_pool.allocate_descriptor_sets(&set_layouts)?;
+
+    for level in 1..levels {
+        for layer in 0..layers {
+            let set = &descriptor_sets[((level - 1) * layers + layer) as usize];
+            let src_info = DescriptorImageInfo::builder()
+                .image_view(view(level - 1, layer))
+                .image_layout(ImageLayout::ShaderReadOnlyOptimal)
+                .build();
+            let dst_info = DescriptorImageInfo::builder()
+                .image_view(view(level, layer))
+                .image_layout(ImageLayout::General)
+                .build();
+            let writes = [
+                WriteDescriptorSet::builder()
+                    .dst_set(set)
+                    .dst_binding(0)
+                    .dst_array_element(0)
+                    .descriptor_count(1)
+                    .descriptor_type(DescriptorType::SampledImage)
+                    .image_info(&src_info)
+                    .build(),
+                WriteDescriptorSet::builder()
+                    .dst_set(set)
+                    .dst_binding(1)
+                    .dst_array_element(0)
+                    .descriptor_count(1)
+                    .descriptor_type(DescriptorType::StorageImage)
+                    .image_info(&dst_info)
+                    .build(),
+            ];
+            descriptor_pool.update_descriptor_sets(&writes, &[]);
+        }
+    }
+
+    let push_constant_ranges = [
+        PushConstantRange::builder()
+            .stage_flags(ShaderStageFlags::COMPUTE)
+            .offset(0)
+            .size(16)
+            .build(),
+    ];
+    let set_layout_handles = [descriptor_set_layout.handle()];
+    let pipeline_layout = PipelineLayout::builder()
+        .set_layouts(&set_layout_handles)
+        .push_constant_ranges(&push_constant_ranges)
+        .build(device.clone())?;
+
+    let shader_module = ShaderModule::new(device.clone(), DOWNSAMPLE_SPIRV)?;
+    let entry_point = CStr::from_bytes_with_nul(b"main\0").unwrap();
+    let pipeline = ComputePipeline::builder()
+        .shader(&shader_module, entry_point)
+        .layout(&pipeline_layout)
+        .build(device.clone())?;
+
+    let resources = ComputeResources {
+        pipeline,
+        descriptor_sets,
+        _descriptor_pool: descriptor_pool,
+        pipeline_layout,
+        _descriptor_set_layout: descriptor_set_layout,
+        _shader_module: shader_module,
+        _image_views: image_views,
+    };
+
+    command_buffer.bind_compute_pipeline(&resources.pipeline);
+
+    for level in 1..levels {
+        // The source level was last written by a transfer (level 0) or by
+        // the previous dispatch:
+        let (src_old_layout, src_stage_mask, src_access_mask) = if level == 1 {
+            (ImageLayout::TransferDstOptimal, PipelineStageFlags::TRANSFER,
+                AccessFlags::TRANSFER_WRITE)
+        } else {
+            (ImageLayout::General, PipelineStageFlags::COMPUTE_SHADER, AccessFlags::SHADER_WRITE)
+        };
+        let barriers = [
+            level_barrier(image, level - 1, src_old_layout, ImageLayout::ShaderReadOnlyOptimal,
+                src_access_mask, AccessFlags::SHADER_READ),
+            level_barrier(image, level, ImageLayout::TransferDstOptimal, ImageLayout::General,
+                AccessFlags::empty(), AccessFlags::SHADER_WRITE),
+        ];
+        command_buffer.pipeline_barrier(src_stage_mask, PipelineStageFlags::COMPUTE_SHADER,
+            DependencyFlags::empty(), &[], &[], &barriers);
+
+        let src_extent = level_extent(image, level - 1);
+        let dst_extent = level_extent(image, level);
+        let push_constants: [i32; 4] = [src_extent[0] as i32 - 1, src_extent[1] as i32 - 1,
+            dst_extent[0] as i32, dst_extent[1] as i32];
+        let push_constant_bytes = unsafe {
+            slice::from_raw_parts(push_constants.as_ptr() as *const u8, 16)
+        };
+        command_buffer.push_constants(&resources.pipeline_layout, ShaderStageFlags::COMPUTE, 0,
+            push_constant_bytes);
+        let group_count_x = (dst_extent[0] + DOWNSAMPLE_GROUP_SIZE - 1) / DOWNSAMPLE_GROUP_SIZE;
+        let group_count_y = (dst_extent[1] + DOWNSAMPLE_GROUP_SIZE - 1) / DOWNSAMPLE_GROUP_SIZE;
+
+        for layer in 0..layers {
+            let set = &resources.descriptor_sets[((level - 1) * layers + layer) as usize];
+            command_buffer.bind_descriptor_sets(PipelineBindPoint::Compute,
+                &resources.pipeline_layout, 0, &[set], &[]);
+            command_buffer.dispatch(group_count_x, group_count_y, 1);
+        }
+    }
+
+    let last_to_shader_read = level_barrier(image, levels - 1, ImageLayout::General,
+        ImageLayout::ShaderReadOnlyOptimal, AccessFlags::SHADER_WRITE, AccessFlags::SHADER_READ);
+    command_buffer.pipeline_barrier(PipelineStageFlags::COMPUTE_SHADER,
+        PipelineStageFlags::ALL_COMMANDS, DependencyFlags::empty(), &[], &[],
+        &[last_to_shader_read]);
+
+    Ok(resources)
+}
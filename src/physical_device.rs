@@ -79,7 +79,7 @@ impl PhysicalDevice {
     /// https://www.khronos.org/registry/vulkan/specs/1.0/man/html/vkGetPhysicalDeviceImageFormatProperties.html
     //
     #[inline]
-    pub fn image_format_properties<Pd>(&self, format: Format,
+    pub fn image_format_properties(&self, format: Format,
             type_: ImageType, tiling: ImageTiling, usage: ImageUsageFlags, flags: ImageCreateFlags)
             -> VdResult<ImageFormatProperties> {
         self.instance().get_physical_device_image_format_properties(self, format, type_,
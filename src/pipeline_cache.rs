@@ -0,0 +1,313 @@
+use std::sync::Arc;
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::Path;
+use std::ptr;
+use libc::c_void;
+use smallvec::SmallVec;
+use vks;
+use ::{VdResult, Handle, Device, PipelineCacheCreateInfo, PipelineCacheHeaderVersion, ErrorKind,
+    PhysicalDeviceProperties};
+
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(C)]
+pub struct PipelineCacheHandle(pub(crate) vks::VkPipelineCache);
+
+impl PipelineCacheHandle {
+    #[inline(always)]
+    pub fn to_raw(&self) -> vks::VkPipelineCache {
+        self.0
+    }
+}
+
+unsafe impl Handle for PipelineCacheHandle {
+    type Target = PipelineCacheHandle;
+
+    fn handle(&self) -> Self::Target {
+        *self
+    }
+}
+
+
+/// The size in bytes of a version one pipeline cache header.
+const HEADER_SIZE: usize = 16 + vks::VK_UUID_SIZE;
+
+fn incompatible(reason: &'static str) -> ::Error {
+    ErrorKind::IncompatiblePipelineCache { reason }.into()
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    let b = &bytes[offset..offset + 4];
+    b[0] as u32 | (b[1] as u32) << 8 | (b[2] as u32) << 16 | (b[3] as u32) << 24
+}
+
+/// Checks that the header of pipeline cache `data` was written by an
+/// implementation compatible with a physical device with `properties`.
+fn validate_header(properties: &PhysicalDeviceProperties, data: &[u8]) -> VdResult<()> {
+    if data.len() < HEADER_SIZE {
+        return Err(incompatible("data is smaller than the cache header"));
+    }
+    let header_size = read_u32(data, 0) as usize;
+    if header_size < HEADER_SIZE || header_size > data.len() {
+        return Err(incompatible("invalid header size"));
+    }
+    if read_u32(data, 4) != PipelineCacheHeaderVersion::PipelineCacheHeaderVersionOne as u32 {
+        return Err(incompatible("unsupported header version"));
+    }
+
+    if read_u32(data, 8) != properties.vendor_id() {
+        return Err(incompatible("vendor ID does not match the physical device"));
+    }
+    if read_u32(data, 12) != properties.device_id() {
+        return Err(incompatible("device ID does not match the physical device"));
+    }
+    if &data[16..HEADER_SIZE] != properties.pipeline_cache_uuid() {
+        return Err(incompatible("pipeline cache UUID does not match the physical device"));
+    }
+    Ok(())
+}
+
+
+/// Returns the handle of the single pipeline cache shared by a batch of
+/// pipeline builders, or `None` if none of them specifies one.
+///
+/// Returns an `IncompatiblePipelineCache` error if the builders specify
+/// different caches (a batch is created with one cache).
+pub(crate) fn batch_cache_handle<'c, I>(caches: I) -> VdResult<Option<PipelineCacheHandle>>
+        where I: IntoIterator<Item=Option<&'c PipelineCache>> {
+    let mut batch_handle = None;
+    for handle in caches.into_iter().filter_map(|cache| cache.map(|c| c.handle())) {
+        match batch_handle {
+            Some(batch_handle) if batch_handle != handle =>
+                return Err(incompatible("the builders specify different pipeline caches")),
+            _ => batch_handle = Some(handle),
+        }
+    }
+    Ok(batch_handle)
+}
+
+
+#[derive(Debug)]
+struct Inner {
+    handle: PipelineCacheHandle,
+    device: Device,
+}
+
+impl Drop for Inner {
+    fn drop(&mut self) {
+        unsafe {
+            self.device.destroy_pipeline_cache(self.handle, None);
+        }
+    }
+}
+
+
+/// A pipeline cache, allowing the results of pipeline construction to be
+/// reused between pipelines and between runs of an application.
+///
+/// Pass a cache to a pipeline builder's `pipeline_cache` option and persist
+/// it with `::save` and `::load`.
+///
+///
+/// ### Destruction
+///
+/// Dropping this `PipelineCache` will cause `Device::destroy_pipeline_cache`
+/// to be called, automatically releasing any resources associated with it.
+///
+#[derive(Debug, Clone)]
+pub struct PipelineCache {
+    inner: Arc<Inner>,
+}
+
+impl PipelineCache {
+    /// Creates a new, empty pipeline cache.
+    pub fn new(device: Device) -> VdResult<PipelineCache> {
+        PipelineCache::create(device, &[])
+    }
+
+    /// Creates a new pipeline cache initialized with `data`, previously
+    /// returned by `::data`.
+    ///
+    /// Returns an `IncompatiblePipelineCache` error if the header of `data`
+    /// is malformed or its vendor ID, device ID or pipeline cache UUID do not
+    /// match the device's physical device.
+    pub fn with_data(device: Device, data: &[u8]) -> VdResult<PipelineCache> {
+        validate_header(&device.physical_device().properties(), data)?;
+        PipelineCache::create(device, data)
+    }
+
+    /// Creates a new pipeline cache initialized with the contents of the
+    /// file at `path`, written by `::save`.
+    ///
+    /// Returns an error if the file can not be read or its contents are not
+    /// compatible with the device (see `::with_data`). Callers will usually
+    /// fall back to `::new` in either case.
+    pub fn load<P: AsRef<Path>>(device: Device, path: P) -> VdResult<PipelineCache> {
+        let mut data = Vec::new();
+        File::open(path)?.read_to_end(&mut data)?;
+        PipelineCache::with_data(device, &data)
+    }
+
+    fn create(device: Device, data: &[u8]) -> VdResult<PipelineCache> {
+        let mut create_info = PipelineCacheCreateInfo::default();
+        if !data.is_empty() {
+            create_info.set_initial_data_size(data.len());
+            unsafe { create_info.set_initial_data(data.as_ptr() as *const c_void); }
+        }
+
+        let handle = unsafe { device.create_pipeline_cache(&create_info, None)? };
+
+        Ok(PipelineCache {
+            inner: Arc::new(Inner {
+                handle,
+                device,
+            })
+        })
+    }
+
+    /// Returns this object's handle.
+    pub fn handle(&self) -> PipelineCacheHandle {
+        self.inner.handle
+    }
+
+    /// Returns a reference to the associated device.
+    pub fn device(&self) -> &Device {
+        &self.inner.device
+    }
+
+    /// Returns the contents of this cache, including its header.
+    pub fn data(&self) -> VdResult<Vec<u8>> {
+        let mut data_size = 0;
+        unsafe {
+            self.inner.device.get_pipeline_cache_data(self, &mut data_size, ptr::null_mut())?;
+        }
+        let mut data = vec![0u8; data_size];
+        unsafe {
+            self.inner.device.get_pipeline_cache_data(self, &mut data_size,
+                data.as_mut_ptr() as *mut c_void)?;
+        }
+        data.truncate(data_size);
+        Ok(data)
+    }
+
+    /// Writes the contents of this cache to the file at `path`.
+    ///
+    /// The data is first written to a temporary file in the same directory
+    /// which then replaces `path`, so an interrupted save never leaves a
+    /// truncated cache behind.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> VdResult<()> {
+        let path = path.as_ref();
+        let data = self.data()?;
+
+        let mut tmp_name = path.file_name()
+            .ok_or_else(|| ErrorKind::String(format!("invalid pipeline cache path: {}",
+                path.display())))?
+            .to_os_string();
+        tmp_name.push(".tmp");
+        let tmp_path = path.with_file_name(tmp_name);
+
+        let result = File::create(&tmp_path)
+            .and_then(|mut file| {
+                file.write_all(&data)?;
+                file.sync_all()
+            })
+            .and_then(|_| fs::rename(&tmp_path, path));
+        if let Err(err) = result {
+            let _ = fs::remove_file(&tmp_path);
+            return Err(err.into());
+        }
+        Ok(())
+    }
+
+    /// Merges the contents of `sources` into this cache.
+    pub fn merge(&self, sources: &[&PipelineCache]) -> VdResult<()> {
+        let handles: SmallVec<[PipelineCacheHandle; 8]> = sources.iter()
+            .map(|cache| cache.handle())
+            .collect();
+        unsafe { self.inner.device.merge_pipeline_caches(self, &handles) }
+    }
+}
+
+unsafe impl<'h> Handle for &'h PipelineCache {
+    type Target = PipelineCacheHandle;
+
+    fn handle(&self) -> Self::Target {
+        self.inner.handle
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::PhysicalDevicePropertiesBuilder;
+
+    const UUID: [u8; vks::VK_UUID_SIZE] = [7; vks::VK_UUID_SIZE];
+
+    fn properties() -> PhysicalDeviceProperties {
+        PhysicalDevicePropertiesBuilder::new()
+            .vendor_id(0x10de)
+            .device_id(0x1b80)
+            .pipeline_cache_uuid(UUID)
+            .build()
+    }
+
+    fn header(vendor_id: u32, device_id: u32) -> Vec<u8> {
+        let mut data = Vec::new();
+        for &word in &[HEADER_SIZE as u32, 1, vendor_id, device_id] {
+            data.extend_from_slice(&[word as u8, (word >> 8) as u8, (word >> 16) as u8,
+                (word >> 24) as u8]);
+        }
+        data.extend_from_slice(&UUID);
+        data
+    }
+
+    fn reason(result: VdResult<()>) -> &'static str {
+        let err = result.unwrap_err();
+        match *err.kind() {
+            ErrorKind::IncompatiblePipelineCache { reason } => reason,
+            _ => panic!("unexpected error: {}", err),
+        }
+    }
+
+    #[test]
+    fn matching_header() {
+        let mut data = header(0x10de, 0x1b80);
+        validate_header(&properties(), &data).unwrap();
+        data.extend_from_slice(&[1, 2, 3]);
+        validate_header(&properties(), &data).unwrap();
+    }
+
+    #[test]
+    fn malformed_header() {
+        let data = header(0x10de, 0x1b80);
+        assert_eq!(reason(validate_header(&properties(), &data[..HEADER_SIZE - 1])),
+            "data is smaller than the cache header");
+
+        let mut oversized = data.clone();
+        oversized[0] = HEADER_SIZE as u8 + 1;
+        assert_eq!(reason(validate_header(&properties(), &oversized)), "invalid header size");
+        let mut undersized = data.clone();
+        undersized[0] = 4;
+        assert_eq!(reason(validate_header(&properties(), &undersized)), "invalid header size");
+
+        let mut version = data.clone();
+        version[4] = 2;
+        assert_eq!(reason(validate_header(&properties(), &version)),
+            "unsupported header version");
+    }
+
+    #[test]
+    fn mismatched_device() {
+        assert_eq!(reason(validate_header(&properties(), &header(0x1002, 0x1b80))),
+            "vendor ID does not match the physical device");
+        assert_eq!(reason(validate_header(&properties(), &header(0x10de, 0x1b81))),
+            "device ID does not match the physical device");
+
+        let mut data = header(0x10de, 0x1b80);
+        data[HEADER_SIZE - 1] = 0;
+        assert_eq!(reason(validate_header(&properties(), &data)),
+            "pipeline cache UUID does not match the physical device");
+    }
+}
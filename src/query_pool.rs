@@ -0,0 +1,345 @@
+use std::sync::Arc;
+use std::mem;
+use libc::c_void;
+use vks;
+use ::{VdResult, Handle, Device, Queue, QueryPoolCreateInfo, QueryType,
+    QueryPipelineStatisticFlags, QueryResultFlags, ErrorKind};
+
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(C)]
+pub struct QueryPoolHandle(pub(crate) vks::VkQueryPool);
+
+impl QueryPoolHandle {
+    #[inline(always)]
+    pub fn to_raw(&self) -> vks::VkQueryPool {
+        self.0
+    }
+}
+
+unsafe impl Handle for QueryPoolHandle {
+    type Target = QueryPoolHandle;
+
+    fn handle(&self) -> Self::Target {
+        *self
+    }
+}
+
+
+/// The counters of a pipeline statistics query.
+///
+/// Counters not enabled in the query pool's `QueryPipelineStatisticFlags`
+/// are `None`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PipelineStatistics {
+    pub input_assembly_vertices: Option<u64>,
+    pub input_assembly_primitives: Option<u64>,
+    pub vertex_shader_invocations: Option<u64>,
+    pub geometry_shader_invocations: Option<u64>,
+    pub geometry_shader_primitives: Option<u64>,
+    pub clipping_invocations: Option<u64>,
+    pub clipping_primitives: Option<u64>,
+    pub fragment_shader_invocations: Option<u64>,
+    pub tessellation_control_shader_patches: Option<u64>,
+    pub tessellation_evaluation_shader_invocations: Option<u64>,
+    pub compute_shader_invocations: Option<u64>,
+}
+
+impl PipelineStatistics {
+    /// Returns the statistics for `values`, which are written by the
+    /// implementation in the bit order of the enabled `flags`.
+    fn from_values(flags: QueryPipelineStatisticFlags, values: &[u64]) -> PipelineStatistics {
+        let mut values = values.iter().cloned();
+        let mut next = |flag| if flags.contains(flag) { values.next() } else { None };
+
+        PipelineStatistics {
+            input_assembly_vertices: next(QueryPipelineStatisticFlags::INPUT_ASSEMBLY_VERTICES),
+            input_assembly_primitives: next(QueryPipelineStatisticFlags::INPUT_ASSEMBLY_PRIMITIVES),
+            vertex_shader_invocations: next(QueryPipelineStatisticFlags::VERTEX_SHADER_INVOCATIONS),
+            geometry_shader_invocations:
+                next(QueryPipelineStatisticFlags::GEOMETRY_SHADER_INVOCATIONS),
+            geometry_shader_primitives:
+                next(QueryPipelineStatisticFlags::GEOMETRY_SHADER_PRIMITIVES),
+            clipping_invocations: next(QueryPipelineStatisticFlags::CLIPPING_INVOCATIONS),
+            clipping_primitives: next(QueryPipelineStatisticFlags::CLIPPING_PRIMITIVES),
+            fragment_shader_invocations:
+                next(QueryPipelineStatisticFlags::FRAGMENT_SHADER_INVOCATIONS),
+            tessellation_control_shader_patches:
+                next(QueryPipelineStatisticFlags::TESSELLATION_CONTROL_SHADER_PATCHES),
+            tessellation_evaluation_shader_invocations:
+                next(QueryPipelineStatisticFlags::TESSELLATION_EVALUATION_SHADER_INVOCATIONS),
+            compute_shader_invocations:
+                next(QueryPipelineStatisticFlags::COMPUTE_SHADER_INVOCATIONS),
+        }
+    }
+}
+
+
+/// Splits query results, each made up of `value_count` values followed by
+/// its availability, returning `None` for unavailable queries.
+fn split_results(data: &[u64], value_count: usize) -> Vec<Option<Vec<u64>>> {
+    data.chunks(value_count + 1).map(|query| {
+        if query[value_count] != 0 {
+            Some(query[..value_count].to_vec())
+        } else {
+            None
+        }
+    }).collect()
+}
+
+
+#[derive(Debug)]
+struct Inner {
+    handle: QueryPoolHandle,
+    device: Device,
+    query_type: QueryType,
+    query_count: u32,
+    pipeline_statistics: QueryPipelineStatisticFlags,
+}
+
+impl Drop for Inner {
+    fn drop(&mut self) {
+        unsafe {
+            self.device.destroy_query_pool(self.handle, None);
+        }
+    }
+}
+
+
+/// A pool of queries of a single type.
+///
+/// The typed readers (`::timestamps_ns`, `::occlusion_counts` and
+/// `::pipeline_statistics`) return one entry per query. When `wait` is
+/// `false`, queries whose results are not yet available are `None`; when it
+/// is `true` the readers block until every result is available.
+///
+///
+/// ### Destruction
+///
+/// Dropping this `QueryPool` will cause `Device::destroy_query_pool` to be
+/// called, automatically releasing any resources associated with it.
+///
+#[derive(Debug, Clone)]
+pub struct QueryPool {
+    inner: Arc<Inner>,
+}
+
+impl QueryPool {
+    /// Returns a new `QueryPoolBuilder`.
+    pub fn builder<'b>() -> QueryPoolBuilder<'b> {
+        QueryPoolBuilder::new()
+    }
+
+    /// Returns this object's handle.
+    pub fn handle(&self) -> QueryPoolHandle {
+        self.inner.handle
+    }
+
+    /// Returns a reference to the associated device.
+    pub fn device(&self) -> &Device {
+        &self.inner.device
+    }
+
+    /// Returns the type of the queries in this pool.
+    pub fn query_type(&self) -> QueryType {
+        self.inner.query_type
+    }
+
+    /// Returns the number of queries in this pool.
+    pub fn query_count(&self) -> u32 {
+        self.inner.query_count
+    }
+
+    /// Returns the statistics counted by a pipeline statistics pool.
+    pub fn pipeline_statistics_flags(&self) -> QueryPipelineStatisticFlags {
+        self.inner.pipeline_statistics
+    }
+
+    /// Returns the timestamps written to `query_count` queries starting at
+    /// `first_query`, in nanoseconds.
+    ///
+    /// Timestamps are masked by the `timestampValidBits` of the family of
+    /// `queue`, which must be the queue they were written on, and converted
+    /// using the device's `timestampPeriod`.
+    pub fn timestamps_ns(&self, queue: &Queue, first_query: u32, query_count: u32, wait: bool)
+            -> VdResult<Vec<Option<u64>>> {
+        self.check_type(QueryType::Timestamp)?;
+
+        let physical_device = self.inner.device.physical_device();
+        let valid_bits = physical_device.queue_family_properties()?
+            .get(queue.family_index() as usize)
+            .map(|properties| properties.timestamp_valid_bits())
+            .unwrap_or(0);
+        if valid_bits == 0 {
+            return Err(ErrorKind::InvalidQuery {
+                reason: "the queue family does not support timestamps" }.into());
+        }
+        let mask = if valid_bits >= 64 { !0 } else { (1u64 << valid_bits) - 1 };
+        let period = physical_device.properties().limits().timestamp_period() as f64;
+
+        Ok(self.results(first_query, query_count, 1, wait)?.into_iter()
+            .map(|values| values.map(|values| ((values[0] & mask) as f64 * period) as u64))
+            .collect())
+    }
+
+    /// Returns the number of samples which passed the depth and stencil
+    /// tests for `query_count` occlusion queries starting at `first_query`.
+    pub fn occlusion_counts(&self, first_query: u32, query_count: u32, wait: bool)
+            -> VdResult<Vec<Option<u64>>> {
+        self.check_type(QueryType::Occlusion)?;
+        Ok(self.results(first_query, query_count, 1, wait)?.into_iter()
+            .map(|values| values.map(|values| values[0]))
+            .collect())
+    }
+
+    /// Returns the counters of `query_count` pipeline statistics queries
+    /// starting at `first_query`.
+    pub fn pipeline_statistics(&self, first_query: u32, query_count: u32, wait: bool)
+            -> VdResult<Vec<Option<PipelineStatistics>>> {
+        self.check_type(QueryType::PipelineStatistics)?;
+        let flags = self.inner.pipeline_statistics;
+        let value_count = flags.bits().count_ones();
+        Ok(self.results(first_query, query_count, value_count, wait)?.into_iter()
+            .map(|values| values.map(|values| PipelineStatistics::from_values(flags, &values)))
+            .collect())
+    }
+
+    fn check_type(&self, query_type: QueryType) -> VdResult<()> {
+        if self.inner.query_type != query_type {
+            return Err(ErrorKind::InvalidQuery {
+                reason: "the query pool is of a different query type" }.into());
+        }
+        Ok(())
+    }
+
+    /// Reads `value_count` 64-bit values for each query along with its
+    /// availability, returning `None` for unavailable queries.
+    fn results(&self, first_query: u32, query_count: u32, value_count: u32, wait: bool)
+            -> VdResult<Vec<Option<Vec<u64>>>> {
+        if first_query.checked_add(query_count).map(|end| end > self.inner.query_count)
+                .unwrap_or(true) {
+            return Err(ErrorKind::InvalidQuery { reason: "query range out of bounds" }.into());
+        }
+
+        // Each query's values are followed by its availability:
+        let stride = value_count as usize + 1;
+        let mut data = vec![0u64; stride * query_count as usize];
+        let mut flags = QueryResultFlags::RESULT_64 | QueryResultFlags::WITH_AVAILABILITY;
+        if wait { flags |= QueryResultFlags::WAIT; }
+        unsafe {
+            self.inner.device.get_query_pool_results(self, first_query, query_count,
+                data.len() * mem::size_of::<u64>(), data.as_mut_ptr() as *mut c_void,
+                (stride * mem::size_of::<u64>()) as u64, flags)?;
+        }
+
+        Ok(split_results(&data, value_count as usize))
+    }
+}
+
+unsafe impl<'h> Handle for &'h QueryPool {
+    type Target = QueryPoolHandle;
+
+    fn handle(&self) -> Self::Target {
+        self.inner.handle
+    }
+}
+
+
+/// A builder for a `QueryPool`.
+#[derive(Debug, Clone)]
+pub struct QueryPoolBuilder<'b> {
+    create_info: QueryPoolCreateInfo<'b>,
+}
+
+impl<'b> QueryPoolBuilder<'b> {
+    /// Returns a new `QueryPoolBuilder`.
+    pub fn new() -> QueryPoolBuilder<'b> {
+        QueryPoolBuilder { create_info: QueryPoolCreateInfo::default() }
+    }
+
+    /// Specifies the type of queries managed by the pool.
+    pub fn query_type<'s>(&'s mut self, query_type: QueryType) -> &'s mut QueryPoolBuilder<'b> {
+        self.create_info.set_query_type(query_type);
+        self
+    }
+
+    /// Specifies the number of queries managed by the pool.
+    pub fn query_count<'s>(&'s mut self, query_count: u32) -> &'s mut QueryPoolBuilder<'b> {
+        self.create_info.set_query_count(query_count);
+        self
+    }
+
+    /// Specifies which counters will be returned by queries of a pipeline
+    /// statistics pool.
+    pub fn pipeline_statistics<'s>(&'s mut self, pipeline_statistics: QueryPipelineStatisticFlags)
+            -> &'s mut QueryPoolBuilder<'b> {
+        self.create_info.set_pipeline_statistics(pipeline_statistics);
+        self
+    }
+
+    /// Creates and returns a new `QueryPool`.
+    pub fn build(&self, device: Device) -> VdResult<QueryPool> {
+        let handle = unsafe { device.create_query_pool(&self.create_info, None)? };
+
+        Ok(QueryPool {
+            inner: Arc::new(Inner {
+                handle,
+                device,
+                query_type: self.create_info.query_type(),
+                query_count: self.create_info.query_count(),
+                pipeline_statistics: self.create_info.pipeline_statistics(),
+            })
+        })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn statistics_follow_flag_bit_order() {
+        let flags = QueryPipelineStatisticFlags::COMPUTE_SHADER_INVOCATIONS |
+            QueryPipelineStatisticFlags::INPUT_ASSEMBLY_VERTICES |
+            QueryPipelineStatisticFlags::FRAGMENT_SHADER_INVOCATIONS;
+        let statistics = PipelineStatistics::from_values(flags, &[3, 300, 7]);
+        assert_eq!(statistics, PipelineStatistics {
+            input_assembly_vertices: Some(3),
+            fragment_shader_invocations: Some(300),
+            compute_shader_invocations: Some(7),
+            .. PipelineStatistics::default()
+        });
+    }
+
+    #[test]
+    fn all_statistics() {
+        let values: Vec<u64> = (1..12).collect();
+        let statistics = PipelineStatistics::from_values(QueryPipelineStatisticFlags::all(),
+            &values);
+        assert_eq!(statistics.input_assembly_vertices, Some(1));
+        assert_eq!(statistics.vertex_shader_invocations, Some(3));
+        assert_eq!(statistics.clipping_primitives, Some(7));
+        assert_eq!(statistics.tessellation_control_shader_patches, Some(9));
+        assert_eq!(statistics.compute_shader_invocations, Some(11));
+
+        assert_eq!(PipelineStatistics::from_values(QueryPipelineStatisticFlags::empty(), &[]),
+            PipelineStatistics::default());
+    }
+
+    #[test]
+    fn missing_values_are_none() {
+        let flags = QueryPipelineStatisticFlags::VERTEX_SHADER_INVOCATIONS |
+            QueryPipelineStatisticFlags::CLIPPING_INVOCATIONS;
+        let statistics = PipelineStatistics::from_values(flags, &[5]);
+        assert_eq!(statistics.vertex_shader_invocations, Some(5));
+        assert_eq!(statistics.clipping_invocations, None);
+    }
+
+    #[test]
+    fn results_with_availability() {
+        assert_eq!(split_results(&[1, 2, 1, 3, 4, 0, 5, 6, 1], 2),
+            vec![Some(vec![1, 2]), None, Some(vec![5, 6])]);
+        assert_eq!(split_results(&[9, 0, 8, 1], 1), vec![None, Some(vec![8])]);
+    }
+}
@@ -74,8 +74,8 @@ impl Queue {
     /// https://www.khronos.org/registry/vulkan/specs/1.0/man/html/vkQueueBindSparse.html
     //
     #[inline]
-    pub fn bind_sparse<Q, F>(&self, bind_info: &[BindSparseInfo], fence: F) -> VdResult<()>
-            where Q: Handle<Target=QueueHandle>, F: Handle<Target=FenceHandle> {
+    pub fn bind_sparse(&self, bind_info: &[BindSparseInfo], fence: Option<FenceHandle>)
+            -> VdResult<()> {
         unsafe { self.device.queue_bind_sparse(self.handle, bind_info, fence) }
     }
 
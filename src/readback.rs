@@ -0,0 +1,223 @@
+use std::cmp;
+use ::{VdResult, Device, Queue, Image, Buffer, Fence, Format, ImageLayout, ImageTiling,
+    ImageSubresource, ImageSubresourceLayers, ImageSubresourceRange, ImageAspectFlags,
+    MemoryBarrier, ImageMemoryBarrier, BufferMemoryBarrier, BufferImageCopy, BufferCreateInfo, BufferUsageFlags,
+    SharingMode, MemoryPropertyFlags, MemoryTypeSelector, Offset3d, Extent3d, SubmitInfo,
+    CommandBufferHandle, FenceCreateFlags, AccessFlags, PipelineStageFlags, DependencyFlags,
+    Component, DeviceSize, SampleCountFlags, CommandBuffer, ErrorKind, QUEUE_FAMILY_IGNORED};
+use alloc::Allocation;
+use upload;
+
+
+fn invalid(reason: &'static str) -> ::Error {
+    ErrorKind::InvalidReadback { reason }.into()
+}
+
+/// Returns the size in bytes of a texel block of `aspect` of `format` when
+/// copied to a buffer.
+fn aspect_block_size(format: Format, aspect: ImageAspectFlags) -> u32 {
+    if aspect == ImageAspectFlags::STENCIL {
+        1
+    } else if aspect == ImageAspectFlags::DEPTH {
+        // Packed (`X8D24`) and combined 24-bit depth occupies four bytes:
+        let bits = format.components().iter().find(|c| c.component() == Component::Depth)
+            .map(|c| c.bits()).unwrap_or(0);
+        if bits <= 16 { 2 } else { 4 }
+    } else {
+        format.block_size()
+    }
+}
+
+/// Returns the extent of `subresource` of `image`.
+fn subresource_extent(image: &Image, subresource: &ImageSubresource) -> [u32; 3] {
+    let extent = image.extent();
+    let level = subresource.mip_level();
+    [cmp::max(extent.width() >> level, 1), cmp::max(extent.height() >> level, 1),
+        cmp::max(extent.depth() >> level, 1)]
+}
+
+/// Returns the number of texel block rows and slices and the size of a
+/// tightly packed row in bytes.
+fn packed_layout(format: Format, aspect: ImageAspectFlags, extent: [u32; 3]) -> (usize, usize, usize) {
+    let [block_width, block_height, block_depth] = format.block_extent();
+    let blocks = |texels: u32, block: u32| ((texels + block - 1) / block) as usize;
+    let row_size = blocks(extent[0], block_width) * aspect_block_size(format, aspect) as usize;
+    (blocks(extent[1], block_height), blocks(extent[2], block_depth), row_size)
+}
+
+/// Ends, submits, and waits for the completion of `command_buffer`.
+fn submit_and_wait(device: &Device, queue: &Queue, command_buffer: CommandBuffer)
+        -> VdResult<()> {
+    command_buffer.end()?;
+
+    let fence = Fence::new(device.clone(), FenceCreateFlags::empty())?;
+    let command_buffers: [CommandBufferHandle; 1] = [command_buffer.handle()];
+    let submit_info = SubmitInfo::builder()
+        .command_buffers(&command_buffers[..])
+        .build();
+    queue.submit(&[submit_info], Some(fence.handle()))?;
+    unsafe { device.wait_for_fences(&[fence.handle()], true, u64::max_value())?; }
+    Ok(())
+}
+
+/// Reads `subresource` of `image` to a vector. See `Image::read_to_vec`.
+pub(crate) fn read_to_vec(image: &Image, queue: &Queue, layout: ImageLayout,
+        subresource: &ImageSubresource) -> VdResult<Vec<u8>> {
+    if image.samples() != SampleCountFlags::COUNT_1 {
+        return Err(invalid("multisampled images must be resolved before being read"));
+    }
+    let aspect = subresource.aspect_mask();
+    if !image.format().aspects().contains(aspect) || aspect.bits().count_ones() != 1 {
+        return Err(invalid("the aspect mask must contain exactly one aspect of the image's format"));
+    }
+    if subresource.mip_level() >= image.mip_levels() ||
+            subresource.array_layer() >= image.array_layers() {
+        return Err(invalid("subresource out of range"));
+    }
+
+    let host_visible = image.allocation().map(|allocation| {
+        let memory_types = image.device().physical_device().memory_properties();
+        memory_types.memory_types()[allocation.memory_type_index() as usize].property_flags()
+            .contains(MemoryPropertyFlags::HOST_VISIBLE)
+    }).unwrap_or(false);
+
+    if image.tiling() == ImageTiling::Linear && host_visible &&
+            (layout == ImageLayout::General || layout == ImageLayout::Preinitialized) {
+        read_mapped(image, queue, subresource)
+    } else {
+        read_staged(image, queue, layout, subresource)
+    }
+}
+
+/// Reads a linear, host-visible image directly from its memory.
+fn read_mapped(image: &Image, queue: &Queue, subresource: &ImageSubresource)
+        -> VdResult<Vec<u8>> {
+    // Make prior device writes visible to the host. A global barrier is used
+    // since no layout transition is wanted (and `Preinitialized` is not a
+    // valid new layout):
+    let device = image.device();
+    let command_buffer = upload::begin_commands(device, queue.family_index())?;
+    let barrier = MemoryBarrier::builder()
+        .src_access_mask(AccessFlags::MEMORY_WRITE)
+        .dst_access_mask(AccessFlags::HOST_READ)
+        .build();
+    command_buffer.pipeline_barrier(PipelineStageFlags::ALL_COMMANDS, PipelineStageFlags::HOST,
+        DependencyFlags::empty(), &[barrier], &[], &[]);
+    submit_and_wait(device, queue, command_buffer)?;
+
+    let allocation = image.allocation().expect("image has no allocation");
+    let subresource_layout = unsafe {
+        image.device().get_image_subresource_layout(image, subresource)
+    };
+    let extent = subresource_extent(image, subresource);
+    let (rows, slices, row_size) = packed_layout(image.format(), subresource.aspect_mask(), extent);
+
+    let mapping = allocation.memory().persistent_mapping()?;
+    let base = allocation.offset() + subresource_layout.offset();
+    let mut pixels = Vec::with_capacity(rows * slices * row_size);
+    for slice in 0..slices {
+        for row in 0..rows {
+            let start = base + slice as DeviceSize * subresource_layout.depth_pitch() +
+                row as DeviceSize * subresource_layout.row_pitch();
+            pixels.extend(mapping.read::<u8>(start..start + row_size as DeviceSize)?);
+        }
+    }
+    Ok(pixels)
+}
+
+/// Reads an image by copying it to a host-visible staging buffer.
+fn read_staged(image: &Image, queue: &Queue, layout: ImageLayout,
+        subresource: &ImageSubresource) -> VdResult<Vec<u8>> {
+    let device = image.device();
+    let aspect = subresource.aspect_mask();
+    let extent = subresource_extent(image, subresource);
+    let (rows, slices, row_size) = packed_layout(image.format(), aspect, extent);
+    let size = (rows * slices * row_size) as DeviceSize;
+
+    let buffer = readback_buffer(device, size)?;
+
+    let range = ImageSubresourceRange::builder()
+        .aspect_mask(aspect)
+        .base_mip_level(subresource.mip_level())
+        .level_count(1)
+        .base_array_layer(subresource.array_layer())
+        .layer_count(1)
+        .build();
+    let layout_barrier = |old_layout, new_layout, src_access_mask, dst_access_mask| {
+        ImageMemoryBarrier::builder()
+            .src_access_mask(src_access_mask)
+            .dst_access_mask(dst_access_mask)
+            .old_layout(old_layout)
+            .new_layout(new_layout)
+            .src_queue_family_index(QUEUE_FAMILY_IGNORED)
+            .dst_queue_family_index(QUEUE_FAMILY_IGNORED)
+            .image(image)
+            .subresource_range(range.clone())
+            .build()
+    };
+    // The layout can not be restored to `Undefined` or `Preinitialized`:
+    let restored_layout = match layout {
+        ImageLayout::Undefined | ImageLayout::Preinitialized => ImageLayout::TransferSrcOptimal,
+        layout => layout,
+    };
+
+    let command_buffer = upload::begin_commands(device, queue.family_index())?;
+    command_buffer.pipeline_barrier(PipelineStageFlags::ALL_COMMANDS,
+        PipelineStageFlags::TRANSFER, DependencyFlags::empty(), &[], &[],
+        &[layout_barrier(layout, ImageLayout::TransferSrcOptimal, AccessFlags::MEMORY_WRITE,
+            AccessFlags::TRANSFER_READ)]);
+
+    let region = BufferImageCopy::builder()
+        .buffer_offset(0)
+        .buffer_row_length(0)
+        .buffer_image_height(0)
+        .image_subresource(ImageSubresourceLayers::builder()
+            .aspect_mask(aspect)
+            .mip_level(subresource.mip_level())
+            .base_array_layer(subresource.array_layer())
+            .layer_count(1)
+            .build())
+        .image_offset(Offset3d::builder().x(0).y(0).z(0).build())
+        .image_extent(Extent3d::builder().width(extent[0]).height(extent[1]).depth(extent[2])
+            .build())
+        .build();
+    unsafe {
+        command_buffer.copy_image_to_buffer(image, ImageLayout::TransferSrcOptimal, &buffer,
+            &[region]);
+    }
+
+    let buffer_barrier = BufferMemoryBarrier::builder()
+        .src_access_mask(AccessFlags::TRANSFER_WRITE)
+        .dst_access_mask(AccessFlags::HOST_READ)
+        .src_queue_family_index(QUEUE_FAMILY_IGNORED)
+        .dst_queue_family_index(QUEUE_FAMILY_IGNORED)
+        .buffer(&buffer)
+        .offset(0)
+        .size(size)
+        .build();
+    command_buffer.pipeline_barrier(PipelineStageFlags::TRANSFER,
+        PipelineStageFlags::HOST | PipelineStageFlags::ALL_COMMANDS, DependencyFlags::empty(),
+        &[], &[buffer_barrier], &[layout_barrier(ImageLayout::TransferSrcOptimal,
+            restored_layout, AccessFlags::empty(), AccessFlags::MEMORY_READ)]);
+    submit_and_wait(device, queue, command_buffer)?;
+
+    let allocation = buffer.allocation().expect("readback buffer has no allocation");
+    let mapping = allocation.memory().persistent_mapping()?;
+    mapping.read::<u8>(allocation.offset()..allocation.offset() + size)
+}
+
+/// Creates a host-visible buffer of `size` bytes to copy into.
+pub(crate) fn readback_buffer(device: &Device, size: DeviceSize) -> VdResult<Buffer> {
+    let mut create_info = BufferCreateInfo::default();
+    create_info.set_size(size);
+    create_info.set_usage(BufferUsageFlags::TRANSFER_DST);
+    create_info.set_sharing_mode(SharingMode::Exclusive);
+
+    Buffer::with_allocation(device.clone(), &create_info, |requirements| {
+        let memory_type_index = MemoryTypeSelector::new()
+            .required(MemoryPropertyFlags::HOST_VISIBLE)
+            .preferred(MemoryPropertyFlags::HOST_CACHED | MemoryPropertyFlags::HOST_COHERENT)
+            .select(device, requirements.memory_type_bits())?;
+        Allocation::dedicated(device.clone(), requirements, memory_type_index)
+    })
+}
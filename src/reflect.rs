@@ -0,0 +1,1320 @@
+//! SPIR-V reflection.
+//!
+//! `ShaderReflection` parses the words of a SPIR-V module (as returned by
+//! `util::read_spir_v_file`) and extracts its entry points, descriptor
+//! bindings, push constant ranges, interface variables, specialization
+//! constants and workgroup size. Descriptor set layouts, pipeline layouts
+//! and vertex input attributes can then be derived from the shaders of a
+//! pipeline instead of being maintained by hand:
+//!
+//! ```ignore
+//! let vert = ShaderReflection::new(&vert_spirv)?;
+//! let frag = ShaderReflection::new(&frag_spirv)?;
+//! let (pipeline_layout, set_layouts) = reflect::pipeline_layout(&device, &[&vert, &frag])?;
+//! let (attributes, stride) = vert.vertex_input_attributes(0);
+//! ```
+//!
+//! Dynamic uniform and storage buffers can not be distinguished from their
+//! non-dynamic counterparts in SPIR-V and are reported as `UniformBuffer`
+//! and `StorageBuffer`.
+
+use std::borrow::Cow;
+use std::slice;
+use std::collections::{HashMap, BTreeMap};
+use smallvec::SmallVec;
+use ::{VdResult, Device, ErrorKind, DescriptorType, ShaderStageFlags, Format, PushConstantRange,
+    VertexInputAttributeDescription, DescriptorSetLayoutBinding, DescriptorSetLayout,
+    DescriptorSetLayoutHandle, PipelineLayout};
+
+
+/// The first word of every SPIR-V module.
+const MAGIC: u32 = 0x0723_0203;
+
+/// The number of words in a SPIR-V module header.
+const HEADER_LEN: usize = 5;
+
+const OP_NAME: u16 = 5;
+const OP_ENTRY_POINT: u16 = 15;
+const OP_EXECUTION_MODE: u16 = 16;
+const OP_TYPE_VOID: u16 = 19;
+const OP_TYPE_BOOL: u16 = 20;
+const OP_TYPE_INT: u16 = 21;
+const OP_TYPE_FLOAT: u16 = 22;
+const OP_TYPE_VECTOR: u16 = 23;
+const OP_TYPE_MATRIX: u16 = 24;
+const OP_TYPE_IMAGE: u16 = 25;
+const OP_TYPE_SAMPLER: u16 = 26;
+const OP_TYPE_SAMPLED_IMAGE: u16 = 27;
+const OP_TYPE_ARRAY: u16 = 28;
+const OP_TYPE_RUNTIME_ARRAY: u16 = 29;
+const OP_TYPE_STRUCT: u16 = 30;
+const OP_TYPE_POINTER: u16 = 32;
+const OP_CONSTANT_TRUE: u16 = 41;
+const OP_CONSTANT_FALSE: u16 = 42;
+const OP_CONSTANT: u16 = 43;
+const OP_CONSTANT_COMPOSITE: u16 = 44;
+const OP_SPEC_CONSTANT_TRUE: u16 = 48;
+const OP_SPEC_CONSTANT_FALSE: u16 = 49;
+const OP_SPEC_CONSTANT: u16 = 50;
+const OP_SPEC_CONSTANT_COMPOSITE: u16 = 51;
+const OP_VARIABLE: u16 = 59;
+const OP_DECORATE: u16 = 71;
+const OP_MEMBER_DECORATE: u16 = 72;
+
+const DECORATION_SPEC_ID: u32 = 1;
+const DECORATION_BUFFER_BLOCK: u32 = 3;
+const DECORATION_ARRAY_STRIDE: u32 = 6;
+const DECORATION_MATRIX_STRIDE: u32 = 7;
+const DECORATION_BUILT_IN: u32 = 11;
+const DECORATION_LOCATION: u32 = 30;
+const DECORATION_BINDING: u32 = 33;
+const DECORATION_DESCRIPTOR_SET: u32 = 34;
+const DECORATION_OFFSET: u32 = 35;
+
+const STORAGE_CLASS_UNIFORM_CONSTANT: u32 = 0;
+const STORAGE_CLASS_INPUT: u32 = 1;
+const STORAGE_CLASS_UNIFORM: u32 = 2;
+const STORAGE_CLASS_OUTPUT: u32 = 3;
+const STORAGE_CLASS_PUSH_CONSTANT: u32 = 9;
+const STORAGE_CLASS_STORAGE_BUFFER: u32 = 12;
+
+const EXECUTION_MODE_LOCAL_SIZE: u32 = 17;
+
+/// The deepest nesting of arrays, structs and vectors that is reflected.
+const MAX_TYPE_DEPTH: u32 = 64;
+
+/// The number of interface locations that is reflected, more than any
+/// implementation's interface component limits allow.
+const MAX_LOCATIONS: u32 = 256;
+
+/// The number of descriptor sets that can be merged, more than any
+/// implementation's `maxBoundDescriptorSets`.
+const MAX_DESCRIPTOR_SETS: u32 = 32;
+const BUILT_IN_WORKGROUP_SIZE: u32 = 25;
+
+const DIM_BUFFER: u32 = 5;
+const DIM_SUBPASS_DATA: u32 = 6;
+
+
+fn invalid(reason: &'static str) -> ::Error {
+    ErrorKind::InvalidSpirV { reason }.into()
+}
+
+/// Multiplies sizes or counts declared by a module, which may overflow.
+fn mul(a: u32, b: u32) -> VdResult<u32> {
+    a.checked_mul(b).ok_or_else(|| invalid("size or count overflows 32 bits"))
+}
+
+/// Adds sizes or offsets declared by a module, which may overflow.
+fn add(a: u32, b: u32) -> VdResult<u32> {
+    a.checked_add(b).ok_or_else(|| invalid("size or count overflows 32 bits"))
+}
+
+/// Returns the words of `spirv` in host byte order, checking the magic
+/// number, version and ID bound of its header.
+pub(crate) fn host_order_words(spirv: &[u32]) -> VdResult<Cow<'_, [u32]>> {
+    if spirv.len() < HEADER_LEN {
+        return Err(invalid("module is smaller than the SPIR-V header"));
+    }
+    let words = if spirv[0] == MAGIC {
+        Cow::Borrowed(spirv)
+    } else if spirv[0] == MAGIC.swap_bytes() {
+        Cow::Owned(spirv.iter().map(|word| word.swap_bytes()).collect())
+    } else {
+        return Err(invalid("invalid magic number"));
+    };
+
+    // The version is laid out as 0x00MMmm00:
+    let version = words[1];
+    if version & 0xff0000ff != 0 || (version >> 16) & 0xff != 1 {
+        return Err(invalid("unsupported SPIR-V version"));
+    }
+    if words[3] == 0 {
+        return Err(invalid("ID bound is zero"));
+    }
+    Ok(words)
+}
+
+/// Decodes a nul-terminated literal string.
+fn parse_string(words: &[u32]) -> VdResult<String> {
+    let mut bytes = Vec::with_capacity(words.len() * 4);
+    for &word in words {
+        for shift in &[0, 8, 16, 24] {
+            let byte = (word >> shift) as u8;
+            if byte == 0 {
+                return String::from_utf8(bytes).map_err(|_| invalid("string is not valid UTF-8"));
+            }
+            bytes.push(byte);
+        }
+    }
+    Err(invalid("unterminated string"))
+}
+
+/// Returns the number of words occupied by a literal string.
+fn string_word_count(words: &[u32]) -> usize {
+    words.iter().position(|&word| word >> 24 == 0).map(|i| i + 1).unwrap_or(words.len())
+}
+
+/// Returns the instructions following the header of `words`, as opcodes and
+/// operands.
+fn instructions(words: &[u32]) -> VdResult<Vec<(u16, &[u32])>> {
+    let mut instructions = Vec::new();
+    let mut i = HEADER_LEN;
+    while i < words.len() {
+        let word_count = (words[i] >> 16) as usize;
+        if word_count == 0 || i + word_count > words.len() {
+            return Err(invalid("invalid instruction word count"));
+        }
+        instructions.push((words[i] as u16, &words[i + 1..i + word_count]));
+        i += word_count;
+    }
+    Ok(instructions)
+}
+
+/// Returns the names of the entry points declared by the host order `words`
+/// of a module.
+pub(crate) fn entry_point_names(words: &[u32]) -> VdResult<Vec<String>> {
+    let mut names = Vec::new();
+    for (opcode, operands) in instructions(words)? {
+        if opcode == OP_ENTRY_POINT {
+            if operands.len() < 3 {
+                return Err(invalid("instruction has too few operands"));
+            }
+            names.push(parse_string(&operands[2..])?);
+        }
+    }
+    Ok(names)
+}
+
+
+/// The execution model of an entry point.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum ExecutionModel {
+    Vertex,
+    TessellationControl,
+    TessellationEvaluation,
+    Geometry,
+    Fragment,
+    GlCompute,
+    Kernel,
+    Other(u32),
+}
+
+impl ExecutionModel {
+    fn from_word(word: u32) -> ExecutionModel {
+        match word {
+            0 => ExecutionModel::Vertex,
+            1 => ExecutionModel::TessellationControl,
+            2 => ExecutionModel::TessellationEvaluation,
+            3 => ExecutionModel::Geometry,
+            4 => ExecutionModel::Fragment,
+            5 => ExecutionModel::GlCompute,
+            6 => ExecutionModel::Kernel,
+            other => ExecutionModel::Other(other),
+        }
+    }
+
+    /// Returns the shader stage corresponding to this execution model.
+    pub fn stage(&self) -> ShaderStageFlags {
+        match *self {
+            ExecutionModel::Vertex => ShaderStageFlags::VERTEX,
+            ExecutionModel::TessellationControl => ShaderStageFlags::TESSELLATION_CONTROL,
+            ExecutionModel::TessellationEvaluation => ShaderStageFlags::TESSELLATION_EVALUATION,
+            ExecutionModel::Geometry => ShaderStageFlags::GEOMETRY,
+            ExecutionModel::Fragment => ShaderStageFlags::FRAGMENT,
+            ExecutionModel::GlCompute | ExecutionModel::Kernel => ShaderStageFlags::COMPUTE,
+            ExecutionModel::Other(_) => ShaderStageFlags::empty(),
+        }
+    }
+}
+
+
+/// The type of a scalar value such as a specialization constant.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum ScalarType {
+    Bool,
+    Int { width: u32, signed: bool },
+    Float { width: u32 },
+}
+
+impl ScalarType {
+    /// Returns the size of a value of this type in bytes. Booleans are
+    /// 32-bit, as they are in specialization data.
+    pub fn size(&self) -> u32 {
+        match *self {
+            ScalarType::Bool => 4,
+            ScalarType::Int { width, .. } | ScalarType::Float { width } => width / 8,
+        }
+    }
+
+    /// Returns the format of a vector of `component_count` values of this
+    /// type, if there is one.
+    pub fn format(&self, component_count: u32) -> Option<Format> {
+        use ::Format::*;
+        let formats = match *self {
+            ScalarType::Int { width: 8, signed: false } =>
+                [R8Uint, R8G8Uint, R8G8B8Uint, R8G8B8A8Uint],
+            ScalarType::Int { width: 8, signed: true } =>
+                [R8Sint, R8G8Sint, R8G8B8Sint, R8G8B8A8Sint],
+            ScalarType::Int { width: 16, signed: false } =>
+                [R16Uint, R16G16Uint, R16G16B16Uint, R16G16B16A16Uint],
+            ScalarType::Int { width: 16, signed: true } =>
+                [R16Sint, R16G16Sint, R16G16B16Sint, R16G16B16A16Sint],
+            ScalarType::Int { width: 32, signed: false } =>
+                [R32Uint, R32G32Uint, R32G32B32Uint, R32G32B32A32Uint],
+            ScalarType::Int { width: 32, signed: true } =>
+                [R32Sint, R32G32Sint, R32G32B32Sint, R32G32B32A32Sint],
+            ScalarType::Int { width: 64, signed: false } =>
+                [R64Uint, R64G64Uint, R64G64B64Uint, R64G64B64A64Uint],
+            ScalarType::Int { width: 64, signed: true } =>
+                [R64Sint, R64G64Sint, R64G64B64Sint, R64G64B64A64Sint],
+            ScalarType::Float { width: 16 } =>
+                [R16Sfloat, R16G16Sfloat, R16G16B16Sfloat, R16G16B16A16Sfloat],
+            ScalarType::Float { width: 32 } =>
+                [R32Sfloat, R32G32Sfloat, R32G32B32Sfloat, R32G32B32A32Sfloat],
+            ScalarType::Float { width: 64 } =>
+                [R64Sfloat, R64G64Sfloat, R64G64B64Sfloat, R64G64B64A64Sfloat],
+            _ => return None,
+        };
+        if component_count >= 1 && component_count <= 4 {
+            Some(formats[component_count as usize - 1])
+        } else {
+            None
+        }
+    }
+}
+
+
+/// An input or output variable of an entry point with an explicit location.
+///
+/// Matrices and arrays occupy consecutive locations and are reported as one
+/// variable per location.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InterfaceVariable {
+    pub name: Option<String>,
+    pub location: u32,
+    pub format: Format,
+}
+
+
+/// A resource bound to a descriptor set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DescriptorBinding {
+    pub name: Option<String>,
+    pub set: u32,
+    pub binding: u32,
+    pub descriptor_type: DescriptorType,
+    /// The number of descriptors, which is zero for runtime-sized arrays.
+    pub descriptor_count: u32,
+    pub stage_flags: ShaderStageFlags,
+}
+
+impl DescriptorBinding {
+    /// Returns a `DescriptorSetLayoutBinding` for this binding.
+    ///
+    /// Returns an error for runtime-sized arrays, whose size must be chosen
+    /// by the application.
+    pub fn layout_binding<'b>(&self) -> VdResult<DescriptorSetLayoutBinding<'b>> {
+        if self.descriptor_count == 0 {
+            return Err(format!("descriptor binding {} of set {} is a runtime-sized array and \
+                requires an explicit descriptor count", self.binding, self.set).into());
+        }
+        Ok(DescriptorSetLayoutBinding::builder()
+            .binding(self.binding)
+            .descriptor_type(self.descriptor_type)
+            .descriptor_count(self.descriptor_count)
+            .stage_flags(self.stage_flags)
+            .build())
+    }
+}
+
+
+/// A specialization constant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpecConstant {
+    pub name: Option<String>,
+    /// The constant ID, assigned by the `constant_id` layout qualifier.
+    pub id: u32,
+    pub scalar_type: ScalarType,
+    /// The bits of the default value, zero-extended to 64 bits.
+    pub default_value: u64,
+}
+
+
+/// An entry point of a shader module.
+#[derive(Debug, Clone)]
+pub struct EntryPoint {
+    name: String,
+    execution_model: ExecutionModel,
+    workgroup_size: Option<[u32; 3]>,
+    inputs: Vec<InterfaceVariable>,
+    outputs: Vec<InterfaceVariable>,
+}
+
+impl EntryPoint {
+    /// Returns the name of this entry point.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the execution model of this entry point.
+    pub fn execution_model(&self) -> ExecutionModel {
+        self.execution_model
+    }
+
+    /// Returns the shader stage of this entry point.
+    pub fn stage(&self) -> ShaderStageFlags {
+        self.execution_model.stage()
+    }
+
+    /// Returns the local workgroup size of a compute entry point.
+    pub fn workgroup_size(&self) -> Option<[u32; 3]> {
+        self.workgroup_size
+    }
+
+    /// Returns the input variables of this entry point, sorted by location.
+    /// For a vertex shader these are its vertex attributes.
+    pub fn inputs(&self) -> &[InterfaceVariable] {
+        &self.inputs
+    }
+
+    /// Returns the output variables of this entry point, sorted by location.
+    /// For a fragment shader these are its color attachment outputs.
+    pub fn outputs(&self) -> &[InterfaceVariable] {
+        &self.outputs
+    }
+}
+
+
+#[derive(Debug, Clone)]
+enum Type {
+    Scalar(ScalarType),
+    Vector { component: u32, count: u32 },
+    Matrix { column: u32, count: u32 },
+    Image { dim: u32, sampled: u32 },
+    Sampler,
+    SampledImage { image: u32 },
+    Array { element: u32, length: u32 },
+    RuntimeArray { element: u32 },
+    Struct { members: Vec<u32> },
+    Pointer { pointee: u32 },
+    Other,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Decorations {
+    spec_id: Option<u32>,
+    buffer_block: bool,
+    array_stride: Option<u32>,
+    built_in: Option<u32>,
+    location: Option<u32>,
+    binding: Option<u32>,
+    set: Option<u32>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct MemberDecorations {
+    offset: Option<u32>,
+    matrix_stride: Option<u32>,
+}
+
+#[derive(Debug, Clone)]
+struct Constant {
+    result_type: u32,
+    value: u64,
+    spec: bool,
+}
+
+/// The state gathered from the instructions of a module.
+#[derive(Debug, Default)]
+struct Module {
+    names: HashMap<u32, String>,
+    decorations: HashMap<u32, Decorations>,
+    member_decorations: HashMap<(u32, u32), MemberDecorations>,
+    types: HashMap<u32, Type>,
+    constants: HashMap<u32, Constant>,
+    composites: HashMap<u32, Vec<u32>>,
+    variables: Vec<(u32, u32, u32)>,
+    entry_points: Vec<(ExecutionModel, u32, String, Vec<u32>)>,
+    local_sizes: HashMap<u32, [u32; 3]>,
+}
+
+impl Module {
+    fn parse(words: &[u32]) -> VdResult<Module> {
+        let mut module = Module::default();
+        for (opcode, operands) in instructions(words)? {
+            module.parse_instruction(opcode, operands)?;
+        }
+        Ok(module)
+    }
+
+    fn parse_instruction(&mut self, opcode: u16, ops: &[u32]) -> VdResult<()> {
+        let min_len = match opcode {
+            OP_ENTRY_POINT | OP_MEMBER_DECORATE | OP_VARIABLE | OP_TYPE_INT | OP_TYPE_VECTOR |
+                OP_TYPE_MATRIX | OP_TYPE_ARRAY | OP_TYPE_POINTER | OP_CONSTANT |
+                OP_SPEC_CONSTANT => 3,
+            OP_NAME | OP_DECORATE | OP_EXECUTION_MODE | OP_TYPE_FLOAT | OP_TYPE_RUNTIME_ARRAY |
+                OP_TYPE_SAMPLED_IMAGE | OP_CONSTANT_TRUE | OP_CONSTANT_FALSE |
+                OP_SPEC_CONSTANT_TRUE | OP_SPEC_CONSTANT_FALSE | OP_CONSTANT_COMPOSITE |
+                OP_SPEC_CONSTANT_COMPOSITE => 2,
+            OP_TYPE_IMAGE => 8,
+            OP_TYPE_VOID | OP_TYPE_BOOL | OP_TYPE_SAMPLER | OP_TYPE_STRUCT => 1,
+            _ => 0,
+        };
+        if ops.len() < min_len {
+            return Err(invalid("instruction has too few operands"));
+        }
+
+        match opcode {
+            OP_NAME => { self.names.insert(ops[0], parse_string(&ops[1..])?); },
+            OP_ENTRY_POINT => {
+                let name_len = string_word_count(&ops[2..]);
+                let name = parse_string(&ops[2..])?;
+                self.entry_points.push((ExecutionModel::from_word(ops[0]), ops[1], name,
+                    ops[2 + name_len..].to_vec()));
+            },
+            OP_EXECUTION_MODE => {
+                if ops[1] == EXECUTION_MODE_LOCAL_SIZE && ops.len() >= 5 {
+                    self.local_sizes.insert(ops[0], [ops[2], ops[3], ops[4]]);
+                }
+            },
+            OP_DECORATE => {
+                let decorations = self.decorations.entry(ops[0]).or_insert_with(Default::default);
+                let value = ops.get(2).cloned();
+                match ops[1] {
+                    DECORATION_SPEC_ID => decorations.spec_id = value,
+                    DECORATION_BUFFER_BLOCK => decorations.buffer_block = true,
+                    DECORATION_ARRAY_STRIDE => decorations.array_stride = value,
+                    DECORATION_BUILT_IN => decorations.built_in = value,
+                    DECORATION_LOCATION => decorations.location = value,
+                    DECORATION_BINDING => decorations.binding = value,
+                    DECORATION_DESCRIPTOR_SET => decorations.set = value,
+                    _ => (),
+                }
+            },
+            OP_MEMBER_DECORATE => {
+                let decorations = self.member_decorations.entry((ops[0], ops[1]))
+                    .or_insert_with(Default::default);
+                let value = ops.get(3).cloned();
+                match ops[2] {
+                    DECORATION_OFFSET => decorations.offset = value,
+                    DECORATION_MATRIX_STRIDE => decorations.matrix_stride = value,
+                    _ => (),
+                }
+            },
+            OP_TYPE_VOID => self.define_type(ops[0], Type::Other)?,
+            OP_TYPE_BOOL => self.define_type(ops[0], Type::Scalar(ScalarType::Bool))?,
+            OP_TYPE_INT => {
+                self.define_type(ops[0],
+                    Type::Scalar(ScalarType::Int { width: ops[1], signed: ops[2] != 0 }))?;
+            },
+            OP_TYPE_FLOAT => {
+                self.define_type(ops[0], Type::Scalar(ScalarType::Float { width: ops[1] }))?;
+            },
+            OP_TYPE_VECTOR => {
+                self.define_type(ops[0], Type::Vector { component: ops[1], count: ops[2] })?;
+            },
+            OP_TYPE_MATRIX => {
+                self.define_type(ops[0], Type::Matrix { column: ops[1], count: ops[2] })?;
+            },
+            OP_TYPE_IMAGE => {
+                self.define_type(ops[0], Type::Image { dim: ops[2], sampled: ops[6] })?;
+            },
+            OP_TYPE_SAMPLER => self.define_type(ops[0], Type::Sampler)?,
+            OP_TYPE_SAMPLED_IMAGE => {
+                self.define_type(ops[0], Type::SampledImage { image: ops[1] })?;
+            },
+            OP_TYPE_ARRAY => {
+                self.define_type(ops[0], Type::Array { element: ops[1], length: ops[2] })?;
+            },
+            OP_TYPE_RUNTIME_ARRAY => {
+                self.define_type(ops[0], Type::RuntimeArray { element: ops[1] })?;
+            },
+            OP_TYPE_STRUCT => {
+                self.define_type(ops[0], Type::Struct { members: ops[1..].to_vec() })?;
+            },
+            OP_TYPE_POINTER => self.define_type(ops[0], Type::Pointer { pointee: ops[2] })?,
+            OP_CONSTANT | OP_SPEC_CONSTANT => {
+                let value = ops[2] as u64 | ops.get(3).map(|&hi| (hi as u64) << 32).unwrap_or(0);
+                self.constants.insert(ops[1], Constant { result_type: ops[0], value,
+                    spec: opcode == OP_SPEC_CONSTANT });
+            },
+            OP_CONSTANT_TRUE | OP_CONSTANT_FALSE | OP_SPEC_CONSTANT_TRUE |
+                    OP_SPEC_CONSTANT_FALSE => {
+                let value = (opcode == OP_CONSTANT_TRUE || opcode == OP_SPEC_CONSTANT_TRUE) as u64;
+                let spec = opcode == OP_SPEC_CONSTANT_TRUE || opcode == OP_SPEC_CONSTANT_FALSE;
+                self.constants.insert(ops[1], Constant { result_type: ops[0], value, spec });
+            },
+            OP_CONSTANT_COMPOSITE | OP_SPEC_CONSTANT_COMPOSITE => {
+                self.composites.insert(ops[1], ops[2..].to_vec());
+            },
+            OP_VARIABLE => { self.variables.push((ops[1], ops[0], ops[2])); },
+            _ => (),
+        }
+        Ok(())
+    }
+
+    /// Defines type `id`, checking that the types it is made up of are
+    /// already defined so that types can not contain themselves. Pointees
+    /// are exempt, as pointers may be forward declared.
+    fn define_type(&mut self, id: u32, ty: Type) -> VdResult<()> {
+        if self.types.contains_key(&id) {
+            return Err(invalid("type is defined more than once"));
+        }
+        let constituents = match ty {
+            Type::Vector { ref component, .. } => slice::from_ref(component),
+            Type::Matrix { ref column, .. } => slice::from_ref(column),
+            Type::SampledImage { ref image } => slice::from_ref(image),
+            Type::Array { ref element, .. } | Type::RuntimeArray { ref element } =>
+                slice::from_ref(element),
+            Type::Struct { ref members } => &members[..],
+            _ => &[],
+        };
+        if constituents.iter().any(|constituent| !self.types.contains_key(constituent)) {
+            return Err(invalid("type refers to a type which is not yet defined"));
+        }
+        self.types.insert(id, ty);
+        Ok(())
+    }
+
+    fn decorations(&self, id: u32) -> Decorations {
+        self.decorations.get(&id).cloned().unwrap_or_default()
+    }
+
+    fn ty(&self, id: u32) -> VdResult<&Type> {
+        self.types.get(&id).ok_or_else(|| invalid("reference to an undefined type"))
+    }
+
+    fn constant_value(&self, id: u32) -> VdResult<u32> {
+        self.constants.get(&id).map(|constant| constant.value as u32)
+            .ok_or_else(|| invalid("reference to an undefined constant"))
+    }
+
+    fn scalar_type(&self, id: u32) -> VdResult<Option<ScalarType>> {
+        match *self.ty(id)? {
+            Type::Scalar(scalar_type) => Ok(Some(scalar_type)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns the scalar type and component count of a scalar or vector.
+    fn vector_type(&self, id: u32) -> VdResult<Option<(ScalarType, u32)>> {
+        match *self.ty(id)? {
+            Type::Scalar(scalar_type) => Ok(Some((scalar_type, 1))),
+            Type::Vector { component, count } =>
+                Ok(self.scalar_type(component)?.map(|scalar_type| (scalar_type, count))),
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns the size in bytes of a type laid out with explicit offsets
+    /// and strides.
+    fn size(&self, id: u32, matrix_stride: Option<u32>) -> VdResult<u32> {
+        self.nested_size(id, matrix_stride, 0)
+    }
+
+    fn nested_size(&self, id: u32, matrix_stride: Option<u32>, depth: u32) -> VdResult<u32> {
+        if depth > MAX_TYPE_DEPTH {
+            return Err(invalid("types are nested too deeply"));
+        }
+        let depth = depth + 1;
+        Ok(match *self.ty(id)? {
+            Type::Scalar(scalar_type) => scalar_type.size(),
+            Type::Vector { component, count } =>
+                mul(count, self.nested_size(component, None, depth)?)?,
+            Type::Matrix { column, count } => {
+                let stride = match matrix_stride {
+                    Some(stride) => stride,
+                    None => self.nested_size(column, None, depth)?,
+                };
+                mul(count, stride)?
+            },
+            Type::Array { element, length } => {
+                let stride = match self.decorations(id).array_stride {
+                    Some(stride) => stride,
+                    None => self.nested_size(element, matrix_stride, depth)?,
+                };
+                mul(self.constant_value(length)?, stride)?
+            },
+            Type::Struct { ref members } => {
+                let mut size = 0;
+                for (i, &member) in members.iter().enumerate() {
+                    let decorations = self.member_decorations.get(&(id, i as u32))
+                        .cloned().unwrap_or_default();
+                    let end = add(decorations.offset.unwrap_or(0),
+                        self.nested_size(member, decorations.matrix_stride, depth)?)?;
+                    size = size.max(end);
+                }
+                size
+            },
+            _ => 0,
+        })
+    }
+
+    /// Returns the descriptor type and count of a resource variable's type.
+    fn descriptor_type(&self, id: u32, storage_class: u32)
+            -> VdResult<Option<(DescriptorType, u32)>> {
+        let mut id = id;
+        let mut count = 1;
+        for depth in 0.. {
+            if depth > MAX_TYPE_DEPTH {
+                return Err(invalid("types are nested too deeply"));
+            }
+            match *self.ty(id)? {
+                Type::Array { element, length } => {
+                    count = mul(count, self.constant_value(length)?)?;
+                    id = element;
+                },
+                Type::RuntimeArray { element } => {
+                    count = 0;
+                    id = element;
+                },
+                _ => break,
+            }
+        }
+
+        let image_type = |dim, sampled| match (dim, sampled) {
+            (DIM_SUBPASS_DATA, _) => DescriptorType::InputAttachment,
+            (DIM_BUFFER, 2) => DescriptorType::StorageTexelBuffer,
+            (DIM_BUFFER, _) => DescriptorType::UniformTexelBuffer,
+            (_, 2) => DescriptorType::StorageImage,
+            _ => DescriptorType::SampledImage,
+        };
+
+        let descriptor_type = match (storage_class, self.ty(id)?) {
+            (STORAGE_CLASS_UNIFORM_CONSTANT, &Type::Sampler) => DescriptorType::Sampler,
+            (STORAGE_CLASS_UNIFORM_CONSTANT, &Type::Image { dim, sampled }) =>
+                image_type(dim, sampled),
+            (STORAGE_CLASS_UNIFORM_CONSTANT, &Type::SampledImage { image }) => {
+                match *self.ty(image)? {
+                    Type::Image { dim: DIM_BUFFER, .. } => DescriptorType::UniformTexelBuffer,
+                    _ => DescriptorType::CombinedImageSampler,
+                }
+            },
+            (STORAGE_CLASS_UNIFORM, &Type::Struct { .. }) => {
+                if self.decorations(id).buffer_block {
+                    DescriptorType::StorageBuffer
+                } else {
+                    DescriptorType::UniformBuffer
+                }
+            },
+            (STORAGE_CLASS_STORAGE_BUFFER, &Type::Struct { .. }) => DescriptorType::StorageBuffer,
+            _ => return Ok(None),
+        };
+        Ok(Some((descriptor_type, count)))
+    }
+
+    /// Returns the interface variables of the `Input` or `Output` variable
+    /// `id`, one per location.
+    fn interface_variables(&self, id: u32, pointer_type: u32)
+            -> VdResult<SmallVec<[InterfaceVariable; 4]>> {
+        let mut variables = SmallVec::new();
+        let decorations = self.decorations(id);
+        let location = match (decorations.location, decorations.built_in) {
+            (Some(location), None) => location,
+            _ => return Ok(variables),
+        };
+        let pointee = match *self.ty(pointer_type)? {
+            Type::Pointer { pointee } => pointee,
+            _ => return Err(invalid("variable is not of a pointer type")),
+        };
+
+        // Arrays and matrices occupy one location per element or column:
+        let (element, count) = match *self.ty(pointee)? {
+            Type::Array { element, length } => (element, self.constant_value(length)?),
+            Type::Matrix { column, count } => (column, count),
+            _ => (pointee, 1),
+        };
+        let (element, count) = match *self.ty(element)? {
+            Type::Matrix { column, count: columns } => (column, mul(count, columns)?),
+            _ => (element, count),
+        };
+
+        if let Some((scalar_type, component_count)) = self.vector_type(element)? {
+            if let Some(format) = scalar_type.format(component_count) {
+                // 64-bit three and four component vectors occupy two locations:
+                let stride = if scalar_type.size() == 8 && component_count > 2 { 2 } else { 1 };
+                if add(location, mul(count, stride)?)? > MAX_LOCATIONS {
+                    return Err(invalid("interface variable exceeds the location limit"));
+                }
+                for i in 0..count {
+                    variables.push(InterfaceVariable {
+                        name: self.names.get(&id).cloned(),
+                        location: add(location, mul(i, stride)?)?,
+                        format,
+                    });
+                }
+            }
+        }
+        Ok(variables)
+    }
+
+    /// Returns the workgroup size specified by a `WorkgroupSize` built-in
+    /// constant, which overrides the `LocalSize` execution mode.
+    fn workgroup_size_built_in(&self) -> VdResult<Option<[u32; 3]>> {
+        for (&id, constituents) in &self.composites {
+            if self.decorations(id).built_in == Some(BUILT_IN_WORKGROUP_SIZE) {
+                if constituents.len() != 3 {
+                    return Err(invalid("WorkgroupSize built-in is not a three component vector"));
+                }
+                return Ok(Some([self.constant_value(constituents[0])?,
+                    self.constant_value(constituents[1])?, self.constant_value(constituents[2])?]));
+            }
+        }
+        Ok(None)
+    }
+}
+
+
+/// The reflected interface of a SPIR-V shader module.
+///
+/// Descriptor bindings and push constant ranges are reported with the
+/// stages of all entry points in the module.
+#[derive(Debug, Clone)]
+pub struct ShaderReflection {
+    entry_points: Vec<EntryPoint>,
+    descriptor_bindings: Vec<DescriptorBinding>,
+    push_constant_ranges: Vec<PushConstantRange>,
+    spec_constants: Vec<SpecConstant>,
+}
+
+impl ShaderReflection {
+    /// Parses the words of a SPIR-V module, in either byte order.
+    ///
+    /// Returns an `InvalidSpirV` error if the module is malformed.
+    pub fn new(spirv: &[u32]) -> VdResult<ShaderReflection> {
+        let words = host_order_words(spirv)?;
+        let module = Module::parse(&words)?;
+        let workgroup_size = module.workgroup_size_built_in()?;
+
+        let mut entry_points = Vec::with_capacity(module.entry_points.len());
+        for &(execution_model, id, ref name, ref interface) in &module.entry_points {
+            let mut inputs = Vec::new();
+            let mut outputs = Vec::new();
+            for &variable in interface {
+                let &(_, pointer_type, storage_class) = match module.variables.iter()
+                        .find(|&&(variable_id, _, _)| variable_id == variable) {
+                    Some(v) => v,
+                    None => continue,
+                };
+                match storage_class {
+                    STORAGE_CLASS_INPUT => inputs.extend(
+                        module.interface_variables(variable, pointer_type)?),
+                    STORAGE_CLASS_OUTPUT => outputs.extend(
+                        module.interface_variables(variable, pointer_type)?),
+                    _ => (),
+                }
+            }
+            inputs.sort_by_key(|variable| variable.location);
+            outputs.sort_by_key(|variable| variable.location);
+
+            let workgroup_size = match execution_model {
+                ExecutionModel::GlCompute | ExecutionModel::Kernel =>
+                    workgroup_size.or_else(|| module.local_sizes.get(&id).cloned()),
+                _ => None,
+            };
+            entry_points.push(EntryPoint { name: name.clone(), execution_model, workgroup_size,
+                inputs, outputs });
+        }
+
+        let stage_flags = entry_points.iter()
+            .fold(ShaderStageFlags::empty(), |flags, entry_point| flags | entry_point.stage());
+
+        let mut descriptor_bindings = Vec::new();
+        let mut push_constant_ranges = Vec::new();
+        for &(id, pointer_type, storage_class) in &module.variables {
+            let pointee = match *module.ty(pointer_type)? {
+                Type::Pointer { pointee } => pointee,
+                _ => return Err(invalid("variable is not of a pointer type")),
+            };
+
+            if storage_class == STORAGE_CLASS_PUSH_CONSTANT {
+                let offset = match *module.ty(pointee)? {
+                    Type::Struct { ref members } => (0..members.len() as u32)
+                        .filter_map(|i| module.member_decorations.get(&(pointee, i)))
+                        .filter_map(|decorations| decorations.offset)
+                        .min()
+                        .unwrap_or(0),
+                    _ => 0,
+                };
+                let size = module.size(pointee, None)?;
+                if size > offset {
+                    push_constant_ranges.push(PushConstantRange::builder()
+                        .stage_flags(stage_flags)
+                        .offset(offset)
+                        .size(size - offset)
+                        .build());
+                }
+                continue;
+            }
+
+            let decorations = module.decorations(id);
+            let binding = match decorations.binding {
+                Some(binding) => binding,
+                None => continue,
+            };
+            if let Some((descriptor_type, descriptor_count)) =
+                    module.descriptor_type(pointee, storage_class)? {
+                descriptor_bindings.push(DescriptorBinding {
+                    name: module.names.get(&id).cloned(),
+                    set: decorations.set.unwrap_or(0),
+                    binding,
+                    descriptor_type,
+                    descriptor_count,
+                    stage_flags,
+                });
+            }
+        }
+        descriptor_bindings.sort_by_key(|binding| (binding.set, binding.binding));
+
+        let mut spec_constants = Vec::new();
+        for (&id, constant) in &module.constants {
+            if !constant.spec { continue; }
+            let spec_id = match module.decorations(id).spec_id {
+                Some(spec_id) => spec_id,
+                None => continue,
+            };
+            let scalar_type = module.scalar_type(constant.result_type)?
+                .ok_or_else(|| invalid("specialization constant is not a scalar"))?;
+            spec_constants.push(SpecConstant {
+                name: module.names.get(&id).cloned(),
+                id: spec_id,
+                scalar_type,
+                default_value: constant.value,
+            });
+        }
+        spec_constants.sort_by_key(|constant| constant.id);
+
+        Ok(ShaderReflection { entry_points, descriptor_bindings, push_constant_ranges,
+            spec_constants })
+    }
+
+    /// Returns the entry points of the module.
+    pub fn entry_points(&self) -> &[EntryPoint] {
+        &self.entry_points
+    }
+
+    /// Returns the entry point named `name`.
+    pub fn entry_point(&self, name: &str) -> Option<&EntryPoint> {
+        self.entry_points.iter().find(|entry_point| entry_point.name == name)
+    }
+
+    /// Returns the stages of all entry points in the module.
+    pub fn stage_flags(&self) -> ShaderStageFlags {
+        self.entry_points.iter()
+            .fold(ShaderStageFlags::empty(), |flags, entry_point| flags | entry_point.stage())
+    }
+
+    /// Returns the descriptor bindings of the module, sorted by set and
+    /// binding.
+    pub fn descriptor_bindings(&self) -> &[DescriptorBinding] {
+        &self.descriptor_bindings
+    }
+
+    /// Returns the ranges of the module's push constant blocks.
+    pub fn push_constant_ranges(&self) -> &[PushConstantRange] {
+        &self.push_constant_ranges
+    }
+
+    /// Returns the specialization constants of the module, sorted by ID.
+    pub fn spec_constants(&self) -> &[SpecConstant] {
+        &self.spec_constants
+    }
+
+    /// Returns the workgroup size of the first compute entry point.
+    pub fn workgroup_size(&self) -> Option<[u32; 3]> {
+        self.entry_points.iter().filter_map(|entry_point| entry_point.workgroup_size).next()
+    }
+
+    /// Returns vertex input attributes for the inputs of the first vertex
+    /// entry point, sourced from vertex buffer `binding`, along with the
+    /// stride of a vertex.
+    ///
+    /// Attributes are assumed to be tightly packed in location order, as in
+    /// a `#[repr(C)]` struct of `f32`, `i32` and `u32` arrays.
+    pub fn vertex_input_attributes(&self, binding: u32)
+            -> (Vec<VertexInputAttributeDescription>, u32) {
+        let inputs = self.entry_points.iter()
+            .find(|entry_point| entry_point.execution_model == ExecutionModel::Vertex)
+            .map(|entry_point| entry_point.inputs())
+            .unwrap_or(&[]);
+
+        let mut offset = 0;
+        let attributes = inputs.iter().map(|input| {
+            let attribute = VertexInputAttributeDescription::builder()
+                .binding(binding)
+                .location(input.location)
+                .format(input.format)
+                .offset(offset)
+                .build();
+            offset += input.format.block_size();
+            attribute
+        }).collect();
+        (attributes, offset)
+    }
+}
+
+
+/// Merges the descriptor bindings of several shader modules, returning them
+/// grouped by set, with sets numbered by their index.
+///
+/// Bindings used by more than one module have their stages combined.
+/// Returns an error if modules declare different types or counts for the
+/// same binding.
+pub fn merge_descriptor_bindings(reflections: &[&ShaderReflection])
+        -> VdResult<Vec<Vec<DescriptorBinding>>> {
+    let mut merged: BTreeMap<(u32, u32), DescriptorBinding> = BTreeMap::new();
+    for reflection in reflections {
+        for binding in reflection.descriptor_bindings() {
+            let key = (binding.set, binding.binding);
+            if let Some(existing) = merged.get_mut(&key) {
+                if existing.descriptor_type != binding.descriptor_type ||
+                        existing.descriptor_count != binding.descriptor_count {
+                    return Err(format!("descriptor binding {} of set {} is declared as {:?}[{}] \
+                        and {:?}[{}] by different shaders", binding.binding, binding.set,
+                        existing.descriptor_type, existing.descriptor_count,
+                        binding.descriptor_type, binding.descriptor_count).into());
+                }
+                existing.stage_flags |= binding.stage_flags;
+                continue;
+            }
+            merged.insert(key, binding.clone());
+        }
+    }
+
+    let set_count = match merged.keys().next_back() {
+        Some(&(set, _)) if set >= MAX_DESCRIPTOR_SETS => return Err(format!("descriptor set {} \
+            exceeds the limit of {} sets", set, MAX_DESCRIPTOR_SETS).into()),
+        Some(&(set, _)) => set as usize + 1,
+        None => 0,
+    };
+    let mut sets = vec![Vec::new(); set_count];
+    for ((set, _), binding) in merged {
+        sets[set as usize].push(binding);
+    }
+    Ok(sets)
+}
+
+/// Merges the push constant ranges of several shader modules, combining the
+/// stages of identical ranges.
+pub fn merge_push_constant_ranges(reflections: &[&ShaderReflection]) -> Vec<PushConstantRange> {
+    let mut merged: Vec<PushConstantRange> = Vec::new();
+    for range in reflections.iter().flat_map(|reflection| reflection.push_constant_ranges()) {
+        let same = |other: &PushConstantRange| {
+            other.offset() == range.offset() && other.size() == range.size()
+        };
+        match merged.iter().position(same) {
+            Some(i) => {
+                let stage_flags = merged[i].stage_flags() | range.stage_flags();
+                merged[i].set_stage_flags(stage_flags);
+            },
+            None => merged.push(range.clone()),
+        }
+    }
+    merged
+}
+
+/// Creates a descriptor set layout for each set used by the given shader
+/// modules. Sets which are not used by any module get an empty layout.
+pub fn descriptor_set_layouts(device: &Device, reflections: &[&ShaderReflection])
+        -> VdResult<Vec<DescriptorSetLayout>> {
+    let mut layouts = Vec::new();
+    for set in merge_descriptor_bindings(reflections)? {
+        let bindings = set.iter()
+            .map(|binding| binding.layout_binding())
+            .collect::<VdResult<SmallVec<[DescriptorSetLayoutBinding; 8]>>>()?;
+        layouts.push(DescriptorSetLayout::builder()
+            .bindings(&bindings)
+            .build(device.clone())?);
+    }
+    Ok(layouts)
+}
+
+/// Creates a pipeline layout, along with the descriptor set layouts it
+/// refers to, for a pipeline made up of the given shader modules.
+pub fn pipeline_layout(device: &Device, reflections: &[&ShaderReflection])
+        -> VdResult<(PipelineLayout, Vec<DescriptorSetLayout>)> {
+    let set_layouts = descriptor_set_layouts(device, reflections)?;
+    let set_layout_handles: SmallVec<[DescriptorSetLayoutHandle; 8]> = set_layouts.iter()
+        .map(|layout| layout.handle())
+        .collect();
+    let push_constant_ranges = merge_push_constant_ranges(reflections);
+    let pipeline_layout = PipelineLayout::builder()
+        .set_layouts(&set_layout_handles)
+        .push_constant_ranges(&push_constant_ranges)
+        .build(device.clone())?;
+    Ok((pipeline_layout, set_layouts))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Assembles SPIR-V modules instruction by instruction.
+    struct Assembler {
+        words: Vec<u32>,
+    }
+
+    impl Assembler {
+        fn new(bound: u32) -> Assembler {
+            Assembler { words: vec![MAGIC, 0x0001_0000, 0, bound, 0] }
+        }
+
+        fn op(&mut self, opcode: u16, operands: &[u32]) -> &mut Assembler {
+            self.words.push(((operands.len() as u32 + 1) << 16) | opcode as u32);
+            self.words.extend_from_slice(operands);
+            self
+        }
+
+        /// Appends an instruction whose operands end with a literal string.
+        fn op_str(&mut self, opcode: u16, operands: &[u32], string: &str) -> &mut Assembler {
+            let mut operands = operands.to_vec();
+            let mut bytes = string.as_bytes().to_vec();
+            bytes.extend_from_slice(&[0; 4][..4 - bytes.len() % 4]);
+            operands.extend(bytes.chunks(4).map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]])));
+            self.op(opcode, &operands)
+        }
+    }
+
+    const OP_FUNCTION: u16 = 54;
+
+    /// A compute module declaring a uniform buffer, an array of combined
+    /// image samplers, a push constant block and a specialization constant.
+    fn compute_module() -> Vec<u32> {
+        let mut asm = Assembler::new(40);
+        asm.op_str(OP_ENTRY_POINT, &[5, 1], "main")
+            .op(OP_EXECUTION_MODE, &[1, EXECUTION_MODE_LOCAL_SIZE, 8, 4, 1])
+            .op_str(OP_NAME, &[20], "ubo")
+            .op_str(OP_NAME, &[30], "scale")
+            .op(OP_DECORATE, &[13, DECORATION_ARRAY_STRIDE, 64])
+            .op(OP_MEMBER_DECORATE, &[14, 0, DECORATION_OFFSET, 0])
+            .op(OP_MEMBER_DECORATE, &[14, 1, DECORATION_OFFSET, 16])
+            .op(OP_MEMBER_DECORATE, &[14, 1, DECORATION_MATRIX_STRIDE, 16])
+            .op(OP_DECORATE, &[20, DECORATION_DESCRIPTOR_SET, 1])
+            .op(OP_DECORATE, &[20, DECORATION_BINDING, 3])
+            .op(OP_DECORATE, &[25, DECORATION_DESCRIPTOR_SET, 0])
+            .op(OP_DECORATE, &[25, DECORATION_BINDING, 0])
+            .op(OP_MEMBER_DECORATE, &[26, 0, DECORATION_OFFSET, 16])
+            .op(OP_DECORATE, &[30, DECORATION_SPEC_ID, 5])
+            .op(OP_TYPE_FLOAT, &[10, 32])
+            .op(OP_TYPE_INT, &[11, 32, 0])
+            .op(OP_TYPE_VECTOR, &[12, 10, 4])
+            .op(OP_TYPE_MATRIX, &[16, 12, 4])
+            .op(OP_CONSTANT, &[11, 15, 2])
+            .op(OP_TYPE_ARRAY, &[13, 16, 15])
+            .op(OP_TYPE_STRUCT, &[14, 12, 13])
+            .op(OP_TYPE_POINTER, &[19, STORAGE_CLASS_UNIFORM, 14])
+            .op(OP_VARIABLE, &[19, 20, STORAGE_CLASS_UNIFORM])
+            .op(OP_TYPE_IMAGE, &[21, 10, 1, 0, 0, 0, 1, 0])
+            .op(OP_TYPE_SAMPLED_IMAGE, &[22, 21])
+            .op(OP_CONSTANT, &[11, 23, 3])
+            .op(OP_TYPE_ARRAY, &[24, 22, 23])
+            .op(OP_TYPE_POINTER, &[27, STORAGE_CLASS_UNIFORM_CONSTANT, 24])
+            .op(OP_VARIABLE, &[27, 25, STORAGE_CLASS_UNIFORM_CONSTANT])
+            .op(OP_TYPE_STRUCT, &[26, 12])
+            .op(OP_TYPE_POINTER, &[28, STORAGE_CLASS_PUSH_CONSTANT, 26])
+            .op(OP_VARIABLE, &[28, 29, STORAGE_CLASS_PUSH_CONSTANT])
+            .op(OP_SPEC_CONSTANT, &[11, 30, 7])
+            .op(OP_TYPE_VOID, &[31])
+            .op(OP_FUNCTION, &[31, 1, 0, 32]);
+        asm.words
+    }
+
+    fn assert_invalid<T: ::std::fmt::Debug>(res: VdResult<T>) {
+        match res {
+            Err(err) => match *err.kind() {
+                ErrorKind::InvalidSpirV { .. } => (),
+                _ => panic!("unexpected error: {}", err),
+            },
+            Ok(val) => panic!("invalid module accepted: {:?}", val),
+        }
+    }
+
+    #[test]
+    fn header_validation() {
+        let module = compute_module();
+        assert!(host_order_words(&module).is_ok());
+        assert_invalid(host_order_words(&module[..4]));
+
+        let mut bad_magic = module.clone();
+        bad_magic[0] = 0x0723_0204;
+        assert_invalid(host_order_words(&bad_magic));
+
+        let mut bad_version = module.clone();
+        bad_version[1] = 0x0002_0000;
+        assert_invalid(host_order_words(&bad_version));
+        bad_version[1] = 0x0001_0001;
+        assert_invalid(host_order_words(&bad_version));
+
+        let mut zero_bound = module.clone();
+        zero_bound[3] = 0;
+        assert_invalid(host_order_words(&zero_bound));
+
+        let mut truncated = module.clone();
+        truncated.pop();
+        assert_invalid(ShaderReflection::new(&truncated));
+    }
+
+    #[test]
+    fn byte_swapped_modules() {
+        let module = compute_module();
+        let swapped: Vec<u32> = module.iter().map(|word| word.swap_bytes()).collect();
+        assert_eq!(&*host_order_words(&swapped).unwrap(), &module[..]);
+        assert_eq!(entry_point_names(&module).unwrap(), vec!["main".to_owned()]);
+        assert_eq!(ShaderReflection::new(&swapped).unwrap().descriptor_bindings(),
+            ShaderReflection::new(&module).unwrap().descriptor_bindings());
+    }
+
+    #[test]
+    fn reflection() {
+        let reflection = ShaderReflection::new(&compute_module()).unwrap();
+
+        let entry_point = reflection.entry_point("main").unwrap();
+        assert_eq!(entry_point.execution_model(), ExecutionModel::GlCompute);
+        assert_eq!(reflection.stage_flags(), ShaderStageFlags::COMPUTE);
+        assert_eq!(reflection.workgroup_size(), Some([8, 4, 1]));
+
+        assert_eq!(reflection.descriptor_bindings(), &[
+            DescriptorBinding { name: None, set: 0, binding: 0,
+                descriptor_type: DescriptorType::CombinedImageSampler, descriptor_count: 3,
+                stage_flags: ShaderStageFlags::COMPUTE },
+            DescriptorBinding { name: Some("ubo".to_owned()), set: 1, binding: 3,
+                descriptor_type: DescriptorType::UniformBuffer, descriptor_count: 1,
+                stage_flags: ShaderStageFlags::COMPUTE },
+        ][..]);
+
+        let ranges = reflection.push_constant_ranges();
+        assert_eq!(ranges.len(), 1);
+        assert_eq!((ranges[0].offset(), ranges[0].size()), (16, 16));
+
+        assert_eq!(reflection.spec_constants(), &[SpecConstant { name: Some("scale".to_owned()),
+            id: 5, scalar_type: ScalarType::Int { width: 32, signed: false },
+            default_value: 7 }][..]);
+    }
+
+    #[test]
+    fn struct_sizes() {
+        let module = Module::parse(&compute_module()).unwrap();
+        // A vec4 followed by two column-major mat4s at offset 16:
+        assert_eq!(module.size(14, None).unwrap(), 16 + 2 * 64);
+        assert_eq!(module.size(16, Some(32)).unwrap(), 128);
+    }
+
+    #[test]
+    fn overflowing_sizes_and_counts() {
+        let mut module = compute_module();
+        // Make the uniform buffer's matrix array 2^28 elements long, with a
+        // stride of 64 bytes:
+        let pos = module.windows(4).position(|w| w == [0x0004_002b, 11, 15, 2]).unwrap();
+        module[pos + 3] = 1 << 28;
+        assert_invalid(Module::parse(&module).unwrap().size(14, None));
+
+        let mut module = compute_module();
+        // Nest the sampler array in another of 2^31 elements:
+        let mut asm = Assembler { words: Vec::new() };
+        asm.op(OP_CONSTANT, &[11, 33, 1 << 31])
+            .op(OP_TYPE_ARRAY, &[34, 24, 33])
+            .op(OP_TYPE_POINTER, &[35, STORAGE_CLASS_UNIFORM_CONSTANT, 34])
+            .op(OP_VARIABLE, &[35, 36, STORAGE_CLASS_UNIFORM_CONSTANT])
+            .op(OP_DECORATE, &[36, DECORATION_BINDING, 1]);
+        module.extend(asm.words);
+        assert_invalid(ShaderReflection::new(&module));
+    }
+
+    /// Parses the type declarations `types` following those of the compute
+    /// module.
+    fn with_types(types: &[(u16, &[u32])]) -> VdResult<Module> {
+        let mut asm = Assembler { words: compute_module() };
+        for &(opcode, operands) in types {
+            asm.op(opcode, operands);
+        }
+        Module::parse(&asm.words)
+    }
+
+    #[test]
+    fn cyclic_types() {
+        assert_invalid(with_types(&[(OP_TYPE_RUNTIME_ARRAY, &[33, 33])]));
+        assert_invalid(with_types(&[(OP_CONSTANT, &[11, 33, 1]), (OP_TYPE_ARRAY, &[34, 34, 33])]));
+        assert_invalid(with_types(&[(OP_TYPE_STRUCT, &[33, 12, 33])]));
+        assert_invalid(with_types(&[(OP_TYPE_VECTOR, &[33, 33, 4])]));
+        // Mutually recursive types, one referring to the other before it is
+        // defined:
+        assert_invalid(with_types(&[(OP_TYPE_STRUCT, &[33, 34]), (OP_TYPE_STRUCT, &[34, 33])]));
+        // Redefining a type contained in another:
+        assert_invalid(with_types(&[(OP_TYPE_STRUCT, &[33, 10]), (OP_TYPE_STRUCT, &[34, 33]),
+            (OP_TYPE_STRUCT, &[33, 34])]));
+        // Pointers may be declared before their pointee:
+        assert!(with_types(&[(OP_TYPE_POINTER, &[33, STORAGE_CLASS_UNIFORM, 34]),
+            (OP_TYPE_STRUCT, &[34, 10])]).is_ok());
+    }
+
+    #[test]
+    fn deeply_nested_types() {
+        let mut module = compute_module();
+        let mut asm = Assembler { words: Vec::new() };
+        asm.op(OP_CONSTANT, &[11, 100, 1])
+            .op(OP_TYPE_ARRAY, &[101, 10, 100]);
+        for id in 102..102 + MAX_TYPE_DEPTH + 1 {
+            asm.op(OP_TYPE_ARRAY, &[id, id - 1, 100]);
+        }
+        let outermost = 102 + MAX_TYPE_DEPTH;
+        asm.op(OP_TYPE_POINTER, &[outermost + 1, STORAGE_CLASS_UNIFORM_CONSTANT, outermost])
+            .op(OP_VARIABLE, &[outermost + 1, outermost + 2, STORAGE_CLASS_UNIFORM_CONSTANT])
+            .op(OP_DECORATE, &[outermost + 2, DECORATION_BINDING, 1]);
+        module.extend(asm.words);
+        module[3] = outermost + 3;
+
+        let parsed = Module::parse(&module).unwrap();
+        assert_eq!(parsed.size(101 + MAX_TYPE_DEPTH / 2, None).unwrap(), 4);
+        assert_invalid(parsed.size(outermost, None));
+        assert_invalid(ShaderReflection::new(&module));
+    }
+
+    #[test]
+    fn descriptor_set_limit() {
+        let mut module = compute_module();
+        let pos = module.windows(3).position(|w| w == [20, DECORATION_DESCRIPTOR_SET, 1]).unwrap();
+        module[pos + 2] = MAX_DESCRIPTOR_SETS - 1;
+        let reflection = ShaderReflection::new(&module).unwrap();
+        assert_eq!(merge_descriptor_bindings(&[&reflection]).unwrap().len(),
+            MAX_DESCRIPTOR_SETS as usize);
+
+        for &set in &[MAX_DESCRIPTOR_SETS, u32::max_value()] {
+            module[pos + 2] = set;
+            let reflection = ShaderReflection::new(&module).unwrap();
+            assert_eq!(reflection.descriptor_bindings()[1].set, set);
+            assert!(merge_descriptor_bindings(&[&reflection]).is_err());
+        }
+    }
+
+    /// A vertex module with an input array of `length` vec4s at `location`.
+    fn vertex_module(location: u32, length: u32) -> Vec<u32> {
+        let mut asm = Assembler::new(20);
+        let main = u32::from_le_bytes(*b"main");
+        asm.op(OP_ENTRY_POINT, &[0, 1, main, 0, 10])
+            .op(OP_DECORATE, &[10, DECORATION_LOCATION, location])
+            .op(OP_TYPE_FLOAT, &[2, 32])
+            .op(OP_TYPE_VECTOR, &[3, 2, 4])
+            .op(OP_TYPE_INT, &[4, 32, 0])
+            .op(OP_CONSTANT, &[4, 5, length])
+            .op(OP_TYPE_ARRAY, &[6, 3, 5])
+            .op(OP_TYPE_POINTER, &[7, STORAGE_CLASS_INPUT, 6])
+            .op(OP_VARIABLE, &[7, 10, STORAGE_CLASS_INPUT]);
+        asm.words
+    }
+
+    #[test]
+    fn interface_location_limit() {
+        let reflection = ShaderReflection::new(&vertex_module(2, 3)).unwrap();
+        let locations: Vec<u32> = reflection.entry_point("main").unwrap().inputs().iter()
+            .map(|input| input.location)
+            .collect();
+        assert_eq!(locations, vec![2, 3, 4]);
+        assert!(ShaderReflection::new(&vertex_module(MAX_LOCATIONS - 4, 4)).is_ok());
+
+        assert_invalid(ShaderReflection::new(&vertex_module(MAX_LOCATIONS - 4, 5)));
+        assert_invalid(ShaderReflection::new(&vertex_module(0, u32::max_value())));
+        assert_invalid(ShaderReflection::new(&vertex_module(u32::max_value(), 1)));
+    }
+}
@@ -1,7 +1,10 @@
-use std::sync::Arc;
+use std::sync::{Arc, Weak, Mutex};
 use std::marker::PhantomData;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
 use vks;
-use ::{VdResult, Device, Handle};
+use ::{VdResult, Device, Handle, SamplerCreateInfo, SamplerCreateFlags, Filter, SamplerMipmapMode,
+    SamplerAddressMode, CompareOp, BorderColor};
 
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -26,6 +29,138 @@ unsafe impl Handle for SamplerHandle {
 }
 
 
+/// The complete state of a `SamplerCreateInfo`, with floats compared by
+/// their bit patterns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct SamplerKey {
+    next: usize,
+    flags: SamplerCreateFlags,
+    mag_filter: Filter,
+    min_filter: Filter,
+    mipmap_mode: SamplerMipmapMode,
+    address_mode_u: SamplerAddressMode,
+    address_mode_v: SamplerAddressMode,
+    address_mode_w: SamplerAddressMode,
+    mip_lod_bias: u32,
+    anisotropy_enable: bool,
+    max_anisotropy: u32,
+    compare_enable: bool,
+    compare_op: CompareOp,
+    min_lod: u32,
+    max_lod: u32,
+    border_color: BorderColor,
+    unnormalized_coordinates: bool,
+}
+
+impl SamplerKey {
+    fn new(create_info: &SamplerCreateInfo) -> SamplerKey {
+        SamplerKey {
+            next: create_info.next() as usize,
+            flags: create_info.flags(),
+            mag_filter: create_info.mag_filter(),
+            min_filter: create_info.min_filter(),
+            mipmap_mode: create_info.mipmap_mode(),
+            address_mode_u: create_info.address_mode_u(),
+            address_mode_v: create_info.address_mode_v(),
+            address_mode_w: create_info.address_mode_w(),
+            mip_lod_bias: create_info.mip_lod_bias().to_bits(),
+            anisotropy_enable: create_info.anisotropy_enable(),
+            max_anisotropy: create_info.max_anisotropy().to_bits(),
+            compare_enable: create_info.compare_enable(),
+            compare_op: create_info.compare_op(),
+            min_lod: create_info.min_lod().to_bits(),
+            max_lod: create_info.max_lod().to_bits(),
+            border_color: create_info.border_color(),
+            unnormalized_coordinates: create_info.unnormalized_coordinates(),
+        }
+    }
+}
+
+impl<'s> PartialEq for SamplerCreateInfo<'s> {
+    fn eq(&self, other: &SamplerCreateInfo<'s>) -> bool {
+        SamplerKey::new(self) == SamplerKey::new(other)
+    }
+}
+
+impl<'s> Eq for SamplerCreateInfo<'s> {}
+
+impl<'s> Hash for SamplerCreateInfo<'s> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        SamplerKey::new(self).hash(state)
+    }
+}
+
+
+/// A cache of samplers, keyed by their complete creation state. Accessed
+/// with `Device::sampler_cache` and `Device::cached_sampler`.
+///
+/// The cache holds weak references: a cached sampler is destroyed once every
+/// clone of it (including those kept by descriptor set layouts) is dropped.
+/// Entries of destroyed samplers are removed by `::evict_unused` and as new
+/// samplers are inserted.
+#[derive(Debug)]
+pub struct SamplerCache {
+    samplers: Mutex<HashMap<SamplerKey, Weak<Inner>>>,
+}
+
+impl SamplerCache {
+    pub(crate) fn new() -> SamplerCache {
+        SamplerCache { samplers: Mutex::new(HashMap::new()) }
+    }
+
+    /// Returns a sampler matching `create_info`, creating it if no such
+    /// sampler is alive.
+    ///
+    /// Create infos with an extension chain (`next`) bypass the cache: its
+    /// contents can not be compared, only its address.
+    pub(crate) fn get(&self, device: &Device, create_info: &SamplerCreateInfo)
+            -> VdResult<Sampler> {
+        if !create_info.next().is_null() {
+            return Sampler::new(device.clone(), create_info);
+        }
+
+        let key = SamplerKey::new(create_info);
+        let mut samplers = self.samplers.lock().unwrap();
+        if let Some(inner) = samplers.get(&key).and_then(|inner| inner.upgrade()) {
+            return Ok(Sampler { inner });
+        }
+
+        let sampler = Sampler::new(device.clone(), create_info)?;
+        samplers.retain(|_, inner| inner.upgrade().is_some());
+        samplers.insert(key, Arc::downgrade(&sampler.inner));
+        Ok(sampler)
+    }
+
+    /// Returns the live cached sampler with `handle`, if any.
+    pub(crate) fn find(&self, handle: SamplerHandle) -> Option<Sampler> {
+        self.samplers.lock().unwrap().values()
+            .filter_map(|inner| inner.upgrade())
+            .find(|inner| inner.handle == handle)
+            .map(|inner| Sampler { inner })
+    }
+
+    /// Returns the number of entries in the cache, including any whose
+    /// samplers have been destroyed but not yet evicted.
+    pub fn len(&self) -> usize {
+        self.samplers.lock().unwrap().len()
+    }
+
+    /// Returns true if the cache has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes the entries of samplers which are no longer in use, returning
+    /// the number removed.
+    pub fn evict_unused(&self) -> usize {
+        let mut samplers = self.samplers.lock().unwrap();
+        let len = samplers.len();
+        samplers.retain(|_, inner| inner.upgrade().is_some());
+        len - samplers.len()
+    }
+}
+
+
 #[derive(Debug)]
 struct Inner {
     handle: SamplerHandle,
@@ -60,6 +195,19 @@ impl Sampler {
         SamplerBuilder::new()
     }
 
+    /// Creates a new sampler from `create_info`. Use `Device::cached_sampler`
+    /// to share samplers with identical state.
+    pub fn new(device: Device, create_info: &SamplerCreateInfo) -> VdResult<Sampler> {
+        let handle = unsafe { device.create_sampler(create_info, None)? };
+
+        Ok(Sampler {
+            inner: Arc::new(Inner {
+                handle,
+                device,
+            })
+        })
+    }
+
     /// Returns this object's handle.
     pub fn handle(&self) -> SamplerHandle {
         self.inner.handle
@@ -255,13 +403,63 @@ impl<'b> SamplerBuilder<'b> {
 
     /// Creates and returns a new `Sampler`
     pub fn build(&self, device: Device) -> VdResult<Sampler> {
-        let handle = unsafe { device.create_sampler(&self.create_info, None)? };
+        Sampler::new(device, &self.create_info)
+    }
 
-        Ok(Sampler {
-            inner: Arc::new(Inner {
-                handle,
-                device,
-            })
-        })
+    /// Returns a sampler from the device's `SamplerCache` matching this
+    /// builder's state, creating it if necessary.
+    pub fn build_cached(&self, device: &Device) -> VdResult<Sampler> {
+        device.cached_sampler(&self.create_info)
+    }
+}
+
+impl<'b> AsRef<SamplerCreateInfo<'b>> for SamplerBuilder<'b> {
+    fn as_ref(&self) -> &SamplerCreateInfo<'b> {
+        &self.create_info
+    }
+}
+
+impl<'b> PartialEq for SamplerBuilder<'b> {
+    fn eq(&self, other: &SamplerBuilder<'b>) -> bool {
+        self.create_info == other.create_info
+    }
+}
+
+impl<'b> Eq for SamplerBuilder<'b> {}
+
+impl<'b> Hash for SamplerBuilder<'b> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.create_info.hash(state)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::hash_map::DefaultHasher;
+    use super::*;
+
+    fn hash(create_info: &SamplerCreateInfo) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        create_info.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn create_info_keys() {
+        let mut a = SamplerCreateInfo::default();
+        a.set_mag_filter(Filter::Linear);
+        a.set_max_lod(4.0);
+        let b = a.clone();
+        assert!(a == b);
+        assert_eq!(hash(&a), hash(&b));
+
+        let mut c = a.clone();
+        c.set_max_lod(8.0);
+        assert!(a != c);
+
+        let mut d = a.clone();
+        d.set_min_filter(Filter::Linear);
+        assert!(a != d);
     }
 }
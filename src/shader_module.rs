@@ -1,6 +1,8 @@
 use std::sync::Arc;
+use std::path::Path;
 use vks;
-use ::{VdResult, Device, Handle, ShaderModuleCreateInfo};
+use ::{util, VdResult, Device, Handle, ShaderModuleCreateInfo};
+use reflect;
 
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -29,6 +31,7 @@ unsafe impl Handle for ShaderModuleHandle {
 struct Inner {
     handle: ShaderModuleHandle,
     device: Device,
+    entry_points: Vec<String>,
 }
 
 // The following object types are consumed when they are passed into a Vulkan
@@ -45,10 +48,18 @@ pub struct ShaderModule {
 }
 
 impl ShaderModule {
-    /// Creates and returns a new `ShaderModule`.
+    /// Creates and returns a new `ShaderModule` from SPIR-V words in either
+    /// byte order.
+    ///
+    /// Returns an `InvalidSpirV` error if the module header has an invalid
+    /// magic number, an unsupported version or a zero ID bound, or if its
+    /// instructions can not be read.
     pub fn new(device: Device, code: &[u32]) -> VdResult<ShaderModule> {
+        let code = reflect::host_order_words(code)?;
+        let entry_points = reflect::entry_point_names(&code)?;
+
         let create_info = ShaderModuleCreateInfo::builder()
-            .code(code)
+            .code(&code)
             .build();
 
         let handle = unsafe { device.create_shader_module(&create_info, None)? };
@@ -57,10 +68,27 @@ impl ShaderModule {
             inner: Arc::new(Inner {
                 handle,
                 device,
+                entry_points,
             })
         })
     }
 
+    /// Creates a new `ShaderModule` from the bytes of a SPIR-V module.
+    ///
+    /// Returns an `InvalidSpirV` error if the length of `bytes` is not a
+    /// multiple of four or the module is malformed (see `::new`).
+    pub fn from_bytes(device: Device, bytes: &[u8]) -> VdResult<ShaderModule> {
+        ShaderModule::new(device, &util::spir_v_words(bytes)?)
+    }
+
+    /// Creates a new `ShaderModule` from the SPIR-V file at `path`.
+    ///
+    /// Returns an I/O error if the file can not be read and an `InvalidSpirV`
+    /// error if its contents are not a valid module.
+    pub fn from_file<P: AsRef<Path>>(device: Device, path: P) -> VdResult<ShaderModule> {
+        ShaderModule::from_bytes(device, &util::read_file(path)?)
+    }
+
     /// Returns this object's handle.
     pub fn handle(&self) -> ShaderModuleHandle {
         self.inner.handle
@@ -70,6 +98,16 @@ impl ShaderModule {
     pub fn device(&self) -> &Device {
         &self.inner.device
     }
+
+    /// Returns the names of the entry points declared by this module.
+    pub fn entry_points(&self) -> &[String] {
+        &self.inner.entry_points
+    }
+
+    /// Returns true if this module declares an entry point named `name`.
+    pub fn has_entry_point(&self, name: &str) -> bool {
+        self.inner.entry_points.iter().any(|entry_point| entry_point == name)
+    }
 }
 
 unsafe impl<'h> Handle for &'h ShaderModule {
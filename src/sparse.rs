@@ -0,0 +1,802 @@
+use std::cmp;
+use std::ops::Range;
+use std::collections::BTreeMap;
+use smallvec::SmallVec;
+use ::{VdResult, Device, Buffer, Image, ImageBuilder, DeviceMemory, DeviceMemoryHandle, Queue,
+    Semaphore, SemaphoreHandle, Fence, FenceStatus, DeviceSize, BufferUsageFlags, BufferCreateFlags,
+    ImageCreateFlags, ImageAspectFlags, SharingMode, QueueFlags, Extent3d, Offset3d,
+    ImageSubresource, SparseImageFormatFlags, SparseMemoryBindFlags, SparseMemoryBind,
+    SparseImageMemoryBind, SparseBufferMemoryBindInfo, SparseImageOpaqueMemoryBindInfo,
+    SparseImageMemoryBindInfo, BindSparseInfo, ErrorKind};
+
+
+/// Returns an `InvalidSparseBinding` error.
+fn invalid<T>(reason: &'static str) -> VdResult<T> {
+    Err(ErrorKind::InvalidSparseBinding { reason }.into())
+}
+
+/// Checks that `memory` is usable with a resource having `memory_type_bits`
+/// and that `size` bytes starting at `offset` fit within it.
+fn check_memory(memory: &DeviceMemory, memory_type_bits: u32, offset: DeviceSize,
+        size: DeviceSize, alignment: DeviceSize) -> VdResult<()> {
+    if memory_type_bits & (1 << memory.memory_type_index()) == 0 {
+        return invalid("memory type is not supported by the resource");
+    }
+    if alignment > 1 && offset % alignment != 0 {
+        return invalid("memory offset is not a multiple of the sparse block size");
+    }
+    match offset.checked_add(size) {
+        Some(end) if end <= memory.allocation_size() => Ok(()),
+        _ => invalid("memory allocation is too small"),
+    }
+}
+
+/// Returns the offset and extent in texels along one dimension of a mip
+/// level `mip_extent` texels long of `tile_count` tiles, `tile` texels long,
+/// starting at tile `first_tile`.
+fn tile_bounds(first_tile: u32, tile_count: u32, tile: u32, mip_extent: u32)
+        -> VdResult<(u32, u32)> {
+    let start = first_tile.checked_mul(tile);
+    let end = first_tile.checked_add(tile_count).and_then(|end| end.checked_mul(tile));
+    match (start, end) {
+        (Some(start), Some(end)) if tile_count != 0 && start < mip_extent &&
+                end - tile < mip_extent => Ok((start, cmp::min(end, mip_extent) - start)),
+        _ => invalid("region is empty or exceeds the mip level"),
+    }
+}
+
+
+/// A memory range bound to part of a sparse resource.
+#[derive(Debug, Clone)]
+struct ResidentMemory {
+    memory: DeviceMemory,
+    offset: DeviceSize,
+}
+
+
+/// A set of sparse memory binding operations to be submitted to a queue
+/// together.
+///
+/// Binds are recorded with `SparseBuffer::commit`, `SparseImage::commit`
+/// and their `evict` counterparts, then submitted as a single
+/// `vkQueueBindSparse` call with `::submit`.
+///
+/// The batch keeps every resource and memory object it refers to alive,
+/// including memory which is being evicted. Submitting it hands them to the
+/// returned `SparseBindToken`, which holds them until the binds complete.
+///
+#[derive(Debug, Default)]
+pub struct SparseBindBatch {
+    buffer_binds: Vec<(Buffer, Vec<SparseMemoryBind>)>,
+    image_opaque_binds: Vec<(Image, Vec<SparseMemoryBind>)>,
+    image_binds: Vec<(Image, Vec<SparseImageMemoryBind>)>,
+    memory: Vec<DeviceMemory>,
+}
+
+impl SparseBindBatch {
+    /// Returns a new, empty `SparseBindBatch`.
+    pub fn new() -> SparseBindBatch {
+        SparseBindBatch::default()
+    }
+
+    fn keep_alive(&mut self, memory: &DeviceMemory) {
+        if !self.memory.iter().any(|m| m.handle() == memory.handle()) {
+            self.memory.push(memory.clone());
+        }
+    }
+
+    fn push_buffer_bind(&mut self, buffer: &Buffer, bind: SparseMemoryBind) {
+        match self.buffer_binds.iter().position(|&(ref b, _)| b.handle() == buffer.handle()) {
+            Some(idx) => self.buffer_binds[idx].1.push(bind),
+            None => self.buffer_binds.push((buffer.clone(), vec![bind])),
+        }
+    }
+
+    fn push_image_opaque_bind(&mut self, image: &Image, bind: SparseMemoryBind) {
+        match self.image_opaque_binds.iter().position(|&(ref i, _)| i.handle() == image.handle()) {
+            Some(idx) => self.image_opaque_binds[idx].1.push(bind),
+            None => self.image_opaque_binds.push((image.clone(), vec![bind])),
+        }
+    }
+
+    fn push_image_bind(&mut self, image: &Image, bind: SparseImageMemoryBind) {
+        match self.image_binds.iter().position(|&(ref i, _)| i.handle() == image.handle()) {
+            Some(idx) => self.image_binds[idx].1.push(bind),
+            None => self.image_binds.push((image.clone(), vec![bind])),
+        }
+    }
+
+    /// Returns true if no binds have been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.buffer_binds.is_empty() && self.image_opaque_binds.is_empty() &&
+            self.image_binds.is_empty()
+    }
+
+    /// Submits all recorded binds to `queue` as a single batch.
+    ///
+    /// The binds execute after `wait_semaphores` are signaled and signal
+    /// `signal_semaphores` and `fence` on completion. `fence` must be
+    /// unsignaled. `queue` must belong to a family supporting
+    /// `QueueFlags::SPARSE_BINDING`.
+    ///
+    /// Work which accesses memory being evicted must complete before the
+    /// binds execute (typically by signaling one of `wait_semaphores`). The
+    /// returned token keeps the batch's resources and memory alive until
+    /// `fence` is signaled.
+    pub fn submit(self, queue: &Queue, wait_semaphores: &[&Semaphore],
+            signal_semaphores: &[&Semaphore], fence: &Fence) -> VdResult<SparseBindToken> {
+        let family_properties = queue.device().physical_device().queue_family_properties()?;
+        let supports_sparse = family_properties.get(queue.family_index() as usize)
+            .map(|props| props.queue_flags().contains(QueueFlags::SPARSE_BINDING))
+            .unwrap_or(false);
+        if !supports_sparse {
+            return invalid("queue family does not support sparse binding");
+        }
+
+        {
+            let buffer_binds: SmallVec<[SparseBufferMemoryBindInfo; 4]> = self.buffer_binds.iter()
+                .map(|&(ref buffer, ref binds)| {
+                    SparseBufferMemoryBindInfo::builder()
+                        .buffer(buffer)
+                        .binds(binds)
+                        .build()
+                }).collect();
+            let image_opaque_binds: SmallVec<[SparseImageOpaqueMemoryBindInfo; 4]> =
+                self.image_opaque_binds.iter().map(|&(ref image, ref binds)| {
+                    SparseImageOpaqueMemoryBindInfo::builder()
+                        .image(image)
+                        .binds(binds)
+                        .build()
+                }).collect();
+            let image_binds: SmallVec<[SparseImageMemoryBindInfo; 4]> = self.image_binds.iter()
+                .map(|&(ref image, ref binds)| {
+                    SparseImageMemoryBindInfo::builder()
+                        .image(image)
+                        .binds(binds)
+                        .build()
+                }).collect();
+            let wait_semaphores: SmallVec<[SemaphoreHandle; 8]> = wait_semaphores.iter()
+                .map(|s| s.handle()).collect();
+            let signal_semaphores: SmallVec<[SemaphoreHandle; 8]> = signal_semaphores.iter()
+                .map(|s| s.handle()).collect();
+
+            let bind_info = BindSparseInfo::builder()
+                .wait_semaphores(&wait_semaphores)
+                .buffer_binds(&buffer_binds)
+                .image_opaque_binds(&image_opaque_binds)
+                .image_binds(&image_binds)
+                .signal_semaphores(&signal_semaphores)
+                .build();
+
+            queue.bind_sparse(&[bind_info], Some(fence.handle()))?;
+        }
+
+        Ok(SparseBindToken { fence: fence.clone(), _batch: self })
+    }
+}
+
+
+/// A submitted `SparseBindBatch`.
+///
+///
+/// ### Destruction
+///
+/// Dropping this `SparseBindToken` blocks until the binds have completed,
+/// then releases the resources and memory (including evicted memory) held by
+/// the batch.
+///
+#[derive(Debug)]
+pub struct SparseBindToken {
+    fence: Fence,
+    _batch: SparseBindBatch,
+}
+
+impl SparseBindToken {
+    /// Returns true if the binds have completed.
+    pub fn is_complete(&self) -> VdResult<bool> {
+        match self.fence.status()? {
+            FenceStatus::Signaled => Ok(true),
+            FenceStatus::Unsignaled => Ok(false),
+            FenceStatus::Error(res) => Err(ErrorKind::ApiCall(res, "vkGetFenceStatus").into()),
+        }
+    }
+
+    /// Blocks until the binds have completed.
+    pub fn wait(self) -> VdResult<()> {
+        unsafe { self.fence.device().wait_for_fences(&[self.fence.handle()], true, u64::max_value()) }
+    }
+
+    /// Returns the fence signaled when the binds complete.
+    pub fn fence(&self) -> &Fence {
+        &self.fence
+    }
+}
+
+impl Drop for SparseBindToken {
+    fn drop(&mut self) {
+        unsafe {
+            self.fence.device().wait_for_fences(&[self.fence.handle()], true, u64::max_value()).ok();
+        }
+    }
+}
+
+
+/// A buffer created with sparse residency whose memory is bound one page
+/// at a time.
+///
+/// The page size is the buffer's memory alignment requirement. A page table
+/// records which pages are resident and the memory backing each of them.
+///
+#[derive(Debug)]
+pub struct SparseBuffer {
+    buffer: Buffer,
+    page_size: DeviceSize,
+    pages: BTreeMap<DeviceSize, ResidentMemory>,
+}
+
+impl SparseBuffer {
+    /// Creates a new sparsely resident buffer of `size` bytes.
+    pub fn new(device: Device, size: DeviceSize, usage: BufferUsageFlags)
+            -> VdResult<SparseBuffer> {
+        let buffer = Buffer::builder()
+            .flags(BufferCreateFlags::SPARSE_BINDING | BufferCreateFlags::SPARSE_RESIDENCY)
+            .size(size)
+            .usage(usage)
+            .sharing_mode(SharingMode::Exclusive)
+            .build(device)?;
+        let page_size = cmp::max(buffer.memory_requirements().alignment(), 1);
+
+        Ok(SparseBuffer { buffer, page_size, pages: BTreeMap::new() })
+    }
+
+    /// Returns the range of pages covered by the byte range `region`.
+    fn page_range(&self, region: &Range<DeviceSize>) -> VdResult<Range<DeviceSize>> {
+        let size = self.buffer.memory_requirements().size();
+        if region.start >= region.end || region.end > size {
+            return invalid("region is empty or exceeds the buffer");
+        }
+        if region.start % self.page_size != 0 ||
+                (region.end % self.page_size != 0 && region.end != size) {
+            return invalid("region is not aligned to the page size");
+        }
+        Ok(region.start / self.page_size..
+            (region.end + self.page_size - 1) / self.page_size)
+    }
+
+    /// Records binds making the byte ranges `regions` resident, backed by
+    /// consecutive ranges of `memory` starting at `memory_offset`.
+    ///
+    /// Regions must be page-aligned. Pages which are already resident are
+    /// rebound.
+    pub fn commit(&mut self, batch: &mut SparseBindBatch, regions: &[Range<DeviceSize>],
+            memory: &DeviceMemory, memory_offset: DeviceSize) -> VdResult<()> {
+        let mut page_ranges = SmallVec::<[Range<DeviceSize>; 8]>::new();
+        let mut total: DeviceSize = 0;
+        for region in regions {
+            let pages = self.page_range(region)?;
+            total = match total.checked_add((pages.end - pages.start) * self.page_size) {
+                Some(total) => total,
+                None => return invalid("regions exceed the size of any memory allocation"),
+            };
+            page_ranges.push(pages);
+        }
+        check_memory(memory, self.buffer.memory_requirements().memory_type_bits(),
+            memory_offset, total, self.page_size)?;
+
+        let mut offset = memory_offset;
+        for pages in page_ranges {
+            let size = (pages.end - pages.start) * self.page_size;
+            batch.push_buffer_bind(&self.buffer, SparseMemoryBind::builder()
+                .resource_offset(pages.start * self.page_size)
+                .size(size)
+                .memory(memory)
+                .memory_offset(offset)
+                .build());
+            for (i, page) in (pages.start..pages.end).enumerate() {
+                let resident = ResidentMemory { memory: memory.clone(),
+                    offset: offset + i as DeviceSize * self.page_size };
+                if let Some(old) = self.pages.insert(page, resident) {
+                    batch.keep_alive(&old.memory);
+                }
+            }
+            offset += size;
+        }
+        batch.keep_alive(memory);
+        Ok(())
+    }
+
+    /// Records binds making the byte ranges `regions` non-resident.
+    pub fn evict(&mut self, batch: &mut SparseBindBatch, regions: &[Range<DeviceSize>])
+            -> VdResult<()> {
+        for region in regions {
+            let pages = self.page_range(region)?;
+            batch.push_buffer_bind(&self.buffer, SparseMemoryBind::builder()
+                .resource_offset(pages.start * self.page_size)
+                .size((pages.end - pages.start) * self.page_size)
+                .memory(DeviceMemoryHandle(0))
+                .build());
+            for page in pages {
+                if let Some(old) = self.pages.remove(&page) {
+                    batch.keep_alive(&old.memory);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns true if the page containing byte `offset` is resident.
+    pub fn is_resident(&self, offset: DeviceSize) -> bool {
+        self.pages.contains_key(&(offset / self.page_size))
+    }
+
+    /// Returns the memory and memory offset backing the page containing byte
+    /// `offset`, if resident.
+    pub fn page_memory(&self, offset: DeviceSize) -> Option<(&DeviceMemory, DeviceSize)> {
+        self.pages.get(&(offset / self.page_size)).map(|r| (&r.memory, r.offset))
+    }
+
+    /// Returns the number of resident pages.
+    pub fn resident_page_count(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// Returns the size in bytes of a page.
+    pub fn page_size(&self) -> DeviceSize {
+        self.page_size
+    }
+
+    /// Returns the number of pages spanned by the buffer.
+    pub fn page_count(&self) -> DeviceSize {
+        (self.buffer.memory_requirements().size() + self.page_size - 1) / self.page_size
+    }
+
+    /// Returns the underlying buffer.
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+}
+
+
+/// The sparse memory layout of one aspect of a `SparseImage`.
+#[derive(Debug, Clone)]
+pub struct SparseImageAspect {
+    aspect_mask: ImageAspectFlags,
+    tile_extent: Extent3d,
+    flags: SparseImageFormatFlags,
+    mip_tail_first_lod: u32,
+    mip_tail_size: DeviceSize,
+    mip_tail_offset: DeviceSize,
+    mip_tail_stride: DeviceSize,
+}
+
+impl SparseImageAspect {
+    /// Returns the aspects this layout applies to.
+    pub fn aspect_mask(&self) -> ImageAspectFlags {
+        self.aspect_mask
+    }
+
+    /// Returns the extent in texels of a tile (sparse image block).
+    pub fn tile_extent(&self) -> &Extent3d {
+        &self.tile_extent
+    }
+
+    /// Returns the sparse image format flags.
+    pub fn flags(&self) -> SparseImageFormatFlags {
+        self.flags
+    }
+
+    /// Returns the first mip level which is part of the mip tail.
+    pub fn mip_tail_first_lod(&self) -> u32 {
+        self.mip_tail_first_lod
+    }
+
+    /// Returns the size in bytes of the mip tail (of each array layer unless
+    /// `::has_single_mip_tail`).
+    pub fn mip_tail_size(&self) -> DeviceSize {
+        self.mip_tail_size
+    }
+
+    /// Returns true if all array layers share a single mip tail.
+    pub fn has_single_mip_tail(&self) -> bool {
+        self.flags.contains(SparseImageFormatFlags::SINGLE_MIPTAIL)
+    }
+
+    /// Returns the opaque resource offset of the mip tail of `array_layer`.
+    fn mip_tail_offset(&self, array_layer: u32) -> DeviceSize {
+        if self.has_single_mip_tail() {
+            self.mip_tail_offset
+        } else {
+            self.mip_tail_offset + array_layer as DeviceSize * self.mip_tail_stride
+        }
+    }
+}
+
+
+/// A rectangular block of tiles within one subresource of a `SparseImage`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseImageRegion {
+    aspect_mask: ImageAspectFlags,
+    mip_level: u32,
+    array_layer: u32,
+    first_tile: [u32; 3],
+    tile_count: [u32; 3],
+}
+
+impl SparseImageRegion {
+    /// Returns a new region of `tile_count` tiles, starting at tile
+    /// coordinates `first_tile`, of the given subresource.
+    pub fn new(aspect_mask: ImageAspectFlags, mip_level: u32, array_layer: u32,
+            first_tile: [u32; 3], tile_count: [u32; 3]) -> SparseImageRegion {
+        SparseImageRegion { aspect_mask, mip_level, array_layer, first_tile, tile_count }
+    }
+
+    /// Returns the aspects of the region.
+    pub fn aspect_mask(&self) -> ImageAspectFlags {
+        self.aspect_mask
+    }
+
+    /// Returns the mip level of the region.
+    pub fn mip_level(&self) -> u32 {
+        self.mip_level
+    }
+
+    /// Returns the array layer of the region.
+    pub fn array_layer(&self) -> u32 {
+        self.array_layer
+    }
+
+    /// Returns the coordinates of the first tile of the region.
+    pub fn first_tile(&self) -> [u32; 3] {
+        self.first_tile
+    }
+
+    /// Returns the number of tiles in each dimension.
+    pub fn tile_count(&self) -> [u32; 3] {
+        self.tile_count
+    }
+}
+
+
+/// Identifies a single tile of a sparse image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct TileKey {
+    aspect_mask: ImageAspectFlags,
+    mip_level: u32,
+    array_layer: u32,
+    tile: [u32; 3],
+}
+
+
+/// An image created with sparse residency whose memory is bound one tile at
+/// a time.
+///
+/// Tiles of mip levels below each aspect's mip tail are bound individually.
+/// The mip tail is bound as a whole using `::commit_mip_tail`.
+///
+#[derive(Debug)]
+pub struct SparseImage {
+    image: Image,
+    block_size: DeviceSize,
+    aspects: SmallVec<[SparseImageAspect; 4]>,
+    tiles: BTreeMap<TileKey, ResidentMemory>,
+    mip_tails: BTreeMap<(ImageAspectFlags, u32), ResidentMemory>,
+}
+
+impl SparseImage {
+    /// Creates a new sparsely resident image as specified by `builder`.
+    ///
+    /// The builder's flags must include `ImageCreateFlags::SPARSE_BINDING`
+    /// and `ImageCreateFlags::SPARSE_RESIDENCY`.
+    pub fn new(device: Device, builder: &ImageBuilder) -> VdResult<SparseImage> {
+        let image = builder.build(device)?;
+        if !image.flags().contains(ImageCreateFlags::SPARSE_BINDING |
+                ImageCreateFlags::SPARSE_RESIDENCY) {
+            return invalid("image was not created with sparse binding and residency flags");
+        }
+
+        let requirements = unsafe {
+            image.device().get_image_sparse_memory_requirements(&image)
+        };
+        if requirements.is_empty() {
+            return invalid("image format does not support sparse residency");
+        }
+        let aspects = requirements.iter().map(|req| {
+            let props = req.format_properties();
+            SparseImageAspect {
+                aspect_mask: props.aspect_mask(),
+                tile_extent: props.image_granularity().clone(),
+                flags: props.flags(),
+                mip_tail_first_lod: req.image_mip_tail_first_lod(),
+                mip_tail_size: req.image_mip_tail_size(),
+                mip_tail_offset: req.image_mip_tail_offset(),
+                mip_tail_stride: req.image_mip_tail_stride(),
+            }
+        }).collect();
+        let block_size = cmp::max(image.memory_requirements().alignment(), 1);
+
+        Ok(SparseImage {
+            image,
+            block_size,
+            aspects,
+            tiles: BTreeMap::new(),
+            mip_tails: BTreeMap::new(),
+        })
+    }
+
+    /// Returns the sparse layout of the aspect containing `aspect_mask`.
+    fn aspect(&self, aspect_mask: ImageAspectFlags) -> VdResult<&SparseImageAspect> {
+        match self.aspects.iter().find(|a| a.aspect_mask.contains(aspect_mask)) {
+            Some(aspect) => Ok(aspect),
+            None => invalid("aspect is not part of the image's sparse layout"),
+        }
+    }
+
+    /// Returns the extent in texels of `mip_level`.
+    fn mip_extent(&self, mip_level: u32) -> [u32; 3] {
+        let extent = self.image.extent();
+        [cmp::max(extent.width() >> mip_level, 1),
+            cmp::max(extent.height() >> mip_level, 1),
+            cmp::max(extent.depth() >> mip_level, 1)]
+    }
+
+    /// Validates `region` and returns the offset and extent in texels it
+    /// covers along with its tile count.
+    fn region_bounds(&self, region: &SparseImageRegion) -> VdResult<(Offset3d, Extent3d, u32)> {
+        let aspect = self.aspect(region.aspect_mask)?;
+        if region.mip_level >= aspect.mip_tail_first_lod || region.mip_level >= self.image.mip_levels() {
+            return invalid("mip level is part of the mip tail or out of range");
+        }
+        if region.array_layer >= self.image.array_layers() {
+            return invalid("array layer is out of range");
+        }
+
+        let mip_extent = self.mip_extent(region.mip_level);
+        let tile = [aspect.tile_extent.width(), aspect.tile_extent.height(),
+            aspect.tile_extent.depth()];
+        let mut offset = [0; 3];
+        let mut extent = [0; 3];
+        for d in 0..3 {
+            let (start, len) = tile_bounds(region.first_tile[d], region.tile_count[d], tile[d],
+                mip_extent[d])?;
+            offset[d] = start;
+            extent[d] = len;
+        }
+
+        let tile_count = match region.tile_count[0].checked_mul(region.tile_count[1])
+                .and_then(|count| count.checked_mul(region.tile_count[2])) {
+            Some(tile_count) => tile_count,
+            None => return invalid("region has too many tiles"),
+        };
+
+        Ok((Offset3d::builder().x(offset[0] as i32).y(offset[1] as i32).z(offset[2] as i32).build(),
+            Extent3d::builder().width(extent[0]).height(extent[1]).depth(extent[2]).build(),
+            tile_count))
+    }
+
+    /// Returns the page table keys of every tile in `region`.
+    fn region_tiles(region: &SparseImageRegion) -> Vec<TileKey> {
+        let mut keys = Vec::new();
+        for z in 0..region.tile_count[2] {
+            for y in 0..region.tile_count[1] {
+                for x in 0..region.tile_count[0] {
+                    keys.push(TileKey {
+                        aspect_mask: region.aspect_mask,
+                        mip_level: region.mip_level,
+                        array_layer: region.array_layer,
+                        tile: [region.first_tile[0] + x, region.first_tile[1] + y,
+                            region.first_tile[2] + z],
+                    });
+                }
+            }
+        }
+        keys
+    }
+
+    fn subresource(region: &SparseImageRegion) -> ImageSubresource {
+        ImageSubresource::builder()
+            .aspect_mask(region.aspect_mask)
+            .mip_level(region.mip_level)
+            .array_layer(region.array_layer)
+            .build()
+    }
+
+    /// Records binds making the tiles within `regions` resident, backed by
+    /// consecutive ranges of `memory` starting at `memory_offset`.
+    ///
+    /// Each tile consumes one sparse block (`::block_size` bytes). Tiles
+    /// which are already resident are rebound.
+    pub fn commit(&mut self, batch: &mut SparseBindBatch, regions: &[SparseImageRegion],
+            memory: &DeviceMemory, memory_offset: DeviceSize) -> VdResult<()> {
+        let mut bounds = SmallVec::<[(Offset3d, Extent3d, u32); 8]>::new();
+        let mut total: DeviceSize = 0;
+        for region in regions {
+            let b = self.region_bounds(region)?;
+            total = match total.checked_add(b.2 as DeviceSize * self.block_size) {
+                Some(total) => total,
+                None => return invalid("regions exceed the size of any memory allocation"),
+            };
+            bounds.push(b);
+        }
+        check_memory(memory, self.image.memory_requirements().memory_type_bits(),
+            memory_offset, total, self.block_size)?;
+
+        let mut offset = memory_offset;
+        for (region, (texel_offset, texel_extent, tile_count)) in regions.iter().zip(bounds) {
+            batch.push_image_bind(&self.image, SparseImageMemoryBind::builder()
+                .subresource(SparseImage::subresource(region))
+                .offset(texel_offset)
+                .extent(texel_extent)
+                .memory(memory)
+                .memory_offset(offset)
+                .build());
+            for (i, key) in SparseImage::region_tiles(region).into_iter().enumerate() {
+                let resident = ResidentMemory { memory: memory.clone(),
+                    offset: offset + i as DeviceSize * self.block_size };
+                if let Some(old) = self.tiles.insert(key, resident) {
+                    batch.keep_alive(&old.memory);
+                }
+            }
+            offset += tile_count as DeviceSize * self.block_size;
+        }
+        batch.keep_alive(memory);
+        Ok(())
+    }
+
+    /// Records binds making the tiles within `regions` non-resident.
+    pub fn evict(&mut self, batch: &mut SparseBindBatch, regions: &[SparseImageRegion])
+            -> VdResult<()> {
+        for region in regions {
+            let (texel_offset, texel_extent, _) = self.region_bounds(region)?;
+            batch.push_image_bind(&self.image, SparseImageMemoryBind::builder()
+                .subresource(SparseImage::subresource(region))
+                .offset(texel_offset)
+                .extent(texel_extent)
+                .memory(DeviceMemoryHandle(0))
+                .build());
+            for key in SparseImage::region_tiles(region) {
+                if let Some(old) = self.tiles.remove(&key) {
+                    batch.keep_alive(&old.memory);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Records a bind making the mip tail of `array_layer` for the aspect
+    /// containing `aspect_mask` resident, backed by `memory` at
+    /// `memory_offset`.
+    ///
+    /// `array_layer` is ignored if the aspect has a single mip tail.
+    pub fn commit_mip_tail(&mut self, batch: &mut SparseBindBatch, aspect_mask: ImageAspectFlags,
+            array_layer: u32, memory: &DeviceMemory, memory_offset: DeviceSize) -> VdResult<()> {
+        let (key, resource_offset, size, flags) = self.mip_tail_bind(aspect_mask, array_layer)?;
+        check_memory(memory, self.image.memory_requirements().memory_type_bits(),
+            memory_offset, size, self.block_size)?;
+
+        batch.push_image_opaque_bind(&self.image, SparseMemoryBind::builder()
+            .resource_offset(resource_offset)
+            .size(size)
+            .memory(memory)
+            .memory_offset(memory_offset)
+            .flags(flags)
+            .build());
+        let resident = ResidentMemory { memory: memory.clone(), offset: memory_offset };
+        if let Some(old) = self.mip_tails.insert(key, resident) {
+            batch.keep_alive(&old.memory);
+        }
+        batch.keep_alive(memory);
+        Ok(())
+    }
+
+    /// Records a bind making the mip tail of `array_layer` for the aspect
+    /// containing `aspect_mask` non-resident.
+    pub fn evict_mip_tail(&mut self, batch: &mut SparseBindBatch, aspect_mask: ImageAspectFlags,
+            array_layer: u32) -> VdResult<()> {
+        let (key, resource_offset, size, flags) = self.mip_tail_bind(aspect_mask, array_layer)?;
+        batch.push_image_opaque_bind(&self.image, SparseMemoryBind::builder()
+            .resource_offset(resource_offset)
+            .size(size)
+            .memory(DeviceMemoryHandle(0))
+            .flags(flags)
+            .build());
+        if let Some(old) = self.mip_tails.remove(&key) {
+            batch.keep_alive(&old.memory);
+        }
+        Ok(())
+    }
+
+    /// Returns the page table key, resource offset, size and bind flags of a
+    /// mip tail.
+    fn mip_tail_bind(&self, aspect_mask: ImageAspectFlags, array_layer: u32)
+            -> VdResult<((ImageAspectFlags, u32), DeviceSize, DeviceSize, SparseMemoryBindFlags)> {
+        let aspect = self.aspect(aspect_mask)?;
+        if aspect.mip_tail_first_lod >= self.image.mip_levels() {
+            return invalid("aspect has no mip tail");
+        }
+        if array_layer >= self.image.array_layers() {
+            return invalid("array layer is out of range");
+        }
+        let layer = if aspect.has_single_mip_tail() { 0 } else { array_layer };
+        let flags = if aspect.aspect_mask.contains(ImageAspectFlags::METADATA) {
+            SparseMemoryBindFlags::METADATA
+        } else {
+            SparseMemoryBindFlags::empty()
+        };
+        Ok(((aspect.aspect_mask, layer), aspect.mip_tail_offset(layer), aspect.mip_tail_size,
+            flags))
+    }
+
+    /// Returns true if the tile at `tile` of the given subresource is
+    /// resident.
+    pub fn is_resident(&self, aspect_mask: ImageAspectFlags, mip_level: u32, array_layer: u32,
+            tile: [u32; 3]) -> bool {
+        self.tiles.contains_key(&TileKey { aspect_mask, mip_level, array_layer, tile })
+    }
+
+    /// Returns true if the mip tail of `array_layer` for the aspect
+    /// containing `aspect_mask` is resident.
+    pub fn is_mip_tail_resident(&self, aspect_mask: ImageAspectFlags, array_layer: u32) -> bool {
+        match self.aspect(aspect_mask) {
+            Ok(aspect) => {
+                let layer = if aspect.has_single_mip_tail() { 0 } else { array_layer };
+                self.mip_tails.contains_key(&(aspect.aspect_mask, layer))
+            },
+            Err(_) => false,
+        }
+    }
+
+    /// Returns the number of resident tiles, excluding mip tails.
+    pub fn resident_tile_count(&self) -> usize {
+        self.tiles.len()
+    }
+
+    /// Returns the number of tiles in each dimension of `mip_level` for the
+    /// aspect containing `aspect_mask`.
+    pub fn tile_count(&self, aspect_mask: ImageAspectFlags, mip_level: u32) -> VdResult<[u32; 3]> {
+        let aspect = self.aspect(aspect_mask)?;
+        let mip_extent = self.mip_extent(mip_level);
+        let tile = [aspect.tile_extent.width(), aspect.tile_extent.height(),
+            aspect.tile_extent.depth()];
+        Ok([(mip_extent[0] + tile[0] - 1) / tile[0], (mip_extent[1] + tile[1] - 1) / tile[1],
+            (mip_extent[2] + tile[2] - 1) / tile[2]])
+    }
+
+    /// Returns the size in bytes of the memory backing a single tile.
+    pub fn block_size(&self) -> DeviceSize {
+        self.block_size
+    }
+
+    /// Returns the sparse layout of each aspect of the image.
+    pub fn aspects(&self) -> &[SparseImageAspect] {
+        &self.aspects
+    }
+
+    /// Returns the underlying image.
+    pub fn image(&self) -> &Image {
+        &self.image
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tile_bounds_clamp_to_the_mip_level() {
+        assert_eq!(tile_bounds(0, 2, 32, 100).unwrap(), (0, 64));
+        // The last tile may extend past the end of the level:
+        assert_eq!(tile_bounds(2, 2, 32, 100).unwrap(), (64, 36));
+        assert!(tile_bounds(0, 0, 32, 100).is_err());
+        assert!(tile_bounds(4, 1, 32, 100).is_err());
+        assert!(tile_bounds(2, 3, 32, 100).is_err());
+    }
+
+    #[test]
+    fn tile_bounds_reject_overflow() {
+        // Would wrap to a start of 0 and an end of 32:
+        assert!(tile_bounds(1 << 27, 1, 32, 100).is_err());
+        assert!(tile_bounds(0, u32::max_value(), 32, 100).is_err());
+        assert!(tile_bounds(1, u32::max_value(), 1, 100).is_err());
+    }
+}
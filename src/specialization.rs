@@ -0,0 +1,276 @@
+use std::mem;
+use libc::c_void;
+use ::{VdResult, ErrorKind, SpecializationInfo, SpecializationMapEntry};
+use reflect::{ScalarType, ShaderReflection};
+
+
+/// A type which may be used as the value of a specialization constant.
+///
+/// Boolean constants are specified as `u32` values (`VkBool32`).
+pub unsafe trait SpecConstantType: Copy {
+    /// Returns the SPIR-V scalar type corresponding to this type.
+    fn scalar_type() -> ScalarType;
+}
+
+macro_rules! impl_spec_constant_type {
+    ($($ty:ty => $scalar_type:expr),*) => {
+        $(unsafe impl SpecConstantType for $ty {
+            fn scalar_type() -> ScalarType { $scalar_type }
+        })*
+    };
+}
+
+impl_spec_constant_type!(
+    u32 => ScalarType::Int { width: 32, signed: false },
+    i32 => ScalarType::Int { width: 32, signed: true },
+    u64 => ScalarType::Int { width: 64, signed: false },
+    i64 => ScalarType::Int { width: 64, signed: true },
+    f32 => ScalarType::Float { width: 32 },
+    f64 => ScalarType::Float { width: 64 }
+);
+
+
+/// The constant ID, location and type of a field of a `SpecializationData`
+/// struct.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpecConstantField {
+    pub constant_id: u32,
+    pub offset: u32,
+    pub size: usize,
+    pub scalar_type: ScalarType,
+}
+
+impl SpecConstantField {
+    /// Returns the description of the field at address `field` of a struct
+    /// at address `base`. Used by `specialization_data!`.
+    #[doc(hidden)]
+    pub fn of<T: SpecConstantType>(constant_id: u32, base: *const u8, field: *const T)
+            -> SpecConstantField {
+        SpecConstantField {
+            constant_id,
+            offset: (field as usize - base as usize) as u32,
+            size: mem::size_of::<T>(),
+            scalar_type: T::scalar_type(),
+        }
+    }
+}
+
+
+/// A `#[repr(C)]` struct whose fields are the values of specialization
+/// constants.
+///
+/// Implement this trait with the `specialization_data!` macro, which assigns
+/// a constant ID to each field:
+///
+/// ```ignore
+/// #[repr(C)]
+/// #[derive(Clone, Copy)]
+/// struct Constants { workgroup_size: u32, scale: f32 }
+///
+/// specialization_data!(Constants { workgroup_size: 0, scale: 1 });
+///
+/// let constants = Constants { workgroup_size: 64, scale: 0.5 };
+/// let specialization = Specialization::new(&constants)?;
+/// ComputePipeline::builder().specialization(&specialization) // ...
+/// ```
+///
+/// Implementations must only describe fields which lie within `Self`.
+pub unsafe trait SpecializationData: Sized {
+    /// Returns the constant ID, offset, size and type of each field.
+    fn fields() -> Vec<SpecConstantField>;
+}
+
+/// Implements `SpecializationData` for a `#[repr(C)]` struct, assigning
+/// each listed field a constant ID.
+#[macro_export]
+macro_rules! specialization_data {
+    ($ty:ty { $($field:ident: $constant_id:expr),* $(,)* }) => {
+        unsafe impl $crate::SpecializationData for $ty {
+            fn fields() -> Vec<$crate::SpecConstantField> {
+                let mut fields = Vec::new();
+                // Offsets are taken from a live (uninitialized) value; no
+                // references to its fields are created.
+                let value = ::std::mem::MaybeUninit::<$ty>::uninit();
+                let base = value.as_ptr();
+                unsafe {
+                    $(fields.push($crate::SpecConstantField::of($constant_id, base as *const u8,
+                        ::std::ptr::addr_of!((*base).$field)));)*
+                }
+                fields
+            }
+        }
+    };
+}
+
+
+/// The specialization constants of a shader stage, borrowing a
+/// `SpecializationData` struct.
+///
+/// Pass `::info` to `PipelineShaderStageCreateInfo::specialization_info`
+/// (for `GraphicsPipelineBuilder::stages`) or the whole specialization to
+/// `ComputePipelineBuilder::specialization`.
+#[derive(Debug)]
+pub struct Specialization<'d> {
+    fields: Vec<SpecConstantField>,
+    // Boxed so that `info` remains valid when this struct is moved:
+    _map_entries: Box<[SpecializationMapEntry]>,
+    info: SpecializationInfo<'d>,
+}
+
+impl<'d> Specialization<'d> {
+    /// Returns a new specialization with the constant values in `data`.
+    ///
+    /// Returns an `InvalidSpecialization` error if a field described by
+    /// `D::fields` does not lie within `D` or two fields share a constant ID.
+    pub fn new<D: SpecializationData>(data: &'d D) -> VdResult<Specialization<'d>> {
+        let fields = D::fields();
+        for (i, field) in fields.iter().enumerate() {
+            if fields[..i].iter().any(|other| other.constant_id == field.constant_id) {
+                return Err(ErrorKind::InvalidSpecialization { constant_id: field.constant_id,
+                    reason: "more than one field has this constant ID" }.into());
+            }
+            let in_bounds = (field.offset as usize).checked_add(field.size)
+                .map(|end| end <= mem::size_of::<D>())
+                .unwrap_or(false);
+            if !in_bounds {
+                return Err(ErrorKind::InvalidSpecialization { constant_id: field.constant_id,
+                    reason: "the field does not lie within the specialization data" }.into());
+            }
+        }
+
+        let map_entries: Box<[SpecializationMapEntry]> = fields.iter()
+            .map(|field| {
+                SpecializationMapEntry::builder()
+                    .constant_id(field.constant_id)
+                    .offset(field.offset)
+                    .size(field.size)
+                    .build()
+            })
+            .collect::<Vec<_>>()
+            .into_boxed_slice();
+
+        let mut info = SpecializationInfo::default();
+        unsafe {
+            info.set_map_entries(&*(&*map_entries as *const [SpecializationMapEntry]));
+            info.set_data_size(mem::size_of::<D>());
+            info.set_data(data as *const D as *const c_void);
+        }
+
+        Ok(Specialization { fields, _map_entries: map_entries, info })
+    }
+
+    /// Returns the fields of the specialization data.
+    pub fn fields(&self) -> &[SpecConstantField] {
+        &self.fields
+    }
+
+    /// Returns the specialization info, valid for as long as this
+    /// specialization.
+    pub fn info<'s>(&'s self) -> &'s SpecializationInfo<'s> {
+        &self.info
+    }
+
+    /// Checks the constant IDs and types of the fields against the
+    /// specialization constants declared by a shader module.
+    ///
+    /// Returns an `InvalidSpecialization` error if the module declares no
+    /// constant with a field's ID or declares it with a different type.
+    pub fn validate(&self, reflection: &ShaderReflection) -> VdResult<()> {
+        for field in self.fields.iter() {
+            let constant = reflection.spec_constants().iter()
+                .find(|constant| constant.id == field.constant_id)
+                .ok_or_else(|| ErrorKind::InvalidSpecialization { constant_id: field.constant_id,
+                    reason: "the shader module declares no constant with this ID" })?;
+            let compatible = match constant.scalar_type {
+                ScalarType::Bool =>
+                    field.scalar_type == ScalarType::Int { width: 32, signed: false },
+                scalar_type => field.scalar_type == scalar_type,
+            };
+            if !compatible {
+                return Err(ErrorKind::InvalidSpecialization { constant_id: field.constant_id,
+                    reason: "the field type does not match the constant's type" }.into());
+            }
+        }
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::mem;
+    use super::*;
+
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    struct Constants {
+        count: u32,
+        scale: f64,
+        flag: u32,
+    }
+
+    specialization_data!(Constants { count: 0, scale: 4, flag: 7 });
+
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    struct Duplicate {
+        a: u32,
+        b: u32,
+    }
+
+    specialization_data!(Duplicate { a: 2, b: 2 });
+
+    #[allow(dead_code)]
+    struct OutOfBounds(u32);
+
+    unsafe impl SpecializationData for OutOfBounds {
+        fn fields() -> Vec<SpecConstantField> {
+            vec![SpecConstantField { constant_id: 3, offset: 2, size: 4,
+                scalar_type: ScalarType::Int { width: 32, signed: false } }]
+        }
+    }
+
+    #[test]
+    fn field_offsets() {
+        let fields = Constants::fields();
+        assert_eq!(fields.len(), 3);
+        assert_eq!(fields[0], SpecConstantField { constant_id: 0, offset: 0, size: 4,
+            scalar_type: ScalarType::Int { width: 32, signed: false } });
+        assert_eq!(fields[1], SpecConstantField { constant_id: 4, offset: 8, size: 8,
+            scalar_type: ScalarType::Float { width: 64 } });
+        assert_eq!(fields[2], SpecConstantField { constant_id: 7, offset: 16, size: 4,
+            scalar_type: ScalarType::Int { width: 32, signed: false } });
+        assert_eq!(mem::size_of::<Constants>(), 24);
+    }
+
+    #[test]
+    fn new_describes_data() {
+        let constants = Constants { count: 64, scale: 0.5, flag: 1 };
+        let specialization = Specialization::new(&constants).unwrap();
+        assert_eq!(specialization.fields(), &Constants::fields()[..]);
+    }
+
+    #[test]
+    fn new_rejects_out_of_bounds_fields() {
+        let data = OutOfBounds(0);
+        match Specialization::new(&data) {
+            Err(err) => match *err.kind() {
+                ErrorKind::InvalidSpecialization { constant_id: 3, .. } => (),
+                _ => panic!("unexpected error: {}", err),
+            },
+            Ok(_) => panic!("out of bounds field accepted"),
+        }
+    }
+
+    #[test]
+    fn new_rejects_duplicate_constant_ids() {
+        let data = Duplicate { a: 1, b: 2 };
+        match Specialization::new(&data) {
+            Err(err) => match *err.kind() {
+                ErrorKind::InvalidSpecialization { constant_id: 2, .. } => (),
+                _ => panic!("unexpected error: {}", err),
+            },
+            Ok(_) => panic!("duplicate constant ID accepted"),
+        }
+    }
+}
@@ -0,0 +1,327 @@
+use std::cmp;
+use std::mem;
+use std::collections::VecDeque;
+use ::{VdResult, Device, Buffer, DeviceMemory, Image, Fence, FenceStatus, CommandBuffer,
+    DeviceSize, BufferUsageFlags, SharingMode, MemoryPropertyFlags, BufferCopy, BufferImageCopy,
+    ImageLayout, MemoryTypeSelector, ErrorKind, PersistentMapping, Pod};
+
+
+/// Rounds `val` up to a multiple of `alignment`.
+#[inline]
+fn align_up(val: DeviceSize, alignment: DeviceSize) -> DeviceSize {
+    if alignment <= 1 { val } else { ((val + alignment - 1) / alignment) * alignment }
+}
+
+fn gcd(a: DeviceSize, b: DeviceSize) -> DeviceSize {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+/// Returns the least common multiple of two non-zero alignments.
+fn lcm(a: DeviceSize, b: DeviceSize) -> DeviceSize {
+    a / gcd(a, b) * b
+}
+
+/// Finds space for `size` bytes aligned to `alignment` in a ring buffer of
+/// `capacity` bytes whose monotonic write and release counters are `head` and
+/// `tail`.
+///
+/// Returns the offset of the space within the buffer and the new `head`, or
+/// `None` if it would overrun unreleased data. Space never straddles the end
+/// of the buffer.
+fn ring_reserve(head: DeviceSize, tail: DeviceSize, capacity: DeviceSize, size: DeviceSize,
+        alignment: DeviceSize) -> Option<(DeviceSize, DeviceSize)> {
+    let pos = head % capacity;
+    let mut offset = align_up(pos, alignment);
+    let mut start = head + (offset - pos);
+
+    // Wrap around rather than straddle the end of the buffer:
+    if offset + size > capacity {
+        start = head + (capacity - pos);
+        offset = 0;
+    }
+    let end = start + size;
+
+    if end - tail > capacity { None } else { Some((offset, end)) }
+}
+
+
+/// A linear ring allocator for streaming data to the device.
+///
+/// A `StagingBelt` owns a persistently mapped, host-visible `Buffer` and
+/// hands out aligned sub-ranges of it. Ranges written during a frame are
+/// associated with that frame's `Fence` by `::finish_frame` and the space
+/// they occupy is reclaimed once the fence is signaled.
+///
+/// Offsets respect `optimalBufferCopyOffsetAlignment` (and
+/// `minUniformBufferOffsetAlignment` for `::write_uniform`). Writes are
+/// flushed automatically, in units of `nonCoherentAtomSize`, when the
+/// underlying memory is not host-coherent.
+///
+///
+/// ### Destruction
+///
+/// Dropping this `StagingBelt` frees its buffer and memory. The caller must
+/// ensure that no submitted commands still read from it.
+///
+#[derive(Debug)]
+pub struct StagingBelt {
+    buffer: Buffer,
+    mapping: PersistentMapping,
+    capacity: DeviceSize,
+    non_coherent_atom_size: DeviceSize,
+    min_uniform_buffer_offset_alignment: DeviceSize,
+    optimal_buffer_copy_offset_alignment: DeviceSize,
+    // Monotonic byte counters. Positions within the buffer are taken modulo
+    // `capacity`.
+    head: DeviceSize,
+    tail: DeviceSize,
+    frame_start: DeviceSize,
+    in_flight: VecDeque<(Fence, DeviceSize)>,
+}
+
+impl StagingBelt {
+    /// Creates and returns a new `StagingBelt` of at least `capacity` bytes.
+    ///
+    /// `usage` is combined with `BufferUsageFlags::TRANSFER_SRC` (use
+    /// `UNIFORM_BUFFER` to bind ranges written with `::write_uniform`).
+    pub fn new(device: Device, capacity: DeviceSize, usage: BufferUsageFlags)
+            -> VdResult<StagingBelt> {
+        let limits = device.physical_device().properties().limits().clone();
+        let non_coherent_atom_size = cmp::max(limits.non_coherent_atom_size(), 1);
+        let capacity = align_up(capacity, non_coherent_atom_size);
+
+        let buffer = Buffer::builder()
+            .size(capacity)
+            .usage(usage | BufferUsageFlags::TRANSFER_SRC)
+            .sharing_mode(SharingMode::Exclusive)
+            .build(device.clone())?;
+
+        let memory_requirements = buffer.memory_requirements().clone();
+        let memory_type_index = MemoryTypeSelector::new()
+            .required(MemoryPropertyFlags::HOST_VISIBLE)
+            .preferred(MemoryPropertyFlags::HOST_COHERENT)
+            .select(&device, memory_requirements.memory_type_bits())?;
+
+        let memory = DeviceMemory::new(device, memory_requirements.size(), memory_type_index)?;
+        unsafe { buffer.bind_memory(&memory, 0)?; }
+        let mapping = memory.persistent_mapping()?;
+
+        Ok(StagingBelt {
+            buffer,
+            mapping,
+            capacity,
+            non_coherent_atom_size,
+            min_uniform_buffer_offset_alignment: limits.min_uniform_buffer_offset_alignment(),
+            optimal_buffer_copy_offset_alignment: limits.optimal_buffer_copy_offset_alignment(),
+            head: 0,
+            tail: 0,
+            frame_start: 0,
+            in_flight: VecDeque::new(),
+        })
+    }
+
+    /// Reserves `size` bytes aligned to `alignment` and returns the offset of
+    /// the reservation within the buffer.
+    ///
+    /// Reclaims space from completed frames if necessary.
+    fn reserve(&mut self, size: DeviceSize, alignment: DeviceSize) -> VdResult<DeviceSize> {
+        let mut alignment = cmp::max(alignment, 1);
+        if !self.mapping.is_coherent() {
+            alignment = lcm(alignment, self.non_coherent_atom_size);
+        }
+        let capacity = self.capacity;
+        let out_of_space = move || ErrorKind::OutOfStagingSpace { requested: size, capacity };
+        if size > self.capacity {
+            return Err(out_of_space().into());
+        }
+
+        let reservation = match ring_reserve(self.head, self.tail, self.capacity, size, alignment) {
+            Some(reservation) => reservation,
+            None => {
+                self.reclaim()?;
+                ring_reserve(self.head, self.tail, self.capacity, size, alignment)
+                    .ok_or_else(out_of_space)?
+            },
+        };
+
+        let (offset, end) = reservation;
+        self.head = end;
+        Ok(offset)
+    }
+
+    /// Copies `data` into a newly reserved range and returns its offset.
+    fn write_aligned<T: Pod>(&mut self, data: &[T], alignment: DeviceSize)
+            -> VdResult<DeviceSize> {
+        let size = (mem::size_of::<T>() * data.len()) as DeviceSize;
+        let alignment = lcm(cmp::max(alignment, 1), mem::align_of::<T>() as DeviceSize);
+        let offset = self.reserve(size, alignment)?;
+        self.mapping.write(offset..offset + size, data)?;
+        Ok(offset)
+    }
+
+    /// Copies `data` into the belt, aligned for use as the source of a copy
+    /// command, and returns its offset within `::buffer`.
+    pub fn write<T: Pod>(&mut self, data: &[T]) -> VdResult<DeviceSize> {
+        let alignment = self.optimal_buffer_copy_offset_alignment;
+        self.write_aligned(data, alignment)
+    }
+
+    /// Copies `data` into the belt, aligned for use as a uniform buffer
+    /// (e.g. with a dynamic offset), and returns its offset within
+    /// `::buffer`.
+    pub fn write_uniform<T: Pod>(&mut self, data: &T) -> VdResult<DeviceSize> {
+        let alignment = self.min_uniform_buffer_offset_alignment;
+        self.write_aligned(::std::slice::from_ref(data), alignment)
+    }
+
+    /// Stages `data` and records a copy of it to `dst_buffer` at
+    /// `dst_offset` into `command_buffer`.
+    pub fn upload_to_buffer<T: Pod>(&mut self, command_buffer: &CommandBuffer, data: &[T],
+            dst_buffer: &Buffer, dst_offset: DeviceSize) -> VdResult<()> {
+        let src_offset = self.write(data)?;
+        let region = BufferCopy::builder()
+            .src_offset(src_offset)
+            .dst_offset(dst_offset)
+            .size((mem::size_of::<T>() * data.len()) as DeviceSize)
+            .build();
+
+        unsafe { command_buffer.copy_buffer(&self.buffer, dst_buffer, &[region]); }
+        Ok(())
+    }
+
+    /// Stages `data` and records a copy of it to `dst_image` into
+    /// `command_buffer`.
+    ///
+    /// The `buffer_offset` of each region is relative to the start of `data`.
+    /// `data` is staged at an offset which is a multiple of four and of the
+    /// texel block size of `dst_image`'s format, as required for copies to
+    /// images. `dst_image` must be in `dst_image_layout` when the copy
+    /// executes.
+    pub fn upload_to_image(&mut self, command_buffer: &CommandBuffer, data: &[u8],
+            dst_image: &Image, dst_image_layout: ImageLayout, regions: &[BufferImageCopy])
+            -> VdResult<()> {
+        let block_size = cmp::max(dst_image.format().block_size() as DeviceSize, 1);
+        let alignment = lcm(lcm(cmp::max(self.optimal_buffer_copy_offset_alignment, 1), 4),
+            block_size);
+        let src_offset = self.write_aligned(data, alignment)?;
+        let regions: Vec<BufferImageCopy> = regions.iter().map(|region| {
+            let mut region = region.clone();
+            let buffer_offset = region.buffer_offset() + src_offset;
+            region.set_buffer_offset(buffer_offset);
+            region
+        }).collect();
+
+        unsafe {
+            command_buffer.copy_buffer_to_image(&self.buffer, dst_image, dst_image_layout,
+                &regions);
+        }
+        Ok(())
+    }
+
+    /// Associates every range written since the previous call with `fence`.
+    ///
+    /// Their space is reclaimed once `fence` is signaled.
+    pub fn finish_frame(&mut self, fence: &Fence) {
+        if self.head != self.frame_start {
+            self.in_flight.push_back((fence.clone(), self.head));
+            self.frame_start = self.head;
+        }
+    }
+
+    /// Reclaims space used by frames whose fences have been signaled.
+    pub fn reclaim(&mut self) -> VdResult<()> {
+        loop {
+            let end = match self.in_flight.front() {
+                Some(&(ref fence, end)) => match fence.status()? {
+                    FenceStatus::Signaled => end,
+                    FenceStatus::Unsignaled => break,
+                    FenceStatus::Error(res) => {
+                        return Err(ErrorKind::ApiCall(res, "vkGetFenceStatus").into());
+                    },
+                },
+                None => break,
+            };
+            self.in_flight.pop_front();
+            self.tail = end;
+        }
+        Ok(())
+    }
+
+    /// Reclaims all space, including that of the current frame.
+    ///
+    /// ## Safety
+    ///
+    /// The caller must ensure that all commands reading from this belt have
+    /// completed (e.g. after `Queue::wait_idle`).
+    pub unsafe fn reset(&mut self) {
+        self.in_flight.clear();
+        self.frame_start = self.head;
+        self.tail = self.head;
+    }
+
+    /// Returns the buffer backing this belt.
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    /// Returns the capacity of this belt in bytes.
+    pub fn capacity(&self) -> DeviceSize {
+        self.capacity
+    }
+
+    /// Returns the number of bytes currently in use (including alignment
+    /// padding).
+    pub fn used(&self) -> DeviceSize {
+        self.head - self.tail
+    }
+
+    /// Returns a reference to the associated device.
+    pub fn device(&self) -> &Device {
+        self.buffer.device()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn alignment() {
+        assert_eq!(align_up(13, 4), 16);
+        assert_eq!(align_up(16, 4), 16);
+        assert_eq!(align_up(7, 0), 7);
+        assert_eq!(align_up(7, 12), 12);
+        assert_eq!(lcm(4, 12), 12);
+        assert_eq!(lcm(64, 12), 192);
+        assert_eq!(lcm(lcm(1, 4), 3), 12);
+    }
+
+    #[test]
+    fn ring_reserve_aligns() {
+        assert_eq!(ring_reserve(0, 0, 256, 10, 16), Some((0, 10)));
+        assert_eq!(ring_reserve(10, 0, 256, 10, 16), Some((16, 26)));
+        assert_eq!(ring_reserve(26, 0, 256, 8, 12), Some((36, 44)));
+    }
+
+    #[test]
+    fn ring_reserve_wraps() {
+        // 40 bytes remain at the end of the buffer, so the reservation wraps
+        // to offset zero once the tail has advanced far enough:
+        assert_eq!(ring_reserve(216, 0, 256, 64, 16), None);
+        assert_eq!(ring_reserve(216, 63, 256, 64, 16), None);
+        assert_eq!(ring_reserve(216, 64, 256, 64, 16), Some((0, 320)));
+
+        // Positions are taken modulo the capacity after wrapping:
+        assert_eq!(ring_reserve(320, 200, 256, 16, 16), Some((64, 336)));
+        assert_eq!(ring_reserve(336, 200, 256, 128, 16), None);
+        assert_eq!(ring_reserve(336, 208, 256, 128, 16), Some((80, 464)));
+    }
+
+    #[test]
+    fn ring_reserve_full_capacity() {
+        assert_eq!(ring_reserve(0, 0, 256, 256, 16), Some((0, 256)));
+        assert_eq!(ring_reserve(256, 0, 256, 1, 1), None);
+        assert_eq!(ring_reserve(256, 256, 256, 256, 1), Some((0, 512)));
+    }
+}
@@ -2,7 +2,8 @@ use std::sync::Arc;
 use std::marker::PhantomData;
 use smallvec::SmallVec;
 use vks;
-use ::{VdResult, SurfaceKhr, Device, PhysicalDevice, Image, Handle, Semaphore, Fence};
+use ::{VdResult, SurfaceKhr, Device, PhysicalDevice, Image, Handle, Semaphore, Fence,
+    ImageCreateInfo, ImageType, Extent3d, SampleCountFlags, ImageTiling, SharingMode};
 
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -321,9 +322,25 @@ impl<'b> SwapchainKhrBuilder<'b> {
 
         let handle = unsafe { device.create_swapchain_khr(&self.create_info, None)? };
 
+        // Describes the images as if they were created by the application:
+        let mut image_create_info = ImageCreateInfo::default();
+        image_create_info.set_image_type(ImageType::Type2d);
+        image_create_info.set_format(image_format);
+        image_create_info.set_extent(Extent3d::builder().width(extent.width())
+            .height(extent.height()).depth(1).build());
+        image_create_info.set_mip_levels(1);
+        image_create_info.set_array_layers(self.create_info.image_array_layers());
+        image_create_info.set_samples(SampleCountFlags::COUNT_1);
+        image_create_info.set_tiling(ImageTiling::Optimal);
+        image_create_info.set_usage(self.create_info.image_usage());
+        image_create_info.set_sharing_mode(self.create_info.image_sharing_mode());
+        if self.create_info.image_sharing_mode() == SharingMode::Concurrent {
+            image_create_info.set_queue_family_indices(self.create_info.queue_family_indices());
+        }
+
         let images = unsafe {
             device.get_swapchain_images_khr(handle)?.iter().map(|&h| {
-                Image::from_handle(device.clone(), h, true)
+                Image::from_handle(device.clone(), h, &image_create_info, true)
             }).collect()
         };
 
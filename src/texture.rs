@@ -0,0 +1,876 @@
+//! KTX2 and DDS texture container loading.
+//!
+//! `TextureData` parses a container, including its mip levels, array layers,
+//! cube faces and block-compressed formats. `TextureData::upload` (or
+//! `Texture::load`) validates it against the physical device's format
+//! support and produces a device-local `Image` and a matching `ImageView`.
+
+use std::cmp;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use num_traits::FromPrimitive;
+use ::{VdResult, Device, Queue, Image, ImageView, UploadToken, Format, Extent3d, ImageType,
+    ImageViewType, ImageTiling, ImageUsageFlags, ImageCreateFlags, ImageCreateInfo, ImageLayout,
+    SampleCountFlags, SharingMode, FormatFeatureFlags, MemoryTypeSelector, MemoryPropertyFlags,
+    ComponentMapping, ImageSubresourceRange, ImageSubresourceLayers, ImageMemoryBarrier,
+    BufferImageCopy, Offset3d, AccessFlags, PipelineStageFlags, DependencyFlags, ErrorKind,
+    QUEUE_FAMILY_IGNORED};
+use alloc::Allocation;
+use upload::{self, UploadBarrier};
+
+
+const KTX2_IDENTIFIER: [u8; 12] = [0xAB, 0x4B, 0x54, 0x58, 0x20, 0x32, 0x30, 0xBB, 0x0D, 0x0A,
+    0x1A, 0x0A];
+const DDS_MAGIC: [u8; 4] = *b"DDS ";
+
+// DDS header flags:
+const DDSD_MIPMAPCOUNT: u32 = 0x20000;
+const DDPF_ALPHAPIXELS: u32 = 0x1;
+const DDPF_FOURCC: u32 = 0x4;
+const DDPF_RGB: u32 = 0x40;
+const DDPF_LUMINANCE: u32 = 0x20000;
+const DDSCAPS2_CUBEMAP: u32 = 0x200;
+const DDSCAPS2_VOLUME: u32 = 0x200000;
+const DDS_RESOURCE_MISC_TEXTURECUBE: u32 = 0x4;
+const DDS_DIMENSION_TEXTURE1D: u32 = 2;
+const DDS_DIMENSION_TEXTURE3D: u32 = 4;
+
+
+/// A texture container format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ContainerFormat {
+    Ktx2,
+    Dds,
+}
+
+impl ContainerFormat {
+    /// Detects the container format of `bytes` from its magic number.
+    pub fn detect(bytes: &[u8]) -> Option<ContainerFormat> {
+        if bytes.starts_with(&KTX2_IDENTIFIER) {
+            Some(ContainerFormat::Ktx2)
+        } else if bytes.starts_with(&DDS_MAGIC) {
+            Some(ContainerFormat::Dds)
+        } else {
+            None
+        }
+    }
+}
+
+
+fn invalid(reason: &'static str) -> ::Error {
+    ErrorKind::InvalidTextureData { reason }.into()
+}
+
+fn unsupported(format: Format, reason: &'static str) -> ::Error {
+    ErrorKind::UnsupportedTexture { format, reason }.into()
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> VdResult<u32> {
+    match bytes.get(offset..offset + 4) {
+        Some(b) => Ok(b[0] as u32 | (b[1] as u32) << 8 | (b[2] as u32) << 16 | (b[3] as u32) << 24),
+        None => Err(invalid("unexpected end of data")),
+    }
+}
+
+fn read_u64(bytes: &[u8], offset: usize) -> VdResult<u64> {
+    Ok(read_u32(bytes, offset)? as u64 | (read_u32(bytes, offset + 4)? as u64) << 32)
+}
+
+/// Returns the extent of mip level `level` of an image of extent `extent`.
+///
+/// `level` must be less than `max_mip_levels(extent)`.
+fn level_extent(extent: &Extent3d, level: u32) -> [u32; 3] {
+    [cmp::max(extent.width() >> level, 1), cmp::max(extent.height() >> level, 1),
+        cmp::max(extent.depth() >> level, 1)]
+}
+
+/// Returns the number of levels in a full mip chain for `extent`.
+fn max_mip_levels(extent: &Extent3d) -> u32 {
+    32 - cmp::max(cmp::max(extent.width(), extent.height()), extent.depth()).leading_zeros()
+}
+
+/// Returns the size in bytes of mip level `level` of one layer, or an error
+/// if it does not fit in memory.
+fn level_size(format: Format, extent: &Extent3d, level: u32) -> VdResult<usize> {
+    let [width, height, depth] = level_extent(extent, level);
+    let [block_width, block_height, block_depth] = format.block_extent();
+    let blocks = |texels: u32, block: u32| {
+        (texels / block + if texels % block == 0 { 0 } else { 1 }) as usize
+    };
+    blocks(width, block_width).checked_mul(blocks(height, block_height))
+        .and_then(|n| n.checked_mul(blocks(depth, block_depth)))
+        .and_then(|n| n.checked_mul(format.block_size() as usize))
+        .ok_or_else(|| invalid("texture dimensions too large"))
+}
+
+/// Checks the mip level count against `extent` and returns the total number
+/// of subresources, which must not exceed `data_len` (every subresource
+/// holds at least one byte).
+fn subresource_count(extent: &Extent3d, mip_levels: u32, array_layers: u32, data_len: usize)
+        -> VdResult<usize> {
+    if mip_levels > max_mip_levels(extent) {
+        return Err(invalid("mip level count exceeds the full mip chain"));
+    }
+    (mip_levels as usize).checked_mul(array_layers as usize)
+        .and_then(|count| if count <= data_len { Some(count) } else { None })
+        .ok_or_else(|| invalid("subresource count exceeds the data size"))
+}
+
+
+/// The contents of a texture container.
+///
+/// Subresources are addressed by mip level and layer, where the layers of a
+/// cube map are its faces (`+X`, `-X`, `+Y`, `-Y`, `+Z`, `-Z`), and the
+/// faces of each cube of a cube array are consecutive layers.
+#[derive(Debug, Clone)]
+pub struct TextureData {
+    container_format: ContainerFormat,
+    format: Format,
+    image_type: ImageType,
+    view_type: ImageViewType,
+    extent: Extent3d,
+    mip_levels: u32,
+    array_layers: u32,
+    data: Vec<u8>,
+    // The offset into `data` of each subresource, indexed by
+    // `level * array_layers + layer`:
+    subresource_offsets: Vec<usize>,
+    // The size of one layer of each level:
+    level_sizes: Vec<usize>,
+}
+
+impl TextureData {
+    /// Parses a KTX2 or DDS container, detecting its format.
+    pub fn from_bytes(bytes: Vec<u8>) -> VdResult<TextureData> {
+        match ContainerFormat::detect(&bytes) {
+            Some(ContainerFormat::Ktx2) => TextureData::from_ktx2(bytes),
+            Some(ContainerFormat::Dds) => TextureData::from_dds(bytes),
+            None => Err(invalid("unrecognized container format")),
+        }
+    }
+
+    /// Reads and parses the KTX2 or DDS file at `path`.
+    pub fn open<P: AsRef<Path>>(path: P) -> VdResult<TextureData> {
+        let mut bytes = Vec::new();
+        File::open(path)?.read_to_end(&mut bytes)?;
+        TextureData::from_bytes(bytes)
+    }
+
+    /// Parses a KTX2 container.
+    ///
+    /// Supercompressed files are not supported. A level count of zero is
+    /// treated as a single level.
+    pub fn from_ktx2(bytes: Vec<u8>) -> VdResult<TextureData> {
+        if !bytes.starts_with(&KTX2_IDENTIFIER) {
+            return Err(invalid("missing KTX2 identifier"));
+        }
+        let vk_format = read_u32(&bytes, 12)?;
+        let width = read_u32(&bytes, 20)?;
+        let height = read_u32(&bytes, 24)?;
+        let depth = read_u32(&bytes, 28)?;
+        let layer_count = read_u32(&bytes, 32)?;
+        let face_count = read_u32(&bytes, 36)?;
+        let level_count = cmp::max(read_u32(&bytes, 40)?, 1);
+        let supercompression_scheme = read_u32(&bytes, 44)?;
+
+        let format = match Format::from_u32(vk_format) {
+            Some(Format::Undefined) | None => return Err(invalid("unknown or undefined vkFormat")),
+            Some(format) => format,
+        };
+        if supercompression_scheme != 0 {
+            return Err(unsupported(format, "supercompressed KTX2 files are not supported"));
+        }
+        if width == 0 || (face_count != 1 && face_count != 6) ||
+                (face_count == 6 && (width != height || depth != 0)) {
+            return Err(invalid("invalid KTX2 dimensions"));
+        }
+
+        let (image_type, view_type) = match (height, depth, face_count, layer_count) {
+            (0, 0, 1, 0) => (ImageType::Type1d, ImageViewType::Type1d),
+            (0, 0, 1, _) => (ImageType::Type1d, ImageViewType::Type1dArray),
+            (_, 0, 6, 0) => (ImageType::Type2d, ImageViewType::Cube),
+            (_, 0, 6, _) => (ImageType::Type2d, ImageViewType::CubeArray),
+            (_, 0, _, 0) => (ImageType::Type2d, ImageViewType::Type2d),
+            (_, 0, _, _) => (ImageType::Type2d, ImageViewType::Type2dArray),
+            (_, _, _, 0) => (ImageType::Type3d, ImageViewType::Type3d),
+            _ => return Err(invalid("3D array textures are not supported")),
+        };
+        let extent = Extent3d::builder()
+            .width(width)
+            .height(cmp::max(height, 1))
+            .depth(cmp::max(depth, 1))
+            .build();
+        let array_layers = cmp::max(layer_count, 1).checked_mul(face_count)
+            .ok_or_else(|| invalid("invalid KTX2 layer count"))?;
+        let count = subresource_count(&extent, level_count, array_layers, bytes.len())?;
+
+        // The level index follows the 48 byte header and 32 byte section index:
+        let mut subresource_offsets = Vec::with_capacity(count);
+        let mut level_sizes = Vec::with_capacity(level_count as usize);
+        for level in 0..level_count {
+            let entry = 80 + level as usize * 24;
+            let byte_offset = read_u64(&bytes, entry)? as usize;
+            let byte_length = read_u64(&bytes, entry + 8)? as usize;
+            let layer_size = level_size(format, &extent, level)?;
+            let in_bounds = layer_size.checked_mul(array_layers as usize)
+                .map(|size| size <= byte_length)
+                .unwrap_or(false) &&
+                byte_offset.checked_add(byte_length).map(|end| end <= bytes.len()).unwrap_or(false);
+            if !in_bounds {
+                return Err(invalid("KTX2 level data out of bounds"));
+            }
+            for layer in 0..array_layers as usize {
+                subresource_offsets.push(byte_offset + layer * layer_size);
+            }
+            level_sizes.push(layer_size);
+        }
+
+        Ok(TextureData {
+            container_format: ContainerFormat::Ktx2,
+            format,
+            image_type,
+            view_type,
+            extent,
+            mip_levels: level_count,
+            array_layers,
+            data: bytes,
+            subresource_offsets,
+            level_sizes,
+        })
+    }
+
+    /// Parses a DDS container.
+    ///
+    /// Files with a `DX10` header extension are supported for all DXGI
+    /// formats with a Vulkan equivalent. Legacy files are supported for the
+    /// `DXT1`-`DXT5`, `ATI1`/`ATI2` (`BC4`/`BC5`) and float four-character
+    /// codes and for 32-bit RGBA and 8-bit luminance pixel formats.
+    pub fn from_dds(bytes: Vec<u8>) -> VdResult<TextureData> {
+        if !bytes.starts_with(&DDS_MAGIC) || read_u32(&bytes, 4)? != 124 {
+            return Err(invalid("missing DDS magic number or header"));
+        }
+        let flags = read_u32(&bytes, 8)?;
+        let height = read_u32(&bytes, 12)?;
+        let width = read_u32(&bytes, 16)?;
+        let depth = read_u32(&bytes, 24)?;
+        let mip_levels = if flags & DDSD_MIPMAPCOUNT != 0 {
+            cmp::max(read_u32(&bytes, 28)?, 1)
+        } else {
+            1
+        };
+        let pf_flags = read_u32(&bytes, 80)?;
+        let four_cc = read_u32(&bytes, 84)?;
+        let caps2 = read_u32(&bytes, 112)?;
+
+        let is_dx10 = pf_flags & DDPF_FOURCC != 0 && four_cc == fourcc(b"DX10");
+        let (format, image_type, cube, layers, data_offset) = if is_dx10 {
+            let dxgi_format = read_u32(&bytes, 128)?;
+            let dimension = read_u32(&bytes, 132)?;
+            let misc_flag = read_u32(&bytes, 136)?;
+            let array_size = cmp::max(read_u32(&bytes, 140)?, 1);
+            let format = dxgi_format_to_format(dxgi_format)
+                .ok_or_else(|| invalid("unsupported DXGI format"))?;
+            let image_type = match dimension {
+                DDS_DIMENSION_TEXTURE1D => ImageType::Type1d,
+                DDS_DIMENSION_TEXTURE3D => ImageType::Type3d,
+                _ => ImageType::Type2d,
+            };
+            (format, image_type, misc_flag & DDS_RESOURCE_MISC_TEXTURECUBE != 0, array_size, 148)
+        } else {
+            let format = legacy_dds_format(&bytes, pf_flags, four_cc)?;
+            let image_type = if caps2 & DDSCAPS2_VOLUME != 0 && depth > 1 {
+                ImageType::Type3d
+            } else {
+                ImageType::Type2d
+            };
+            (format, image_type, caps2 & DDSCAPS2_CUBEMAP != 0, 1, 128)
+        };
+
+        if width == 0 || (cube && (width != height || image_type != ImageType::Type2d)) {
+            return Err(invalid("invalid DDS dimensions"));
+        }
+        let view_type = match (image_type, cube, layers) {
+            (ImageType::Type1d, _, 1) => ImageViewType::Type1d,
+            (ImageType::Type1d, _, _) => ImageViewType::Type1dArray,
+            (ImageType::Type3d, _, 1) => ImageViewType::Type3d,
+            (ImageType::Type3d, _, _) => return Err(invalid("3D array textures are not supported")),
+            (_, true, 1) => ImageViewType::Cube,
+            (_, true, _) => ImageViewType::CubeArray,
+            (_, false, 1) => ImageViewType::Type2d,
+            (_, false, _) => ImageViewType::Type2dArray,
+        };
+        let extent = Extent3d::builder()
+            .width(width)
+            .height(if image_type == ImageType::Type1d { 1 } else { cmp::max(height, 1) })
+            .depth(if image_type == ImageType::Type3d { cmp::max(depth, 1) } else { 1 })
+            .build();
+        let array_layers = layers.checked_mul(if cube { 6 } else { 1 })
+            .ok_or_else(|| invalid("invalid DDS array size"))?;
+        let count = subresource_count(&extent, mip_levels, array_layers, bytes.len())?;
+        let level_sizes = (0..mip_levels)
+            .map(|level| level_size(format, &extent, level))
+            .collect::<VdResult<Vec<_>>>()?;
+
+        // DDS stores each layer's full mip chain in turn:
+        let mut subresource_offsets = vec![0; count];
+        let mut offset: usize = data_offset;
+        for layer in 0..array_layers as usize {
+            for level in 0..mip_levels as usize {
+                subresource_offsets[level * array_layers as usize + layer] = offset;
+                offset = match offset.checked_add(level_sizes[level]) {
+                    Some(end) if end <= bytes.len() => end,
+                    _ => return Err(invalid("DDS data out of bounds")),
+                };
+            }
+        }
+
+        Ok(TextureData {
+            container_format: ContainerFormat::Dds,
+            format,
+            image_type,
+            view_type,
+            extent,
+            mip_levels,
+            array_layers,
+            data: bytes,
+            subresource_offsets,
+            level_sizes,
+        })
+    }
+
+    /// Returns the container format this texture was parsed from.
+    pub fn container_format(&self) -> ContainerFormat {
+        self.container_format
+    }
+
+    /// Returns the texel format.
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    /// Returns the image type.
+    pub fn image_type(&self) -> ImageType {
+        self.image_type
+    }
+
+    /// Returns the image view type matching this texture's dimensions,
+    /// layers and faces.
+    pub fn view_type(&self) -> ImageViewType {
+        self.view_type
+    }
+
+    /// Returns the extent of the first mip level.
+    pub fn extent(&self) -> &Extent3d {
+        &self.extent
+    }
+
+    /// Returns the number of mip levels.
+    pub fn mip_levels(&self) -> u32 {
+        self.mip_levels
+    }
+
+    /// Returns the number of array layers, counting each cube face as a
+    /// layer.
+    pub fn array_layers(&self) -> u32 {
+        self.array_layers
+    }
+
+    /// Returns true if this texture is a cube map or cube map array.
+    pub fn is_cube(&self) -> bool {
+        self.view_type == ImageViewType::Cube || self.view_type == ImageViewType::CubeArray
+    }
+
+    /// Returns the tightly packed texel data of mip level `level` of layer
+    /// `layer`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `level` or `layer` is out of range.
+    pub fn subresource_data(&self, level: u32, layer: u32) -> &[u8] {
+        assert!(level < self.mip_levels && layer < self.array_layers,
+            "subresource (level {}, layer {}) out of range", level, layer);
+        let offset = self.subresource_offsets[(level * self.array_layers + layer) as usize];
+        &self.data[offset..offset + self.level_sizes[level as usize]]
+    }
+
+    /// Creates a device-local image with `usage` (plus `SAMPLED` and
+    /// `TRANSFER_DST`) holding this texture, and a view of all of its levels
+    /// and layers.
+    ///
+    /// Returns an error if the format, dimensions, level count or layer
+    /// count is not supported by the physical device for sampled
+    /// optimal-tiling images, or if the format is a combined depth/stencil
+    /// format. The upload runs as described for
+    /// `Image::from_pixels`; the image is left in
+    /// `ImageLayout::ShaderReadOnlyOptimal`.
+    pub fn upload(&self, device: &Device, usage: ImageUsageFlags, queue: &Queue)
+            -> VdResult<(Texture, UploadToken)> {
+        let format = self.format;
+        let usage = usage | ImageUsageFlags::SAMPLED | ImageUsageFlags::TRANSFER_DST;
+        let flags = if self.is_cube() {
+            ImageCreateFlags::CUBE_COMPATIBLE
+        } else {
+            ImageCreateFlags::empty()
+        };
+        self.validate(device, usage, flags)?;
+
+        let mut create_info = ImageCreateInfo::default();
+        create_info.set_flags(flags);
+        create_info.set_image_type(self.image_type);
+        create_info.set_format(format);
+        create_info.set_extent(self.extent.clone());
+        create_info.set_mip_levels(self.mip_levels);
+        create_info.set_array_layers(self.array_layers);
+        create_info.set_samples(SampleCountFlags::COUNT_1);
+        create_info.set_tiling(ImageTiling::Optimal);
+        create_info.set_usage(usage);
+        create_info.set_sharing_mode(SharingMode::Exclusive);
+        create_info.set_initial_layout(ImageLayout::Undefined);
+        let image = Image::with_allocation(device.clone(), &create_info, |requirements| {
+            let memory_type_index = MemoryTypeSelector::new()
+                .required(MemoryPropertyFlags::DEVICE_LOCAL)
+                .select(device, requirements.memory_type_bits())?;
+            Allocation::dedicated(device.clone(), requirements, memory_type_index)
+        })?;
+
+        // Repack the subresources so that each starts at a multiple of both
+        // the texel block size and four bytes, as required for copies:
+        let block_size = format.block_size() as usize;
+        let alignment = block_size * 4 / gcd(block_size, 4);
+        let mut staging_data = Vec::with_capacity(self.data.len());
+        let mut regions = Vec::with_capacity(self.subresource_offsets.len());
+        let aspect_mask = format.aspects();
+        for level in 0..self.mip_levels {
+            let [width, height, depth] = level_extent(&self.extent, level);
+            for layer in 0..self.array_layers {
+                let padding = (alignment - staging_data.len() % alignment) % alignment;
+                staging_data.extend((0..padding).map(|_| 0u8));
+                regions.push(BufferImageCopy::builder()
+                    .buffer_offset(staging_data.len() as u64)
+                    .buffer_row_length(0)
+                    .buffer_image_height(0)
+                    .image_subresource(ImageSubresourceLayers::builder()
+                        .aspect_mask(aspect_mask)
+                        .mip_level(level)
+                        .base_array_layer(layer)
+                        .layer_count(1)
+                        .build())
+                    .image_offset(Offset3d::builder().x(0).y(0).z(0).build())
+                    .image_extent(Extent3d::builder().width(width).height(height).depth(depth)
+                        .build())
+                    .build());
+                staging_data.extend_from_slice(self.subresource_data(level, layer));
+            }
+        }
+        let staging_buffer = upload::staging_buffer(device, &staging_data[..])?;
+
+        let subresource_range = ImageSubresourceRange::builder()
+            .aspect_mask(aspect_mask)
+            .base_mip_level(0)
+            .level_count(self.mip_levels)
+            .base_array_layer(0)
+            .layer_count(self.array_layers)
+            .build();
+
+        let token = upload::upload(device, queue, staging_buffer, true,
+            |command_buffer, staging_buffer| {
+                let to_transfer_dst = ImageMemoryBarrier::builder()
+                    .src_access_mask(AccessFlags::empty())
+                    .dst_access_mask(AccessFlags::TRANSFER_WRITE)
+                    .old_layout(ImageLayout::Undefined)
+                    .new_layout(ImageLayout::TransferDstOptimal)
+                    .src_queue_family_index(QUEUE_FAMILY_IGNORED)
+                    .dst_queue_family_index(QUEUE_FAMILY_IGNORED)
+                    .image(&image)
+                    .subresource_range(subresource_range.clone())
+                    .build();
+                command_buffer.pipeline_barrier(PipelineStageFlags::TOP_OF_PIPE,
+                    PipelineStageFlags::TRANSFER, DependencyFlags::empty(), &[], &[],
+                    &[to_transfer_dst]);
+                unsafe {
+                    command_buffer.copy_buffer_to_image(staging_buffer, &image,
+                        ImageLayout::TransferDstOptimal, &regions);
+                }
+            },
+            |command_buffer, barrier: UploadBarrier| {
+                let (src_family, dst_family) = barrier.queue_family_indices();
+                let image_barrier = ImageMemoryBarrier::builder()
+                    .src_access_mask(barrier.src_access_mask())
+                    .dst_access_mask(barrier.dst_access_mask())
+                    .old_layout(ImageLayout::TransferDstOptimal)
+                    .new_layout(ImageLayout::ShaderReadOnlyOptimal)
+                    .src_queue_family_index(src_family)
+                    .dst_queue_family_index(dst_family)
+                    .image(&image)
+                    .subresource_range(subresource_range.clone())
+                    .build();
+                command_buffer.pipeline_barrier(barrier.src_stage_mask(),
+                    barrier.dst_stage_mask(), DependencyFlags::empty(), &[], &[],
+                    &[image_barrier]);
+            })?;
+
+        let view = ImageView::builder()
+            .image(&image)
+            .view_type(self.view_type)
+            .format(format)
+            .components(ComponentMapping::default())
+            .subresource_range(subresource_range.clone())
+            .build(device.clone(), None)?;
+
+        Ok((Texture { image, view }, token))
+    }
+
+    /// Checks that the physical device supports images of this texture's
+    /// format and dimensions.
+    fn validate(&self, device: &Device, usage: ImageUsageFlags, flags: ImageCreateFlags)
+            -> VdResult<()> {
+        let format = self.format;
+        let physical_device = device.physical_device();
+        if format.aspects().bits().count_ones() != 1 {
+            return Err(unsupported(format, "combined depth/stencil formats are not supported"));
+        }
+        let features = physical_device.format_properties(format).optimal_tiling_features();
+        if !features.contains(FormatFeatureFlags::SAMPLED_IMAGE) {
+            return Err(unsupported(format, "format does not support sampled optimal-tiling \
+                images"));
+        }
+        // Transfer features are only reported by implementations supporting
+        // `VK_KHR_maintenance1`; older ones report neither bit:
+        let transfer = FormatFeatureFlags::TRANSFER_SRC_KHR | FormatFeatureFlags::TRANSFER_DST_KHR;
+        if features.intersects(transfer) &&
+                !features.contains(FormatFeatureFlags::TRANSFER_DST_KHR) {
+            return Err(unsupported(format, "format does not support transfers to \
+                optimal-tiling images"));
+        }
+
+        let properties = physical_device.image_format_properties(format, self.image_type,
+            ImageTiling::Optimal, usage, flags)
+            .map_err(|err| unsupported(format, "image format properties unavailable").chain(err))?;
+        let max_extent = properties.max_extent();
+        if self.extent.width() > max_extent.width() || self.extent.height() > max_extent.height() ||
+                self.extent.depth() > max_extent.depth() {
+            return Err(unsupported(format, "extent exceeds the maximum supported extent"));
+        }
+        if self.mip_levels > properties.max_mip_levels() {
+            return Err(unsupported(format, "mip level count exceeds the maximum supported"));
+        }
+        if self.array_layers > properties.max_array_layers() {
+            return Err(unsupported(format, "array layer count exceeds the maximum supported"));
+        }
+        Ok(())
+    }
+}
+
+
+/// A sampled image loaded from a texture container, and a view of all of its
+/// levels and layers.
+#[derive(Debug, Clone)]
+pub struct Texture {
+    image: Image,
+    view: ImageView,
+}
+
+impl Texture {
+    /// Reads the KTX2 or DDS file at `path` and uploads it. See
+    /// `TextureData::upload`.
+    pub fn load<P: AsRef<Path>>(device: &Device, path: P, usage: ImageUsageFlags, queue: &Queue)
+            -> VdResult<(Texture, UploadToken)> {
+        TextureData::open(path)?.upload(device, usage, queue)
+    }
+
+    /// Returns the image.
+    pub fn image(&self) -> &Image {
+        &self.image
+    }
+
+    /// Returns the view of the entire image.
+    pub fn view(&self) -> &ImageView {
+        &self.view
+    }
+
+    /// Returns the image and view, consuming this `Texture`.
+    pub fn into_parts(self) -> (Image, ImageView) {
+        (self.image, self.view)
+    }
+}
+
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+fn fourcc(code: &[u8; 4]) -> u32 {
+    code[0] as u32 | (code[1] as u32) << 8 | (code[2] as u32) << 16 | (code[3] as u32) << 24
+}
+
+/// Returns the format of a DDS file without a `DX10` header extension.
+fn legacy_dds_format(bytes: &[u8], pf_flags: u32, four_cc: u32) -> VdResult<Format> {
+    if pf_flags & DDPF_FOURCC != 0 {
+        let format = match four_cc {
+            c if c == fourcc(b"DXT1") => Format::Bc1RgbaUnormBlock,
+            c if c == fourcc(b"DXT2") || c == fourcc(b"DXT3") => Format::Bc2UnormBlock,
+            c if c == fourcc(b"DXT4") || c == fourcc(b"DXT5") => Format::Bc3UnormBlock,
+            c if c == fourcc(b"ATI1") || c == fourcc(b"BC4U") => Format::Bc4UnormBlock,
+            c if c == fourcc(b"BC4S") => Format::Bc4SnormBlock,
+            c if c == fourcc(b"ATI2") || c == fourcc(b"BC5U") => Format::Bc5UnormBlock,
+            c if c == fourcc(b"BC5S") => Format::Bc5SnormBlock,
+            // D3DFMT_A16B16G16R16, D3DFMT_A16B16G16R16F, D3DFMT_A32B32G32R32F:
+            36 => Format::R16G16B16A16Unorm,
+            113 => Format::R16G16B16A16Sfloat,
+            116 => Format::R32G32B32A32Sfloat,
+            _ => return Err(invalid("unsupported DDS four-character code")),
+        };
+        return Ok(format);
+    }
+
+    let bit_count = read_u32(bytes, 88)?;
+    let masks = [read_u32(bytes, 92)?, read_u32(bytes, 96)?, read_u32(bytes, 100)?,
+        if pf_flags & DDPF_ALPHAPIXELS != 0 { read_u32(bytes, 104)? } else { 0 }];
+    match (pf_flags & (DDPF_RGB | DDPF_LUMINANCE), bit_count, masks) {
+        (DDPF_RGB, 32, [0xff, 0xff00, 0xff0000, _]) => Ok(Format::R8G8B8A8Unorm),
+        (DDPF_RGB, 32, [0xff0000, 0xff00, 0xff, _]) => Ok(Format::B8G8R8A8Unorm),
+        (DDPF_LUMINANCE, 8, _) => Ok(Format::R8Unorm),
+        _ => Err(invalid("unsupported DDS pixel format")),
+    }
+}
+
+/// Returns the format equivalent to a `DXGI_FORMAT` value.
+fn dxgi_format_to_format(dxgi_format: u32) -> Option<Format> {
+    let format = match dxgi_format {
+        2 => Format::R32G32B32A32Sfloat,
+        3 => Format::R32G32B32A32Uint,
+        4 => Format::R32G32B32A32Sint,
+        6 => Format::R32G32B32Sfloat,
+        7 => Format::R32G32B32Uint,
+        8 => Format::R32G32B32Sint,
+        10 => Format::R16G16B16A16Sfloat,
+        11 => Format::R16G16B16A16Unorm,
+        12 => Format::R16G16B16A16Uint,
+        13 => Format::R16G16B16A16Snorm,
+        14 => Format::R16G16B16A16Sint,
+        16 => Format::R32G32Sfloat,
+        17 => Format::R32G32Uint,
+        18 => Format::R32G32Sint,
+        24 => Format::A2B10G10R10UnormPack32,
+        25 => Format::A2B10G10R10UintPack32,
+        26 => Format::B10G11R11UfloatPack32,
+        28 => Format::R8G8B8A8Unorm,
+        29 => Format::R8G8B8A8Srgb,
+        30 => Format::R8G8B8A8Uint,
+        31 => Format::R8G8B8A8Snorm,
+        32 => Format::R8G8B8A8Sint,
+        34 => Format::R16G16Sfloat,
+        35 => Format::R16G16Unorm,
+        36 => Format::R16G16Uint,
+        37 => Format::R16G16Snorm,
+        38 => Format::R16G16Sint,
+        40 => Format::D32Sfloat,
+        41 => Format::R32Sfloat,
+        42 => Format::R32Uint,
+        43 => Format::R32Sint,
+        45 => Format::D24UnormS8Uint,
+        49 => Format::R8G8Unorm,
+        50 => Format::R8G8Uint,
+        51 => Format::R8G8Snorm,
+        52 => Format::R8G8Sint,
+        54 => Format::R16Sfloat,
+        55 => Format::D16Unorm,
+        56 => Format::R16Unorm,
+        57 => Format::R16Uint,
+        58 => Format::R16Snorm,
+        59 => Format::R16Sint,
+        61 => Format::R8Unorm,
+        62 => Format::R8Uint,
+        63 => Format::R8Snorm,
+        64 => Format::R8Sint,
+        67 => Format::E5B9G9R9UfloatPack32,
+        71 => Format::Bc1RgbaUnormBlock,
+        72 => Format::Bc1RgbaSrgbBlock,
+        74 => Format::Bc2UnormBlock,
+        75 => Format::Bc2SrgbBlock,
+        77 => Format::Bc3UnormBlock,
+        78 => Format::Bc3SrgbBlock,
+        80 => Format::Bc4UnormBlock,
+        81 => Format::Bc4SnormBlock,
+        83 => Format::Bc5UnormBlock,
+        84 => Format::Bc5SnormBlock,
+        85 => Format::R5G6B5UnormPack16,
+        86 => Format::A1R5G5B5UnormPack16,
+        87 => Format::B8G8R8A8Unorm,
+        91 => Format::B8G8R8A8Srgb,
+        95 => Format::Bc6hUfloatBlock,
+        96 => Format::Bc6hSfloatBlock,
+        98 => Format::Bc7UnormBlock,
+        99 => Format::Bc7SrgbBlock,
+        _ => return None,
+    };
+    Some(format)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn put_u32(bytes: &mut Vec<u8>, offset: usize, value: u32) {
+        if bytes.len() < offset + 4 { bytes.resize(offset + 4, 0); }
+        for i in 0..4 { bytes[offset + i] = (value >> (i * 8)) as u8; }
+    }
+
+    fn put_u64(bytes: &mut Vec<u8>, offset: usize, value: u64) {
+        put_u32(bytes, offset, value as u32);
+        put_u32(bytes, offset + 4, (value >> 32) as u32);
+    }
+
+    /// Returns a 4x4 `R8G8B8A8Unorm` KTX2 file with a full mip chain.
+    fn ktx2() -> Vec<u8> {
+        let mut bytes = KTX2_IDENTIFIER.to_vec();
+        put_u32(&mut bytes, 12, Format::R8G8B8A8Unorm as u32);
+        put_u32(&mut bytes, 20, 4);
+        put_u32(&mut bytes, 24, 4);
+        put_u32(&mut bytes, 36, 1);
+        put_u32(&mut bytes, 40, 3);
+        let data_offset = 80 + 3 * 24;
+        let mut offset = data_offset;
+        for (level, &size) in [64usize, 16, 4].iter().enumerate() {
+            put_u64(&mut bytes, 80 + level * 24, offset as u64);
+            put_u64(&mut bytes, 80 + level * 24 + 8, size as u64);
+            offset += size;
+        }
+        bytes.resize(data_offset, 0);
+        bytes.extend((0..84).map(|i| i as u8));
+        bytes
+    }
+
+    /// Returns a 4x4 32-bit RGBA DDS file with two mip levels.
+    fn dds() -> Vec<u8> {
+        let mut bytes = DDS_MAGIC.to_vec();
+        put_u32(&mut bytes, 4, 124);
+        put_u32(&mut bytes, 8, DDSD_MIPMAPCOUNT);
+        put_u32(&mut bytes, 12, 4);
+        put_u32(&mut bytes, 16, 4);
+        put_u32(&mut bytes, 28, 2);
+        put_u32(&mut bytes, 80, DDPF_RGB | DDPF_ALPHAPIXELS);
+        put_u32(&mut bytes, 88, 32);
+        put_u32(&mut bytes, 92, 0xff);
+        put_u32(&mut bytes, 96, 0xff00);
+        put_u32(&mut bytes, 100, 0xff0000);
+        put_u32(&mut bytes, 104, 0xff000000);
+        bytes.resize(128, 0);
+        bytes.extend((0..80).map(|i| i as u8));
+        bytes
+    }
+
+    /// Returns a DX10 DDS header of a 4x4 `R8G8B8A8Unorm` texture with
+    /// `array_size` layers and no data.
+    fn dds_dx10(array_size: u32, cube: bool) -> Vec<u8> {
+        let mut bytes = DDS_MAGIC.to_vec();
+        put_u32(&mut bytes, 4, 124);
+        put_u32(&mut bytes, 12, 4);
+        put_u32(&mut bytes, 16, 4);
+        put_u32(&mut bytes, 80, DDPF_FOURCC);
+        put_u32(&mut bytes, 84, fourcc(b"DX10"));
+        put_u32(&mut bytes, 128, 28);
+        put_u32(&mut bytes, 132, 3);
+        put_u32(&mut bytes, 136, if cube { DDS_RESOURCE_MISC_TEXTURECUBE } else { 0 });
+        put_u32(&mut bytes, 140, array_size);
+        bytes.resize(148, 0);
+        bytes
+    }
+
+    fn assert_invalid(result: VdResult<TextureData>) {
+        match result {
+            Err(err) => match *err.kind() {
+                ErrorKind::InvalidTextureData { .. } => (),
+                _ => panic!("unexpected error: {}", err),
+            },
+            Ok(_) => panic!("invalid texture data accepted"),
+        }
+    }
+
+    #[test]
+    fn detect() {
+        assert_eq!(ContainerFormat::detect(&ktx2()), Some(ContainerFormat::Ktx2));
+        assert_eq!(ContainerFormat::detect(&dds()), Some(ContainerFormat::Dds));
+        assert_eq!(ContainerFormat::detect(b"\x89PNG"), None);
+    }
+
+    #[test]
+    fn parse_ktx2() {
+        let texture = TextureData::from_bytes(ktx2()).unwrap();
+        assert_eq!(texture.container_format(), ContainerFormat::Ktx2);
+        assert_eq!(texture.format(), Format::R8G8B8A8Unorm);
+        assert_eq!(texture.view_type(), ImageViewType::Type2d);
+        assert_eq!((texture.mip_levels(), texture.array_layers()), (3, 1));
+        assert_eq!(texture.subresource_data(0, 0).len(), 64);
+        assert_eq!(texture.subresource_data(1, 0)[0], 64);
+        assert_eq!(texture.subresource_data(2, 0), &[80, 81, 82, 83]);
+    }
+
+    #[test]
+    fn parse_dds() {
+        let texture = TextureData::from_bytes(dds()).unwrap();
+        assert_eq!(texture.container_format(), ContainerFormat::Dds);
+        assert_eq!(texture.format(), Format::R8G8B8A8Unorm);
+        assert_eq!((texture.mip_levels(), texture.array_layers()), (2, 1));
+        assert_eq!(texture.subresource_data(1, 0)[0], 64);
+        assert_eq!(texture.subresource_data(1, 0).len(), 16);
+    }
+
+    #[test]
+    fn truncated() {
+        for len in [0, 8, 40, 100, 151, 200, 235].iter() {
+            assert_invalid(TextureData::from_bytes(ktx2()[..*len].to_vec()));
+        }
+        for len in [4, 100, 130, 207].iter() {
+            assert_invalid(TextureData::from_bytes(dds()[..*len].to_vec()));
+        }
+    }
+
+    #[test]
+    fn hostile_ktx2_headers() {
+        let mut bytes = ktx2();
+        put_u32(&mut bytes, 40, 40);
+        assert_invalid(TextureData::from_ktx2(bytes));
+
+        let mut bytes = ktx2();
+        put_u32(&mut bytes, 40, 4);
+        assert_invalid(TextureData::from_ktx2(bytes));
+
+        let mut bytes = ktx2();
+        put_u32(&mut bytes, 32, u32::max_value());
+        put_u32(&mut bytes, 36, 6);
+        assert_invalid(TextureData::from_ktx2(bytes));
+
+        let mut bytes = ktx2();
+        put_u32(&mut bytes, 32, 0x1000_0000);
+        assert_invalid(TextureData::from_ktx2(bytes));
+
+        let mut bytes = ktx2();
+        put_u32(&mut bytes, 20, u32::max_value());
+        put_u32(&mut bytes, 24, u32::max_value());
+        put_u32(&mut bytes, 28, u32::max_value());
+        put_u32(&mut bytes, 40, 1);
+        assert_invalid(TextureData::from_ktx2(bytes));
+
+        let mut bytes = ktx2();
+        put_u64(&mut bytes, 80, u64::max_value());
+        assert_invalid(TextureData::from_ktx2(bytes));
+    }
+
+    #[test]
+    fn hostile_dds_headers() {
+        let mut bytes = dds();
+        put_u32(&mut bytes, 28, 100);
+        assert_invalid(TextureData::from_dds(bytes));
+
+        assert_invalid(TextureData::from_dds(dds_dx10(u32::max_value(), true)));
+        assert_invalid(TextureData::from_dds(dds_dx10(0x1000_0000, false)));
+        assert_invalid(TextureData::from_dds(dds_dx10(2, false)));
+
+        let mut bytes = dds_dx10(1, false);
+        bytes.extend((0..64).map(|_| 0u8));
+        assert!(TextureData::from_dds(bytes).is_ok());
+    }
+}
@@ -0,0 +1,262 @@
+use std::fmt;
+use std::mem;
+use std::ops::Range;
+use std::marker::PhantomData;
+use ::{VdResult, Device, Buffer, BufferBuilder, Queue, MemoryTypeSelector, DescriptorBufferInfo,
+    BufferUsageFlags, SharingMode, IndexType, DeviceSize, UploadToken, Pod, ErrorKind};
+use alloc::Allocator;
+
+
+mod private {
+    pub trait Sealed {}
+    impl Sealed for u16 {}
+    impl Sealed for u32 {}
+}
+
+/// An element type usable in an index buffer (`u16` or `u32`).
+pub trait IndexElement: Pod + private::Sealed {
+    /// The index type corresponding to this element type.
+    fn index_type() -> IndexType;
+}
+
+impl IndexElement for u16 {
+    fn index_type() -> IndexType { IndexType::Uint16 }
+}
+
+impl IndexElement for u32 {
+    fn index_type() -> IndexType { IndexType::Uint32 }
+}
+
+
+/// Returns the size of `T`, which must not be zero-sized.
+fn element_size<T>() -> VdResult<DeviceSize> {
+    match mem::size_of::<T>() {
+        0 => Err(ErrorKind::InvalidTypedBuffer { reason: "zero-sized element type" }.into()),
+        size => Ok(size as DeviceSize),
+    }
+}
+
+
+/// A `Buffer` holding `len` elements of type `T`.
+///
+/// Ranges of a `TypedBuffer` are addressed in elements rather than bytes.
+/// Use `::slice` to obtain a view usable with
+/// `CommandBuffer::bind_vertex_buffer_slice`,
+/// `CommandBuffer::bind_index_buffer_slice`,
+/// `CommandBuffer::copy_buffer_slice` and in descriptor writes.
+///
+///
+/// ### Destruction
+///
+/// Dropping this `TypedBuffer` drops its `Buffer`.
+///
+pub struct TypedBuffer<T: Pod> {
+    buffer: Buffer,
+    len: DeviceSize,
+    _p: PhantomData<T>,
+}
+
+impl<T: Pod> TypedBuffer<T> {
+    /// Wraps `buffer`, treating it as an array of `T`.
+    ///
+    /// The length is the number of whole elements which fit in the buffer.
+    /// Returns an error if `T` is zero-sized.
+    pub fn from_buffer(buffer: Buffer) -> VdResult<TypedBuffer<T>> {
+        let len = buffer.size() / element_size::<T>()?;
+        Ok(TypedBuffer { buffer, len, _p: PhantomData })
+    }
+
+    /// Creates a new buffer of `len` elements bound to memory sub-allocated
+    /// from `allocator`.
+    pub fn new_allocated(len: DeviceSize, usage: BufferUsageFlags, allocator: &Allocator,
+            memory_type: &MemoryTypeSelector) -> VdResult<TypedBuffer<T>> {
+        let size = len.checked_mul(element_size::<T>()?).ok_or_else(|| {
+            ErrorKind::InvalidTypedBuffer { reason: "buffer size overflows `DeviceSize`" }
+        })?;
+        let mut builder = BufferBuilder::new();
+        builder.size(size)
+            .usage(usage)
+            .sharing_mode(SharingMode::Exclusive);
+        let buffer = Buffer::new_allocated(&builder, allocator, memory_type)?;
+        Ok(TypedBuffer { buffer, len, _p: PhantomData })
+    }
+
+    /// Creates a new device-local buffer containing `data`. See
+    /// `Buffer::from_slice`.
+    pub fn from_slice(device: &Device, usage: BufferUsageFlags, data: &[T], queue: &Queue)
+            -> VdResult<(TypedBuffer<T>, UploadToken)> {
+        element_size::<T>()?;
+        let (buffer, token) = Buffer::from_slice(device, usage, data, queue)?;
+        Ok((TypedBuffer { buffer, len: data.len() as DeviceSize, _p: PhantomData }, token))
+    }
+
+    /// Returns the number of elements in this buffer.
+    pub fn len(&self) -> DeviceSize {
+        self.len
+    }
+
+    /// Returns true if this buffer holds no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the usage flags this buffer was created with.
+    pub fn usage(&self) -> BufferUsageFlags {
+        self.buffer.usage()
+    }
+
+    /// Returns the byte range occupied by the element range `elements`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `elements` is out of bounds.
+    pub fn byte_range(&self, elements: Range<DeviceSize>) -> Range<DeviceSize> {
+        assert!(elements.start <= elements.end && elements.end <= self.len,
+            "element range {:?} out of bounds for buffer of length {}", elements, self.len);
+        let size = mem::size_of::<T>() as DeviceSize;
+        elements.start * size..elements.end * size
+    }
+
+    /// Returns a view of the elements in `elements`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `elements` is out of bounds.
+    pub fn slice<'b>(&'b self, elements: Range<DeviceSize>) -> BufferSlice<'b, T> {
+        self.byte_range(elements.clone());
+        BufferSlice {
+            buffer: &self.buffer,
+            start: elements.start,
+            len: elements.end - elements.start,
+            _p: PhantomData,
+        }
+    }
+
+    /// Returns a view of the entire buffer.
+    pub fn as_slice<'b>(&'b self) -> BufferSlice<'b, T> {
+        self.slice(0..self.len)
+    }
+
+    /// Returns the underlying buffer.
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    /// Returns the underlying buffer, consuming this `TypedBuffer`.
+    pub fn into_buffer(self) -> Buffer {
+        self.buffer
+    }
+}
+
+impl<T: Pod> Clone for TypedBuffer<T> {
+    fn clone(&self) -> TypedBuffer<T> {
+        TypedBuffer { buffer: self.buffer.clone(), len: self.len, _p: PhantomData }
+    }
+}
+
+impl<T: Pod> fmt::Debug for TypedBuffer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TypedBuffer")
+            .field("buffer", &self.buffer)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+
+/// A range of elements of a `TypedBuffer`.
+pub struct BufferSlice<'b, T: Pod> {
+    buffer: &'b Buffer,
+    start: DeviceSize,
+    len: DeviceSize,
+    _p: PhantomData<T>,
+}
+
+impl<'b, T: Pod> BufferSlice<'b, T> {
+    /// Returns the number of elements in this slice.
+    pub fn len(&self) -> DeviceSize {
+        self.len
+    }
+
+    /// Returns true if this slice holds no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the offset in bytes of this slice within its buffer.
+    pub fn offset(&self) -> DeviceSize {
+        self.start * mem::size_of::<T>() as DeviceSize
+    }
+
+    /// Returns the size in bytes of this slice.
+    pub fn size(&self) -> DeviceSize {
+        self.len * mem::size_of::<T>() as DeviceSize
+    }
+
+    /// Returns the sub-slice `elements` of this slice.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `elements` is out of bounds.
+    pub fn slice(&self, elements: Range<DeviceSize>) -> BufferSlice<'b, T> {
+        assert!(elements.start <= elements.end && elements.end <= self.len,
+            "element range {:?} out of bounds for slice of length {}", elements, self.len);
+        BufferSlice {
+            buffer: self.buffer,
+            start: self.start + elements.start,
+            len: elements.end - elements.start,
+            _p: PhantomData,
+        }
+    }
+
+    /// Returns a descriptor buffer info covering this slice.
+    pub fn descriptor_info(&self) -> DescriptorBufferInfo {
+        DescriptorBufferInfo::builder()
+            .buffer(self.buffer)
+            .offset(self.offset())
+            .range(self.size())
+            .build()
+    }
+
+    /// Returns the underlying buffer.
+    pub fn buffer(&self) -> &'b Buffer {
+        self.buffer
+    }
+}
+
+impl<'b, T: Pod> Clone for BufferSlice<'b, T> {
+    fn clone(&self) -> BufferSlice<'b, T> {
+        *self
+    }
+}
+
+impl<'b, T: Pod> Copy for BufferSlice<'b, T> {}
+
+impl<'b, T: Pod> fmt::Debug for BufferSlice<'b, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BufferSlice")
+            .field("buffer", &self.buffer.handle())
+            .field("start", &self.start)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn element_sizes() {
+        assert_eq!(element_size::<u16>().unwrap(), 2);
+        assert_eq!(element_size::<[f32; 3]>().unwrap(), 12);
+        match element_size::<()>() {
+            Err(err) => match *err.kind() {
+                ErrorKind::InvalidTypedBuffer { .. } => (),
+                _ => panic!("unexpected error: {}", err),
+            },
+            Ok(size) => panic!("zero-sized type accepted with size {}", size),
+        }
+    }
+}
@@ -0,0 +1,227 @@
+use std::mem;
+use smallvec::SmallVec;
+use ::{VdResult, Device, Queue, Buffer, CommandPool, CommandBuffer, Fence, FenceStatus, Semaphore,
+    SemaphoreHandle, CommandBufferHandle, SubmitInfo, BufferCreateInfo, BufferUsageFlags,
+    SharingMode, MemoryPropertyFlags, MemoryTypeSelector, CommandPoolCreateFlags,
+    CommandBufferLevel, CommandBufferUsageFlags, FenceCreateFlags, SemaphoreCreateFlags,
+    PipelineStageFlags, AccessFlags, QueueFlags, DeviceSize, ErrorKind, Pod, QUEUE_FAMILY_IGNORED};
+use alloc::Allocation;
+
+
+/// A pending upload started by `Buffer::from_slice` or `Image::from_pixels`.
+///
+/// The uploaded resource may be used by commands submitted to the
+/// destination queue once the upload's `Fence` is signaled.
+///
+///
+/// ### Destruction
+///
+/// Dropping this `UploadToken` blocks until the upload has completed, then
+/// frees its staging buffer and command buffers.
+///
+#[derive(Debug)]
+pub struct UploadToken {
+    fence: Fence,
+    _staging_buffer: Buffer,
+    _command_buffers: SmallVec<[CommandBuffer; 2]>,
+    _semaphore: Option<Semaphore>,
+}
+
+impl UploadToken {
+    /// Returns true if the upload has completed.
+    pub fn is_complete(&self) -> VdResult<bool> {
+        match self.fence.status()? {
+            FenceStatus::Signaled => Ok(true),
+            FenceStatus::Unsignaled => Ok(false),
+            FenceStatus::Error(res) => Err(ErrorKind::ApiCall(res, "vkGetFenceStatus").into()),
+        }
+    }
+
+    /// Blocks until the upload has completed.
+    pub fn wait(self) -> VdResult<()> {
+        unsafe { self.fence.device().wait_for_fences(&[self.fence.handle()], true, u64::max_value()) }
+    }
+
+    /// Returns the fence signaled when the upload completes.
+    pub fn fence(&self) -> &Fence {
+        &self.fence
+    }
+}
+
+impl Drop for UploadToken {
+    fn drop(&mut self) {
+        unsafe {
+            self.fence.device().wait_for_fences(&[self.fence.handle()], true, u64::max_value()).ok();
+        }
+    }
+}
+
+
+/// Identifies which barrier of an upload is to be recorded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum UploadBarrier {
+    /// Makes the uploaded data visible when both queues share a family.
+    Final,
+    /// Releases ownership on the upload queue.
+    Release { src_family: u32, dst_family: u32 },
+    /// Acquires ownership on the destination queue.
+    Acquire { src_family: u32, dst_family: u32 },
+}
+
+impl UploadBarrier {
+    pub(crate) fn src_stage_mask(&self) -> PipelineStageFlags {
+        match *self {
+            UploadBarrier::Acquire { .. } => PipelineStageFlags::TOP_OF_PIPE,
+            _ => PipelineStageFlags::TRANSFER,
+        }
+    }
+
+    pub(crate) fn dst_stage_mask(&self) -> PipelineStageFlags {
+        match *self {
+            UploadBarrier::Release { .. } => PipelineStageFlags::BOTTOM_OF_PIPE,
+            _ => PipelineStageFlags::ALL_COMMANDS,
+        }
+    }
+
+    pub(crate) fn src_access_mask(&self) -> AccessFlags {
+        match *self {
+            UploadBarrier::Acquire { .. } => AccessFlags::empty(),
+            _ => AccessFlags::TRANSFER_WRITE,
+        }
+    }
+
+    pub(crate) fn dst_access_mask(&self) -> AccessFlags {
+        match *self {
+            UploadBarrier::Release { .. } => AccessFlags::empty(),
+            _ => AccessFlags::MEMORY_READ,
+        }
+    }
+
+    /// Returns the source and destination queue family indices.
+    pub(crate) fn queue_family_indices(&self) -> (u32, u32) {
+        match *self {
+            UploadBarrier::Final => (QUEUE_FAMILY_IGNORED, QUEUE_FAMILY_IGNORED),
+            UploadBarrier::Release { src_family, dst_family } |
+                UploadBarrier::Acquire { src_family, dst_family } => (src_family, dst_family),
+        }
+    }
+}
+
+
+/// Returns a queue of `device` from a dedicated transfer family if there is
+/// one, otherwise `queue`.
+fn transfer_queue(device: &Device, queue: &Queue) -> VdResult<Queue> {
+    let families = device.physical_device().queue_family_properties()?;
+    let dedicated = device.queues().iter().find(|q| {
+        families.get(q.family_index() as usize).map(|props| {
+            let flags = props.queue_flags();
+            flags.contains(QueueFlags::TRANSFER) &&
+                !flags.intersects(QueueFlags::GRAPHICS | QueueFlags::COMPUTE)
+        }).unwrap_or(false)
+    });
+    Ok(dedicated.unwrap_or(queue).clone())
+}
+
+/// Allocates a one-time-submit command buffer from a new transient pool for
+/// `queue_family_index` and begins recording it.
+pub(crate) fn begin_commands(device: &Device, queue_family_index: u32) -> VdResult<CommandBuffer> {
+    let command_pool = CommandPool::builder()
+        .flags(CommandPoolCreateFlags::TRANSIENT)
+        .queue_family_index(queue_family_index)
+        .build(device.clone())?;
+    let command_buffer = command_pool.allocate_command_buffer(CommandBufferLevel::Primary)?;
+    command_buffer.begin(CommandBufferUsageFlags::ONE_TIME_SUBMIT)?;
+    Ok(command_buffer)
+}
+
+/// Creates a host-visible staging buffer containing `data`.
+pub(crate) fn staging_buffer<T: Pod>(device: &Device, data: &[T]) -> VdResult<Buffer> {
+    let size = (mem::size_of::<T>() * data.len()) as DeviceSize;
+    let mut create_info = BufferCreateInfo::default();
+    create_info.set_size(size);
+    create_info.set_usage(BufferUsageFlags::TRANSFER_SRC);
+    create_info.set_sharing_mode(SharingMode::Exclusive);
+
+    let buffer = Buffer::with_allocation(device.clone(), &create_info, |requirements| {
+        let memory_type_index = MemoryTypeSelector::new()
+            .required(MemoryPropertyFlags::HOST_VISIBLE)
+            .preferred(MemoryPropertyFlags::HOST_COHERENT)
+            .select(device, requirements.memory_type_bits())?;
+        Allocation::dedicated(device.clone(), requirements, memory_type_index)
+    })?;
+
+    if let Some(allocation) = buffer.allocation() {
+        allocation.memory().persistent_mapping()?.write(0..size, data)?;
+    }
+    Ok(buffer)
+}
+
+/// Records and submits an upload from `staging_buffer` for use on `queue`.
+///
+/// `copy` records the transfer itself (including any preceding layout
+/// transition). `barrier` records the requested barrier for the destination
+/// resource. When `exclusive` is true and a dedicated transfer queue exists,
+/// the upload runs on it and ownership is transferred to `queue`'s family.
+pub(crate) fn upload<C, B>(device: &Device, queue: &Queue, staging_buffer: Buffer,
+        exclusive: bool, copy: C, barrier: B) -> VdResult<UploadToken>
+        where C: FnOnce(&CommandBuffer, &Buffer), B: Fn(&CommandBuffer, UploadBarrier) {
+    let upload_queue = if exclusive { transfer_queue(device, queue)? } else { queue.clone() };
+    let src_family = upload_queue.family_index();
+    let dst_family = queue.family_index();
+    let transfer_ownership = src_family != dst_family;
+
+    let command_buffer = begin_commands(device, src_family)?;
+    copy(&command_buffer, &staging_buffer);
+    barrier(&command_buffer, if transfer_ownership {
+        UploadBarrier::Release { src_family, dst_family }
+    } else {
+        UploadBarrier::Final
+    });
+    command_buffer.end()?;
+
+    let fence = Fence::new(device.clone(), FenceCreateFlags::empty())?;
+    let mut command_buffers = SmallVec::new();
+    let mut semaphore = None;
+
+    if transfer_ownership {
+        let acquire_command_buffer = begin_commands(device, dst_family)?;
+        barrier(&acquire_command_buffer, UploadBarrier::Acquire { src_family, dst_family });
+        acquire_command_buffer.end()?;
+
+        let released = Semaphore::new(device.clone(), SemaphoreCreateFlags::empty())?;
+        let semaphores: [SemaphoreHandle; 1] = [released.handle()];
+        let release_buffers: [CommandBufferHandle; 1] = [command_buffer.handle()];
+        let acquire_buffers: [CommandBufferHandle; 1] = [acquire_command_buffer.handle()];
+        let wait_dst_stage_mask = PipelineStageFlags::ALL_COMMANDS;
+
+        let release_info = SubmitInfo::builder()
+            .command_buffers(&release_buffers[..])
+            .signal_semaphores(&semaphores[..])
+            .build();
+        upload_queue.submit(&[release_info], None)?;
+
+        let acquire_info = SubmitInfo::builder()
+            .wait_semaphores(&semaphores[..])
+            .wait_dst_stage_mask(&wait_dst_stage_mask)
+            .command_buffers(&acquire_buffers[..])
+            .build();
+        queue.submit(&[acquire_info], Some(fence.handle()))?;
+
+        command_buffers.push(acquire_command_buffer);
+        semaphore = Some(released);
+    } else {
+        let command_buffer_handles: [CommandBufferHandle; 1] = [command_buffer.handle()];
+        let submit_info = SubmitInfo::builder()
+            .command_buffers(&command_buffer_handles[..])
+            .build();
+        upload_queue.submit(&[submit_info], Some(fence.handle()))?;
+    }
+    command_buffers.push(command_buffer);
+
+    Ok(UploadToken {
+        fence,
+        _staging_buffer: staging_buffer,
+        _command_buffers: command_buffers,
+        _semaphore: semaphore,
+    })
+}
@@ -1,12 +1,12 @@
 use std::ffi::{CStr, CString};
-use std::mem;
 use std::path::Path;
 use std::fs::File;
 use std::io::{Read, BufReader};
 use std::ops::Deref;
 use std::slice;
 use libc::c_char;
-use ::{VdResult, PRINT};
+use ::{VdResult, ErrorKind, PRINT};
+use reflect;
 
 /// An owned or borrowed C string representable as a pointer.
 #[derive(Debug, Clone)]
@@ -143,43 +143,40 @@ impl <'cs, 'p, 'q> From<&'p [&'q str]> for CharStrs<'cs> where 'q: 'p, 'p: 'cs,
 
 
 /// Reads a SPIR-V file into a word Vec.
+///
+/// Returns an I/O error if the file can not be read and an `InvalidSpirV`
+/// error if its contents are not a SPIR-V module (see `spir_v_words`).
 pub fn read_spir_v_file<P: AsRef<Path>>(file: P) -> VdResult<Vec<u32>> {
-    let contents = read_file(file)?;
-    assert!(contents.len() % 4 == 0);
-    assert!(mem::size_of_val(&contents[0]) == 1);
-    // TODO: Add some sort of basic verification that the file is actually
-    // spir-v.
-    unsafe {
-        let ptr = contents.as_ptr() as *const u32;
-        let new_len = contents.len() / 4;
-        let slice: &[u32] = slice::from_raw_parts(ptr, new_len);
-        let vec = slice.to_vec();
-        Ok(vec)
+    spir_v_words(&read_file(file)?)
+}
+
+/// Converts the bytes of a SPIR-V module into words in host byte order.
+///
+/// Modules written in either byte order are accepted. Returns an
+/// `InvalidSpirV` error if the length of `bytes` is not a multiple of four or
+/// the module header has an invalid magic number, an unsupported version or
+/// a zero ID bound.
+pub fn spir_v_words(bytes: &[u8]) -> VdResult<Vec<u32>> {
+    if bytes.len() % 4 != 0 {
+        return Err(ErrorKind::InvalidSpirV { reason: "length is not a multiple of four" }.into());
     }
+    let words: Vec<u32> = bytes.chunks(4)
+        .map(|b| b[0] as u32 | (b[1] as u32) << 8 | (b[2] as u32) << 16 | (b[3] as u32) << 24)
+        .collect();
+    Ok(reflect::host_order_words(&words)?.into_owned())
 }
 
 /// Reads a file into a byte Vec.
 pub fn read_file<P: AsRef<Path>>(file: P) -> VdResult<Vec<u8>> {
     let file_name = file.as_ref().display().to_string();
-    let f = File::open(file).expect(&format!("shader file '{}' not found", file_name));
-    let file_bytes = f.metadata().unwrap().len() as usize;
-    let mut contents = Vec::<u8>::with_capacity(file_bytes);
-    let mut reader = BufReader::new(f);
-    match reader.read_to_end(&mut contents) {
-        Ok(bytes) => {
-            assert_eq!(bytes, file_bytes);
-            if PRINT { println!("Read {} bytes from {}", bytes, &file_name); }
-        },
-        Err(e) => panic!("{}", e),
-    }
+    let mut contents = Vec::new();
+    File::open(file)?.read_to_end(&mut contents)?;
+    if PRINT { println!("Read {} bytes from {}", contents.len(), &file_name); }
     Ok(contents)
 }
 
 pub fn file_reader<P: AsRef<Path>>(file: P) -> VdResult<BufReader<File>> {
-    let f = File::open(file).expect("file not found");
-    // let file_bytes = f.metadata().unwrap().len() as usize;
-    // let mut contents = Vec::<u8>::with_capacity(file_bytes);
-    Ok(BufReader::new(f))
+    Ok(BufReader::new(File::open(file)?))
 }
 
 